@@ -0,0 +1,174 @@
+// Circuit size harness: compares board/ shot circuits when the board is decomposed into 128 vs 100 bits
+// @notice run with `cargo bench --bench circuit_size`; reports gate count and degree for each variant
+// @dev the board/ shot rows measure the library circuits against 100 bit variants laid out here; the library
+//      circuits range check the last limb when hashing, so only the decompose/recompose rows isolate the width
+
+use {
+    anyhow::Result,
+    battlezips_plonky2::{
+        circuits::{
+            game::{board::BoardCircuit, shot::ShotCircuit},
+            C, D, F,
+        },
+        gadgets::{
+            board::{decompose_board, hash_board, place_ship, recompose_board},
+            shot::serialize_shot,
+        },
+    },
+    plonky2::{
+        field::types::Field,
+        iop::target::{BoolTarget, Target},
+        plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig},
+    },
+};
+
+/**
+ * Decompose a serialized board into LE bits
+ * @dev the 100 bit variant range checks the unused high bits of the last limb and pads with constant zeros
+ *      to 128 bits since random access requires a power of two vector
+ *
+ * @param board - u128 target to decompose
+ * @param full - decompose all 128 bits (library behavior) or only the 100 board bits
+ * @param builder - circuit builder
+ * @return - 128 target bits representing board state
+ */
+fn decompose(
+    board: [Target; 4],
+    full: bool,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<Vec<Target>> {
+    if full {
+        return decompose_board(board, builder);
+    }
+    let mut bits = board[0..3]
+        .iter()
+        .flat_map(|x| builder.split_le_base::<2>(*x, 32))
+        .collect::<Vec<Target>>();
+    bits.extend(builder.split_le_base::<2>(board[3], 4));
+    let zero = builder.zero();
+    bits.resize(128, zero);
+    Ok(bits)
+}
+
+/**
+ * Recompose LE bits into a serialized u128
+ *
+ * @param board - 128 bit board vector
+ * @param full - recompose all 128 bits (library behavior) or only the 100 board bits
+ * @param builder - circuit builder
+ * @return - u128 target representing board state
+ */
+fn recompose(
+    board: Vec<Target>,
+    full: bool,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<[Target; 4]> {
+    if full {
+        return recompose_board(board, builder);
+    }
+    let bits: Vec<BoolTarget> = board.iter().map(|b| BoolTarget::new_unsafe(*b)).collect();
+    Ok([
+        builder.le_sum(bits[0..32].iter()),
+        builder.le_sum(bits[32..64].iter()),
+        builder.le_sum(bits[64..96].iter()),
+        builder.le_sum(bits[96..100].iter()),
+    ])
+}
+
+/**
+ * Measure the decompose -> recompose round trip in isolation
+ *
+ * @param config - circuit config
+ * @param full - 128 bit (true) or 100 bit (false) decomposition
+ * @return - (number of gates, degree bits)
+ */
+fn round_trip(config: &CircuitConfig, full: bool) -> Result<(usize, usize)> {
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+    let board_t = builder.add_virtual_target_arr::<4>();
+    let bits = decompose(board_t, full, &mut builder)?;
+    let recomposed = recompose(bits, full, &mut builder)?;
+    builder.register_public_inputs(&recomposed);
+    let gates = builder.num_gates();
+    let data = builder.build::<C>();
+    Ok((gates, data.common.degree_bits()))
+}
+
+/**
+ * Lay out the board circuit (see BoardCircuit::build) over a 100 bit decomposition
+ *
+ * @param config - circuit config
+ * @return - (number of gates, degree bits)
+ */
+fn board_circuit_100(config: &CircuitConfig) -> Result<(usize, usize)> {
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+    let ships = (0..5)
+        .map(|_| {
+            let x = builder.add_virtual_target();
+            let y = builder.add_virtual_target();
+            let z = builder.add_virtual_bool_target_safe();
+            (x, y, z)
+        })
+        .collect::<Vec<_>>();
+    let blank: [Target; 4] = builder.constants(&[F::ZERO; 4]).try_into().unwrap();
+    let board = decompose(blank, false, &mut builder)?;
+    let board = place_ship::<5>(ships[0], board, &mut builder)?;
+    let board = place_ship::<4>(ships[1], board, &mut builder)?;
+    let board = place_ship::<3>(ships[2], board, &mut builder)?;
+    let board = place_ship::<3>(ships[3], board, &mut builder)?;
+    let board = place_ship::<2>(ships[4], board, &mut builder)?;
+    let recomposed = recompose(board, false, &mut builder)?;
+    let commitment = hash_board(recomposed, &mut builder)?;
+    builder.register_public_inputs(&commitment.elements);
+    let gates = builder.num_gates();
+    let data = builder.build::<C>();
+    Ok((gates, data.common.degree_bits()))
+}
+
+/**
+ * Lay out the shot circuit (see ShotCircuit::build) over a 100 bit decomposition
+ *
+ * @param config - circuit config
+ * @return - (number of gates, degree bits)
+ */
+fn shot_circuit_100(config: &CircuitConfig) -> Result<(usize, usize)> {
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+    let board_t = builder.add_virtual_target_arr::<4>();
+    let shot_t = builder.add_virtual_target_arr::<2>();
+    let serialized = serialize_shot(shot_t[0], shot_t[1], &mut builder)?;
+    builder.register_public_input(serialized);
+    let bits = decompose(board_t, false, &mut builder)?;
+    let hit = builder.random_access(serialized, bits);
+    builder.register_public_input(hit);
+    let commitment = hash_board(board_t, &mut builder)?;
+    builder.register_public_inputs(&commitment.elements);
+    let gates = builder.num_gates();
+    let data = builder.build::<C>();
+    Ok((gates, data.common.degree_bits()))
+}
+
+fn main() -> Result<()> {
+    // shared config capable of 128 bit random access gates
+    let config = BoardCircuit::config_inner()?;
+
+    // measure each variant (library circuits only expose their degree once built)
+    let (board_100_gates, board_100_degree) = board_circuit_100(&config)?;
+    let (shot_100_gates, shot_100_degree) = shot_circuit_100(&config)?;
+    let (round_trip_128_gates, round_trip_128_degree) = round_trip(&config, true)?;
+    let (round_trip_100_gates, round_trip_100_degree) = round_trip(&config, false)?;
+    let rows = [
+        ("decompose/recompose (128 bit)", Some(round_trip_128_gates), round_trip_128_degree),
+        ("decompose/recompose (100 bit)", Some(round_trip_100_gates), round_trip_100_degree),
+        ("board circuit (library)", None, BoardCircuit::build(&config)?.data.common.degree_bits()),
+        ("board circuit (100 bit)", Some(board_100_gates), board_100_degree),
+        ("shot circuit (library)", None, ShotCircuit::build(&config)?.data.common.degree_bits()),
+        ("shot circuit (100 bit)", Some(shot_100_gates), shot_100_degree),
+    ];
+
+    // report sizes
+    println!("{:<32} {:>8} {:>12}", "circuit", "gates", "degree_bits");
+    for (name, gates, degree_bits) in rows {
+        let gates = gates.map_or("-".to_string(), |gates| gates.to_string());
+        println!("{:<32} {:>8} {:>12}", name, gates, degree_bits);
+    }
+    Ok(())
+}
@@ -1,21 +1,30 @@
 use {
     super::{
-        super::{ProofTuple, RecursiveTargets, C, D, F},
+        super::{
+            check_public_inputs,
+            recursion::{fold, wrap},
+            CircuitConfigBuilder, CircuitError, CircuitKind, ProofTuple,
+            RecursiveTargets, C, D, F,
+        },
         {GameState, GameTargets},
     },
-    crate::{circuits::game::shot::ShotCircuit, gadgets::shot::serialize_shot},
+    crate::{
+        circuits::{channel::increment_channel::StateIncrementCircuit, game::shot::ShotCircuit},
+        gadgets::shot::serialize_shot,
+        utils::board::Board,
+    },
     anyhow::Result,
     log::Level,
     plonky2::{
         field::types::{Field, PrimeField64},
         iop::{
-            target::{BoolTarget, Target},
+            target::Target,
             witness::{PartialWitness, WitnessWrite},
         },
         plonk::{
             circuit_builder::CircuitBuilder,
-            circuit_data::CircuitConfig,
             circuit_data::{CircuitData, CommonCircuitData},
+            config::GenericConfig,
             proof::ProofWithPublicInputs,
             prover::prove,
         },
@@ -23,6 +32,40 @@ use {
     },
 };
 
+// number of public inputs exported by the channel close circuit
+// (kind tag, winner, loser commitments, moves, session nonce, match index)
+pub const CLOSE_PUBLIC_INPUTS: usize = 12;
+
+// number of hits needed to sink every ship on a board (end condition for a channel)
+pub const WIN_THRESHOLD: u8 = Board::tonnage();
+
+// number of public inputs exported by the channel timeout circuit
+// (kind tag, host, guest commitments, move cap, session nonce, match index)
+pub const TIMEOUT_PUBLIC_INPUTS: usize = 12;
+
+// number of state increments after which an unfinished channel can be closed as a draw
+// @dev every cell of both boards has been shot at, so an honest game always ends before the cap
+pub const MOVE_CAP: u32 = 200;
+
+// Public outputs of a channel closed with a winner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CloseOutputs {
+    pub winner: [u64; 4],
+    pub loser: [u64; 4],
+    pub moves: u32, // number of state increments played before the channel closed
+    pub nonce: u64, // session nonce of the channel
+    pub match_index: u64, // index of the game within its series (0 = standalone game)
+}
+
+// Public outputs of a channel closed as a draw via timeout
+pub struct TimeoutOutputs {
+    pub host: [u64; 4],
+    pub guest: [u64; 4],
+    pub move_cap: u32,
+    pub nonce: u64, // session nonce of the channel
+    pub match_index: u64, // index of the game within its series (0 = standalone game)
+}
+
 /**
  * Witness the inputs to a channel close circuit
  *
@@ -32,7 +75,7 @@ use {
  * @param guest_commitment_t - targets for guest board commitment
  * @param host_damage - host damage target
  * @param guest_damage - guest damage target
- * @param turn - turn boolean target
+ * @param moves_t - move counter target
  * @return - partial witness for channel close circuit summarizing a valid battleship game
  */
 pub fn partial_witness(
@@ -42,7 +85,7 @@ pub fn partial_witness(
     guest_commitment_t: [Target; 4],
     host_damage_t: Target,
     guest_damage_t: Target,
-    turn_t: BoolTarget,
+    moves_t: Target,
 ) -> Result<PartialWitness<F>> {
     // construct partial witness
     let mut pw = PartialWitness::new();
@@ -52,7 +95,7 @@ pub fn partial_witness(
     pw.set_verifier_data_target(&state_increment_pt.verifier, &state_increment_p.1.clone());
 
     // witness host board commitment
-    let host_commitment_p: [F; 4] = state_increment_p.0.clone().public_inputs[0..4]
+    let host_commitment_p: [F; 4] = state_increment_p.0.clone().public_inputs[1..5]
         .try_into()
         .unwrap();
     pw.set_target(host_commitment_t[0], host_commitment_p[0]);
@@ -61,7 +104,7 @@ pub fn partial_witness(
     pw.set_target(host_commitment_t[3], host_commitment_p[3]);
 
     // witness guest board commitment
-    let guest_commitment_p: [F; 4] = state_increment_p.0.clone().public_inputs[4..8]
+    let guest_commitment_p: [F; 4] = state_increment_p.0.clone().public_inputs[5..9]
         .try_into()
         .unwrap();
     pw.set_target(guest_commitment_t[0], guest_commitment_p[0]);
@@ -70,95 +113,345 @@ pub fn partial_witness(
     pw.set_target(guest_commitment_t[3], guest_commitment_p[3]);
 
     // witness host damage
-    let host_damage = state_increment_p.0.clone().public_inputs[8];
+    let host_damage = state_increment_p.0.clone().public_inputs[9];
     pw.set_target(host_damage_t, host_damage);
 
     // witness guest damage
-    let guest_damage = state_increment_p.0.clone().public_inputs[9];
+    let guest_damage = state_increment_p.0.clone().public_inputs[10];
     pw.set_target(guest_damage_t, guest_damage);
 
-    // witness turn voolean
-    let turn = state_increment_p.0.clone().public_inputs[10].to_canonical_u64() != 0;
-    pw.set_bool_target(turn_t, turn);
+    // witness move counter
+    let moves = state_increment_p.0.public_inputs[13];
+    pw.set_target(moves_t, moves);
 
     // return partial witness
     Ok(pw)
 }
 
+/**
+ * Natively check whether a state increment proof meets the end condition required to close the channel
+ * @notice mirrors the in-circuit check: exactly one damage counter must equal the threshold
+ * @dev lets clients avoid a failed proving attempt when calling prove_close_channel prematurely
+ *
+ * @param state_p - state increment proof to close the channel with
+ * @return - true if prove_close_channel can succeed on this state
+ */
+pub fn can_close(state_p: &ProofWithPublicInputs<F, C, D>) -> bool {
+    match StateIncrementCircuit::decode_public(state_p.clone()) {
+        Ok(state) => (state.host_damage == WIN_THRESHOLD) != (state.guest_damage == WIN_THRESHOLD),
+        Err(_) => false,
+    }
+}
+
+// Channel Close Circuit Object
+pub struct ChannelCloseCircuit {
+    pub data: CircuitData<F, C, D>,    // circuit data for channel close
+    pub state: RecursiveTargets,       // targets for final state increment proof
+    pub host_commitment: [Target; 4],  // host board commitment target
+    pub guest_commitment: [Target; 4], // guest board commitment target
+    pub host_damage: Target,           // host damage target
+    pub guest_damage: Target,          // guest damage target
+    pub moves: Target,                 // move counter target
+}
+
+impl ChannelCloseCircuit {
+    /**
+     * Return the number of public inputs exported by proofs of this circuit
+     * @notice CLOSE_PUBLIC_INPUTS: kind tag, winner and loser commitments, move count, session nonce, match index
+     *
+     * @return - length of the public input vector to allocate when verifying
+     */
+    pub fn num_public_inputs(&self) -> usize {
+        self.data.common.num_public_inputs
+    }
+
+    /**
+     * Build the circuit finalizing a ZK State Channel by proving the end condition (17 hits) is met
+     * @notice the winner is the player whose board was NOT sunk: exactly one damage counter must equal the
+     *         threshold, and the winner is read from which one it is rather than from the turn bit
+     * @dev commitments and damage counters are bound to the public inputs of the state increment proof
+     *
+     * @param state - common circuit data of the final state increment proof
+     * @return - channel close circuit data and targets
+     */
+    pub fn build(state: &CommonCircuitData<F, D>) -> Result<ChannelCloseCircuit> {
+        // CONFIG //
+        let config = CircuitConfigBuilder::new().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // TARGETS //
+        let state_increment_pt = RecursiveTargets {
+            proof: builder.add_virtual_proof_with_pis(state),
+            verifier: builder.add_virtual_verifier_data(state.config.fri_config.cap_height),
+        };
+        let host_commitment_t = builder.add_virtual_target_arr::<4>();
+        let guest_commitment_t = builder.add_virtual_target_arr::<4>();
+        let host_damage_t = builder.add_virtual_target();
+        let guest_damage_t = builder.add_virtual_target();
+        let moves_t = builder.add_virtual_target();
+
+        // SYNTHESIZE //
+        // verify state increment proof
+        builder.verify_proof::<C>(
+            &state_increment_pt.proof,
+            &state_increment_pt.verifier,
+            state,
+        );
+        // bind game state targets to the public inputs of the state increment proof
+        let state_pis = state_increment_pt.proof.public_inputs.clone();
+        for i in 0..4 {
+            builder.connect(host_commitment_t[i], state_pis[1 + i]);
+            builder.connect(guest_commitment_t[i], state_pis[5 + i]);
+        }
+        builder.connect(host_damage_t, state_pis[9]);
+        builder.connect(guest_damage_t, state_pis[10]);
+        builder.connect(moves_t, state_pis[13]);
+        // compare both damage counters to the threshold to find the sunk board
+        let threshold = builder.constant(F::from_canonical_u8(WIN_THRESHOLD));
+        let host_sunk_t = builder.is_equal(host_damage_t, threshold);
+        let guest_sunk_t = builder.is_equal(guest_damage_t, threshold);
+        // end condition: exactly one board is sunk (fails if neither or both are)
+        let sunk_t = builder.add(host_sunk_t.target, guest_sunk_t.target);
+        let one = builder.one();
+        builder.connect(sunk_t, one);
+
+        // multiplex winner and loser boards: host sunk -> guest wins
+        let winner_commit_t = builder.add_virtual_target_arr::<4>();
+        let loser_commit_t = builder.add_virtual_target_arr::<4>();
+        for i in 0..winner_commit_t.len() {
+            let winner_commit_limb =
+                builder.select(host_sunk_t, guest_commitment_t[i], host_commitment_t[i]);
+            let loser_commit_limb =
+                builder.select(host_sunk_t, host_commitment_t[i], guest_commitment_t[i]);
+            builder.connect(winner_commit_t[i], winner_commit_limb);
+            builder.connect(loser_commit_t[i], loser_commit_limb);
+        }
+
+        // PUBLIC INPUTS //
+        // register circuit kind tag as [0]
+        CircuitKind::ChannelClose.register(&mut builder);
+        // register winner as [1..5]
+        builder.register_public_inputs(&winner_commit_t);
+        // register loser as [5..9]
+        builder.register_public_inputs(&loser_commit_t);
+        // register number of moves played as [9]
+        builder.register_public_input(moves_t);
+        // pass through the session nonce and match index bound at channel open as [10] and [11]
+        builder.register_public_input(state_pis[16]);
+        builder.register_public_input(state_pis[17]);
+
+        // construct circuit data
+        Ok(Self {
+            data: builder.build::<C>(),
+            state: state_increment_pt,
+            host_commitment: host_commitment_t,
+            guest_commitment: guest_commitment_t,
+            host_damage: host_damage_t,
+            guest_damage: guest_damage_t,
+            moves: moves_t,
+        })
+    }
+
+    /**
+     * Prove the channel close against an already built channel close circuit
+     * @dev the circuit must have been built against the common data of state_p (see GameCircuits)
+     *
+     * @param state_p - final state increment proof of the channel
+     * @return - proof exporting the winner and loser board commitments
+     */
+    pub fn prove_cached(&self, state_p: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
+        // WITNESS //
+        let pw = partial_witness(
+            state_p,
+            self.state.clone(),
+            self.host_commitment,
+            self.guest_commitment,
+            self.host_damage,
+            self.guest_damage,
+            self.moves,
+        )?;
+
+        // PROVE //
+        // generate proof
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&self.data.prover_only, &self.data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        self.data.verify(proof.clone())?;
+
+        // PROVE //
+        Ok((proof, self.data.verifier_only.clone(), self.data.common.clone()))
+    }
+}
+
 /**
  * Finalize a ZK State Channel by proving the end condition (17 hits) is met
+ * @notice the winner is chosen by which damage counter reached the threshold (see ChannelCloseCircuit::build)
+ *
+ * @param state_p - final state increment proof of the channel
+ * @return - proof exporting the winner and loser board commitments and the number of moves played
  */
 pub fn prove_close_channel(state_p: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
-    // CONFIG //
-    let config = CircuitConfig::standard_recursion_config();
-    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
-
-    // TARGETS //
-    let state_increment_pt = RecursiveTargets {
-        proof: builder.add_virtual_proof_with_pis(&state_p.2),
-        verifier: builder.add_virtual_verifier_data(state_p.2.config.fri_config.cap_height),
-    };
-    let host_commitment_t = builder.add_virtual_target_arr::<4>();
-    let guest_commitment_t = builder.add_virtual_target_arr::<4>();
-    let host_damage_t = builder.add_virtual_target();
-    let guest_damage_t = builder.add_virtual_target();
-    let turn_t = builder.add_virtual_bool_target_safe();
-
-    // SYNTHESIZE //
-    // verify state increment proof
-    builder.verify_proof::<C>(
-        &state_increment_pt.proof,
-        &state_increment_pt.verifier,
-        &state_p.2,
-    );
-    // multiplex damage to evaluate whether end condition is met
-    let threshold = builder.constant(F::from_canonical_u8(17));
-    let damage_t = builder.select(turn_t, host_damage_t, guest_damage_t);
-    let end_condition = builder.is_equal(damage_t, threshold);
-    let end_const = builder.constant_bool(true);
-    builder.connect(end_condition.target, end_const.target); // will fail if end condition is not met
-
-    // multiplex winner and loser boards
-    let winner_commit_t = builder.add_virtual_target_arr::<4>();
-    let loser_commit_t = builder.add_virtual_target_arr::<4>();
-    for i in 0..winner_commit_t.len() {
-        let winner_commit_limb =
-            builder.select(turn_t, guest_commitment_t[i], host_commitment_t[i]);
-        let loser_commit_limb = builder.select(turn_t, host_commitment_t[i], guest_commitment_t[i]);
-        builder.connect(winner_commit_t[i], winner_commit_limb);
-        builder.connect(loser_commit_t[i], loser_commit_limb);
+    let circuit = ChannelCloseCircuit::build(&state_p.2)?;
+    circuit.prove_cached(state_p)
+}
+
+/**
+ * Finalize a ZK State Channel and wrap the close proof under an alternate generic config
+ * @notice the wrapper exports the same public inputs as the close proof (see recursion::wrap)
+ *
+ * @param state_p - final state increment proof of the channel
+ * @return - close proof wrapped under config OC
+ */
+pub fn prove_close_channel_wrapped<OC: GenericConfig<D, F = F>>(
+    state_p: ProofTuple<F, C, D>,
+) -> Result<ProofTuple<F, OC, D>> {
+    wrap::<OC>(prove_close_channel(state_p)?)
+}
+
+/**
+ * Decode the winner and loser board commitments, move count, session nonce and match index exported by a channel close proof
+ *
+ * @param proof - proof from channel close circuit
+ * @return - winner commitment, loser commitment, number of moves played, session nonce and match index
+ */
+pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<CloseOutputs> {
+    // ensure proof matches the channel close circuit layout
+    // @dev timeout proofs export as many public inputs, so the kind tag is checked as well
+    check_public_inputs(&proof, CLOSE_PUBLIC_INPUTS)?;
+    if proof.public_inputs[0].to_canonical_u64() != CircuitKind::ChannelClose as u64 {
+        return Err(CircuitError::PublicInputShape.into());
     }
 
-    // PUBLIC INPUTS //
-    // register winner as [0..4]
-    builder.register_public_inputs(&winner_commit_t);
-    // register loser as [4..8]
-    builder.register_public_inputs(&loser_commit_t);
-
-    // WITNESS //
-    let pw = partial_witness(
-        state_p.clone(),
-        state_increment_pt,
-        host_commitment_t,
-        guest_commitment_t,
-        host_damage_t,
-        guest_damage_t,
-        turn_t,
-    )?;
-
-    // PROVE //
-    // construct circuit data
-    let data = builder.build::<C>();
-    // generate proof
-    let mut timing = TimingTree::new("prove", Level::Debug);
-    let proof = prove(&data.prover_only, &data.common, pw, &mut timing)?;
-    timing.print();
-
-    // verify the proof was generated correctly
-    data.verify(proof.clone())?;
-
-    // PROVE //
-    Ok((proof, data.verifier_only, data.common))
+    // decode winner commitment
+    let winner: [u64; 4] = proof.public_inputs[1..5]
+        .iter()
+        .map(|x| x.to_canonical_u64())
+        .collect::<Vec<u64>>()
+        .try_into()
+        .unwrap();
+
+    // decode loser commitment
+    let loser: [u64; 4] = proof.public_inputs[5..9]
+        .iter()
+        .map(|x| x.to_canonical_u64())
+        .collect::<Vec<u64>>()
+        .try_into()
+        .unwrap();
+
+    // decode move count
+    let moves = proof.public_inputs[9].to_canonical_u64() as u32;
+
+    // decode session nonce and match index
+    let nonce = proof.public_inputs[10].to_canonical_u64();
+    let match_index = proof.public_inputs[11].to_canonical_u64();
+
+    Ok(CloseOutputs {
+        winner,
+        loser,
+        moves,
+        nonce,
+        match_index,
+    })
+}
+
+/**
+ * Close a ZK State Channel as a draw once the move counter reaches the move cap
+ * @notice no winner is declared: both commitments are exported in host, guest order
+ *
+ * @param state_p - state increment proof to close the channel with
+ * @return - proof that the channel was closed as a draw
+ */
+pub fn prove_close_timeout(state_p: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
+    prove_close_timeout_with_cap(state_p, MOVE_CAP)
+}
+
+/**
+ * Close a ZK State Channel as a draw once the move counter reaches a given cap
+ * @notice a channel with a sunk board cannot be drawn: both damage counters must be below the threshold
+ * @dev the cap is exported publicly so verifiers can reject draws closed under a lower cap than agreed
+ * @todo: accept a draw signed by both players once ecc identities are added
+ *
+ * @param state_p - state increment proof to close the channel with
+ * @param move_cap - number of state increments required before a draw can be declared
+ * @return - proof that the channel was closed as a draw
+ */
+pub fn prove_close_timeout_with_cap(
+    state_p: ProofTuple<F, C, D>,
+    move_cap: u32,
+) -> Result<ProofTuple<F, C, D>> {
+    fold(state_p, |builder, state_pt, _| {
+        // constrain move counter >= move cap (underflow wraps outside of 32 bits and fails range check)
+        let moves_t = state_pt.public_inputs[13];
+        let move_cap_t = builder.constant(F::from_canonical_u32(move_cap));
+        let surplus_t = builder.sub(moves_t, move_cap_t);
+        builder.range_check(surplus_t, 32);
+
+        // constrain both damage counters < threshold (a sunk board underflows and fails the range check)
+        let max_damage_t = builder.constant(F::from_canonical_u8(WIN_THRESHOLD - 1));
+        for damage_t in [state_pt.public_inputs[9], state_pt.public_inputs[10]] {
+            let headroom_t = builder.sub(max_damage_t, damage_t);
+            builder.range_check(headroom_t, 8);
+        }
+
+        // PUBLIC INPUTS //
+        // register circuit kind tag as [0]
+        CircuitKind::ChannelTimeout.register(builder);
+        // register host commitment as [1..5]
+        builder.register_public_inputs(&state_pt.public_inputs[1..5]);
+        // register guest commitment as [5..9]
+        builder.register_public_inputs(&state_pt.public_inputs[5..9]);
+        // register move cap as [9]
+        builder.register_public_input(move_cap_t);
+        // pass through the session nonce and match index bound at channel open as [10] and [11]
+        builder.register_public_input(state_pt.public_inputs[16]);
+        builder.register_public_input(state_pt.public_inputs[17]);
+        Ok(())
+    })
+}
+
+/**
+ * Decode the board commitments, move cap, session nonce and match index exported by a channel timeout proof
+ *
+ * @param proof - proof from channel timeout circuit
+ * @return - host and guest commitments of the drawn channel, the move cap it was closed under, its session nonce
+ *           and match index
+ */
+pub fn decode_timeout(proof: ProofWithPublicInputs<F, C, D>) -> Result<TimeoutOutputs> {
+    // ensure proof matches the channel timeout circuit layout
+    check_public_inputs(&proof, TIMEOUT_PUBLIC_INPUTS)?;
+
+    // decode host commitment
+    let host: [u64; 4] = proof.public_inputs[1..5]
+        .iter()
+        .map(|x| x.to_canonical_u64())
+        .collect::<Vec<u64>>()
+        .try_into()
+        .unwrap();
+
+    // decode guest commitment
+    let guest: [u64; 4] = proof.public_inputs[5..9]
+        .iter()
+        .map(|x| x.to_canonical_u64())
+        .collect::<Vec<u64>>()
+        .try_into()
+        .unwrap();
+
+    // decode move cap
+    let move_cap = proof.public_inputs[9].to_canonical_u64() as u32;
+
+    // decode session nonce and match index
+    let nonce = proof.public_inputs[10].to_canonical_u64();
+    let match_index = proof.public_inputs[11].to_canonical_u64();
+
+    Ok(TimeoutOutputs {
+        host,
+        guest,
+        move_cap,
+        nonce,
+        match_index,
+    })
 }
 
 #[cfg(test)]
@@ -166,11 +459,22 @@ mod tests {
     use super::*;
     use crate::{
         circuits::{
-            channel::{increment_channel::StateIncrementCircuit, open_channel::prove_channel_open},
+            channel::{
+                increment_channel::StateIncrementCircuit,
+                open_channel::{
+                    board_nonce, decode_match_index, prove_channel_open, prove_series_open,
+                },
+                prove_channel::{prove_channel_with_progress, ChannelStage, Progress},
+                GAME_STATE_PUBLIC_INPUTS,
+            },
+            decode_any,
             game::{board::BoardCircuit, shot::ShotCircuit},
+            test_utils::{dummy_proof, mock_state_proof, GAME_NONCE},
+            DecodedPublic, WrapC,
         },
-        utils::{board::Board, ship::Ship},
+        utils::{board::Board, shot::serialize_shot, sim::resolve},
     };
+    use plonky2::plonk::circuit_data::VerifierCircuitData;
 
     // series of shots that will hit every position on the host board configuration
     const HOST_HIT_COORDS: [[u8; 2]; 18] = [
@@ -203,8 +507,8 @@ mod tests {
      * @returns a proof tuple for the open channel circuit
      */
     pub fn open_channel(host: Board, guest: Board, shot: [u8; 2]) -> Result<ProofTuple<F, C, D>> {
-        let host = BoardCircuit::prove_inner(host.clone()).unwrap();
-        let guest = BoardCircuit::prove_inner(guest.clone()).unwrap();
+        let host = BoardCircuit::prove_inner_with_nonce(host.clone(), GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest.clone(), GAME_NONCE).unwrap();
         let open_proof = prove_channel_open(host, guest, shot).unwrap();
         println!("channel opened!");
         Ok(open_proof)
@@ -233,21 +537,9 @@ mod tests {
     pub fn test_unshielded_zk_state_channel() {
         // INPUTS
         // host board (inner)
-        let host_board = Board::new(
-            Ship::new(3, 4, false),
-            Ship::new(9, 6, true),
-            Ship::new(0, 0, false),
-            Ship::new(0, 6, false),
-            Ship::new(6, 1, true),
-        );
+        let host_board = Board::standard_host();
         // guest board (inner)
-        let guest_board = Board::new(
-            Ship::new(3, 3, true),
-            Ship::new(5, 4, false),
-            Ship::new(0, 1, false),
-            Ship::new(0, 5, true),
-            Ship::new(6, 1, false),
-        );
+        let guest_board = Board::standard_guest();
         // opening shot (outer/ main opening chanel proof)
 
         // CHANNEL OPEN PROOF
@@ -282,21 +574,361 @@ mod tests {
         let state_channel_proof = prove_close_channel(previous_p.clone()).unwrap();
 
         // Check State Channel Increment Outputs
-        let winner: [u64; 4] = state_channel_proof.0.clone().public_inputs[0..4]
-            .iter()
-            .map(|x| x.to_canonical_u64())
-            .collect::<Vec<u64>>()
-            .try_into()
-            .unwrap();
-        let loser: [u64; 4] = state_channel_proof.0.clone().public_inputs[4..8]
-            .iter()
-            .map(|x| x.to_canonical_u64())
-            .collect::<Vec<u64>>()
-            .try_into()
-            .unwrap();
+        let outputs = match decode_any(state_channel_proof.0).unwrap() {
+            DecodedPublic::ChannelClose(outputs) => outputs,
+            _ => panic!("expected channel close proof"),
+        };
         let expected_winner = guest_board.hash();
         let expected_loser = host_board.hash();
-        assert_eq!(winner, expected_winner);
-        assert_eq!(loser, expected_loser);
+        assert_eq!(outputs.winner, expected_winner);
+        assert_eq!(outputs.loser, expected_loser);
+        // one move per state increment
+        assert_eq!(outputs.moves as usize, 2 * (HOST_HIT_COORDS.len() - 1));
+    }
+
+    #[test]
+    pub fn test_state_channel_progress() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        // shot log: host opens, then each coordinate is shot at the guest and then the host board
+        let mut shots = vec![HOST_HIT_COORDS[0]];
+        for i in 0..HOST_HIT_COORDS.len() - 1 {
+            shots.push(HOST_HIT_COORDS[i]);
+            shots.push(HOST_HIT_COORDS[i + 1]);
+        }
+
+        // recursively prove entire state channel (open, increments, close)
+        let mut events = Vec::<Progress>::new();
+        let state_channel_proof = prove_channel_with_progress(
+            host_board.clone(),
+            guest_board.clone(),
+            GAME_NONCE,
+            &shots,
+            |progress| {
+                println!("{:?} #{} ({:?})", progress.stage, progress.move_index, progress.elapsed);
+                events.push(progress);
+            },
+        )
+        .unwrap();
+
+        // callback fires for open, once per increment, and close
+        let increments = shots.len() - 1;
+        assert_eq!(events.len(), increments + 2);
+        assert_eq!(events[0].stage, ChannelStage::Open);
+        assert!(events[1..=increments].iter().all(|e| e.stage == ChannelStage::Increment));
+        assert_eq!(events[increments + 1].stage, ChannelStage::Close);
+
+        // same channel as the step by step proof: guest wins after one move per state increment
+        let outputs = decode_public(state_channel_proof.0).unwrap();
+        assert_eq!(outputs.winner, guest_board.hash());
+        assert_eq!(outputs.loser, host_board.hash());
+        assert_eq!(outputs.moves as usize, increments);
+
+        // native replay of the shot log agrees on the winner (true = guest)
+        let moves = shots[..increments]
+            .iter()
+            .enumerate()
+            .map(|(k, shot)| (k % 2 == 0, *shot))
+            .collect::<Vec<(bool, [u8; 2])>>();
+        assert_eq!(resolve(&host_board, &guest_board, &moves), Some(true));
+    }
+
+    /**
+     * Craft state increment public inputs with the given damage counters and turn
+     *
+     * @param host_damage - hits made on host board
+     * @param guest_damage - hits made on guest board
+     * @param turn - turn boolean after the final increment
+     * @return - proof carrying the crafted game state
+     */
+    fn state_with_damage(
+        host_damage: u8,
+        guest_damage: u8,
+        turn: bool,
+    ) -> ProofWithPublicInputs<F, C, D> {
+        let mut proof = dummy_proof(GAME_STATE_PUBLIC_INPUTS);
+        proof.public_inputs[0] = F::from_canonical_u64(CircuitKind::StateIncrement as u64);
+        proof.public_inputs[9] = F::from_canonical_u8(host_damage);
+        proof.public_inputs[10] = F::from_canonical_u8(guest_damage);
+        proof.public_inputs[11] = F::from_bool(turn);
+        proof
+    }
+
+    /**
+     * Natively walk a shot log through channel open and state increments until a board is sunk
+     *
+     * @param host - host board configuration
+     * @param guest - guest board configuration
+     * @param shots - shot log as consumed by prove_channel_with_progress
+     * @return - final game state and the number of increments applied
+     */
+    fn play(host: &Board, guest: &Board, shots: &[[u8; 2]]) -> (GameState, usize) {
+        let serialize = |shot: [u8; 2]| serialize_shot(shot[0], shot[1]).unwrap();
+        let mut state = GameState {
+            host: host.hash(),
+            guest: guest.hash(),
+            host_damage: 0,
+            guest_damage: 0,
+            turn: true,
+            shot: serialize(shots[0]),
+            moves: 0,
+            resolved_shot: 0,
+            resolved_hit: false,
+            nonce: GAME_NONCE,
+            match_index: 0,
+        };
+        for (k, shot) in shots.iter().enumerate().skip(1) {
+            // pending shot is checked against the board named by turn
+            let board = if state.turn { guest } else { host };
+            let hit = board.bits()[state.shot as usize];
+            state = state.next(hit, serialize(*shot));
+            if state.host_damage == WIN_THRESHOLD || state.guest_damage == WIN_THRESHOLD {
+                return (state, k);
+            }
+        }
+        (state, shots.len() - 1)
+    }
+
+    #[test]
+    pub fn test_close_turn_convention() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+
+        // game 1: every host coordinate is hit (guest wins); game 2: every guest coordinate is hit (host wins)
+        let mut host_sunk = vec![HOST_HIT_COORDS[0]];
+        for i in 0..HOST_HIT_COORDS.len() - 1 {
+            host_sunk.push(HOST_HIT_COORDS[i]);
+            host_sunk.push(HOST_HIT_COORDS[i + 1]);
+        }
+        let mut guest_sunk = Vec::<[u8; 2]>::new();
+        for coordinate in guest_board.occupied_coordinates() {
+            guest_sunk.push([coordinate % 10, coordinate / 10]);
+            guest_sunk.push([5, 5]);
+        }
+
+        for (shots, guest_wins) in [(host_sunk, true), (guest_sunk, false)] {
+            // walk the game under the increment circuit's turn convention
+            let (state, increments) = play(&host_board, &guest_board, &shots);
+            assert!(can_close(&mock_state_proof(&state).0));
+            // final turn names the winner: turn flipped away from the board sunk in the final increment
+            assert_eq!(state.turn, guest_wins);
+
+            // native replay of the shot log agrees on the winner
+            let moves = shots[..increments]
+                .iter()
+                .enumerate()
+                .map(|(k, shot)| (k % 2 == 0, *shot))
+                .collect::<Vec<(bool, [u8; 2])>>();
+            assert_eq!(resolve(&host_board, &guest_board, &moves), Some(guest_wins));
+
+            // close circuit names the same winner
+            let outputs =
+                decode_public(prove_close_channel(mock_state_proof(&state)).unwrap().0).unwrap();
+            let (expected_winner, expected_loser) = match guest_wins {
+                true => (guest_board.hash(), host_board.hash()),
+                false => (host_board.hash(), guest_board.hash()),
+            };
+            assert_eq!(outputs.winner, expected_winner);
+            assert_eq!(outputs.loser, expected_loser);
+            assert_eq!(outputs.moves, increments as u32);
+        }
+    }
+
+    #[test]
+    pub fn test_close_wrapped() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let state = GameState {
+            host: host_board.hash(),
+            guest: guest_board.hash(),
+            host_damage: 4,
+            guest_damage: WIN_THRESHOLD,
+            turn: false,
+            shot: 0,
+            moves: 40,
+            resolved_shot: 0,
+            resolved_hit: true,
+            nonce: GAME_NONCE,
+            match_index: 0,
+        };
+        let close = prove_close_channel(mock_state_proof(&state)).unwrap();
+        let wrapped = prove_close_channel_wrapped::<WrapC>(mock_state_proof(&state)).unwrap();
+
+        // wrapper verifies under the alternate config
+        let (proof, verifier_only, common) = wrapped;
+        let data = VerifierCircuitData::<F, WrapC, D> {
+            verifier_only,
+            common,
+        };
+        data.verify(proof.clone()).unwrap();
+
+        // wrapper re-exports the close proof's public inputs (host won)
+        assert_eq!(proof.public_inputs, close.0.public_inputs);
+        assert_eq!(
+            proof.public_inputs[1..5].iter().map(|x| x.to_canonical_u64()).collect::<Vec<u64>>(),
+            host_board.hash().to_vec()
+        );
+    }
+
+    #[test]
+    pub fn test_close_turn_disagrees_with_damage() {
+        // host board sunk, but turn claims the guest board was shot last: damage decides the winner
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let state = GameState {
+            host: host_board.hash(),
+            guest: guest_board.hash(),
+            host_damage: WIN_THRESHOLD,
+            guest_damage: 3,
+            turn: false,
+            shot: 0,
+            moves: 40,
+            resolved_shot: 0,
+            resolved_hit: true,
+            nonce: GAME_NONCE,
+            match_index: 0,
+        };
+        assert!(can_close(&mock_state_proof(&state).0));
+        let close = prove_close_channel(mock_state_proof(&state)).unwrap();
+        let outputs = decode_public(close.0).unwrap();
+        assert_eq!(outputs.winner, guest_board.hash());
+        assert_eq!(outputs.loser, host_board.hash());
+        assert_eq!(outputs.moves, 40);
+        assert_eq!(outputs.nonce, GAME_NONCE);
+    }
+
+    #[test]
+    pub fn test_close_series_game() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let match_index = 3;
+
+        // every guest coordinate is hit (host wins), the host board is shot at an empty cell in between
+        let mut shots = Vec::<[u8; 2]>::new();
+        for coordinate in guest_board.occupied_coordinates() {
+            shots.push([coordinate % 10, coordinate / 10]);
+            shots.push([5, 5]);
+        }
+
+        // open the third game of a series: both boards are bound to the session nonce, match index and opening shot
+        let nonce = board_nonce(GAME_NONCE, match_index, shots[0]).unwrap();
+        let host = BoardCircuit::prove_inner_with_nonce(host_board.clone(), nonce).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), nonce).unwrap();
+        let open_p = prove_series_open(host, guest, shots[0], GAME_NONCE, match_index).unwrap();
+        assert_eq!(decode_match_index(&open_p.0).unwrap(), match_index);
+
+        // every increment carries the match index forward
+        let mut state_p = open_p;
+        let mut k = 1;
+        while !can_close(&state_p.0) {
+            let turn = StateIncrementCircuit::decode_public(state_p.0.clone()).unwrap().turn;
+            let board = if turn { guest_board.clone() } else { host_board.clone() };
+            state_p = increment_channel_state(board, shots[k - 1], state_p, shots[k]).unwrap();
+            assert_eq!(decode_match_index(&state_p.0).unwrap(), match_index);
+            k += 1;
+        }
+
+        // the close proof names the game of the series it finished
+        let outputs = decode_public(prove_close_channel(state_p).unwrap().0).unwrap();
+        assert_eq!(outputs.winner, host_board.hash());
+        assert_eq!(outputs.nonce, GAME_NONCE);
+        assert_eq!(outputs.match_index, match_index);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_close_both_sunk() {
+        // both boards at the threshold cannot name a winner
+        let board = Board::standard_host();
+        let state = GameState {
+            host: board.hash(),
+            guest: board.hash(),
+            host_damage: WIN_THRESHOLD,
+            guest_damage: WIN_THRESHOLD,
+            turn: true,
+            shot: 0,
+            moves: 40,
+            resolved_shot: 0,
+            resolved_hit: true,
+            nonce: GAME_NONCE,
+            match_index: 0,
+        };
+        assert!(!can_close(&mock_state_proof(&state).0));
+        prove_close_channel(mock_state_proof(&state)).unwrap();
+    }
+
+    #[test]
+    pub fn test_can_close() {
+        // guest board sunk in final increment (turn flipped to host)
+        assert!(can_close(&state_with_damage(12, WIN_THRESHOLD, false)));
+        // host board sunk in final increment (turn flipped to guest)
+        assert!(can_close(&state_with_damage(WIN_THRESHOLD, 3, true)));
+    }
+
+    #[test]
+    pub fn test_close_timeout() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+
+        // open channel and play two misses (game unfinished)
+        let open_p = open_channel(host_board.clone(), guest_board.clone(), [0, 0]).unwrap();
+        let state_1 = increment_channel_state(guest_board.clone(), [0, 0], open_p, [5, 5]).unwrap();
+        let state_2 = increment_channel_state(host_board.clone(), [5, 5], state_1, [5, 5]).unwrap();
+        assert!(!can_close(&state_2.0));
+
+        // close the unfinished channel as a draw under a small move cap
+        let timeout_p = prove_close_timeout_with_cap(state_2, 2).unwrap();
+
+        // no winner is declared: proof decodes as a timeout with host, guest commitments
+        let outputs = match decode_any(timeout_p.0.clone()).unwrap() {
+            DecodedPublic::ChannelTimeout(outputs) => outputs,
+            _ => panic!("expected channel timeout proof"),
+        };
+        assert_eq!(outputs.host, host_board.hash());
+        assert_eq!(outputs.guest, guest_board.hash());
+        assert_eq!(outputs.move_cap, 2);
+        assert_eq!(outputs.nonce, GAME_NONCE);
+        assert!(decode_public(timeout_p.0).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_close_timeout_before_cap() {
+        // freshly opened channel has made no moves
+        let open_p =
+            open_channel(Board::standard_host(), Board::standard_guest(), [0, 0]).unwrap();
+        prove_close_timeout_with_cap(open_p, 1).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_close_timeout_after_sunk() {
+        // guest board sunk past the move cap: the channel must be closed with a winner, not drawn
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let state = GameState {
+            host: host_board.hash(),
+            guest: guest_board.hash(),
+            host_damage: 4,
+            guest_damage: WIN_THRESHOLD,
+            turn: false,
+            shot: 0,
+            moves: 40,
+            resolved_shot: 0,
+            resolved_hit: true,
+            nonce: GAME_NONCE,
+            match_index: 0,
+        };
+        assert!(can_close(&mock_state_proof(&state).0));
+        prove_close_timeout_with_cap(mock_state_proof(&state), 2).unwrap();
+    }
+
+    #[test]
+    pub fn test_cannot_close() {
+        // game still in progress
+        assert!(!can_close(&state_with_damage(16, 16, false)));
+        assert!(!can_close(&state_with_damage(0, 0, true)));
+        // proof of a different shape
+        assert!(!can_close(&dummy_proof(CLOSE_PUBLIC_INPUTS)));
     }
 }
@@ -1,7 +1,10 @@
 use {
     super::{
-        super::{ProofTuple, RecursiveTargets, C, D, F},
-        {GameState, GameTargets},
+        super::{
+            check_public_inputs, CircuitConfigBuilder, CircuitError, CircuitKind, ProofTuple,
+            RecursiveTargets, C, D, F,
+        },
+        shot_matches_player, GameState, GameTargets, GAME_STATE_PUBLIC_INPUTS,
     },
     crate::{circuits::game::shot::ShotCircuit, gadgets::shot::serialize_shot},
     anyhow::Result,
@@ -14,7 +17,6 @@ use {
         },
         plonk::{
             circuit_builder::CircuitBuilder,
-            circuit_data::CircuitConfig,
             circuit_data::{CircuitData, CommonCircuitData},
             proof::ProofWithPublicInputs,
             prover::prove,
@@ -31,9 +33,11 @@ pub struct StateIncrementCircuit {
     pub prev: GameTargets,          // targets for previous state increment proof
     pub shot: ShotProofTargets,     // targets for shot proof
     pub next_shot: [Target; 2],     // targets for shot coordinates
+    pub verify_after_prove: bool,   // natively verify each proof after generating it (default true)
 }
 
 // Targets for recursive shot proof verification
+#[derive(Clone)]
 pub struct ShotProofTargets {
     proof: RecursiveTargets,
     commitment: [Target; 4],
@@ -42,6 +46,16 @@ pub struct ShotProofTargets {
 }
 
 impl StateIncrementCircuit {
+    /**
+     * Return the number of public inputs exported by proofs of this circuit
+     * @notice GAME_STATE_PUBLIC_INPUTS: same layout as channel open so increments can verify either
+     *
+     * @return - length of the public input vector to allocate when verifying
+     */
+    pub fn num_public_inputs(&self) -> usize {
+        self.data.common.num_public_inputs
+    }
+
     /**
      * Witness the inner shot proof
      *
@@ -87,7 +101,7 @@ impl StateIncrementCircuit {
         );
 
         // witness hit/miss assertion
-        pw.set_bool_target(hit_t, outputs.hit);
+        pw.set_bool_target(hit_t, outputs.hit.is_hit());
 
         // witness serialized shot coordinate
         pw.set_target(shot_t, F::from_canonical_u8(outputs.shot));
@@ -147,6 +161,9 @@ impl StateIncrementCircuit {
         // witness shot
         pw.set_target(game_state_t.shot, F::from_canonical_u8(state.shot));
 
+        // witness move counter
+        pw.set_target(game_state_t.moves, F::from_canonical_u32(state.moves));
+
         // return ok with witnessed inputs in mutated pw
         Ok(())
     }
@@ -195,6 +212,7 @@ impl StateIncrementCircuit {
             guest_damage: builder.add_virtual_target(),
             turn: builder.add_virtual_bool_target_safe(),
             shot: builder.add_virtual_target(),
+            moves: builder.add_virtual_target(),
         })
     }
 
@@ -224,6 +242,9 @@ impl StateIncrementCircuit {
      * Apply copy constraints to commitments between prev state increment proof and shot proof
      * @notice multiplexes targeted commitment based on turn boolean
      * @dev board commitment checked in shot proof must be equal to the private state committed to in channel open
+     * @dev commitments and turn are bound to the public inputs of their proofs ([1..9], [11] of the game state
+     *      layout, [3..7] of the shot layout) so a shot proof against any other board cannot be witnessed
+     * @dev the hit bit is bound to [2] of the shot layout so the result of the shot cannot be flipped
      *
      * @param builder - circuit builder to construct circuit with
      * @param prev - previous state increment proof targets
@@ -235,6 +256,15 @@ impl StateIncrementCircuit {
         prev: &GameTargets,
         shot: &ShotProofTargets,
     ) -> Result<()> {
+        // bind commitment and turn targets to the public inputs of their proofs
+        let prev_pis = &prev.prev_proof.proof.public_inputs;
+        for i in 0..4 {
+            builder.connect(prev.host[i], prev_pis[1 + i]);
+            builder.connect(prev.guest[i], prev_pis[5 + i]);
+            builder.connect(shot.commitment[i], shot.proof.proof.public_inputs[3 + i]);
+        }
+        builder.connect(prev.turn.target, prev_pis[11]);
+        builder.connect(shot.hit.target, shot.proof.proof.public_inputs[2]);
         // define constained commitment targets
         let constrained_commitment = builder.add_virtual_target_arr::<4>();
         for i in 0..constrained_commitment.len() {
@@ -242,6 +272,8 @@ impl StateIncrementCircuit {
             let limb = builder.select(prev.turn, prev.guest[i], prev.host[i]);
             // constrain commitment target based on multiplexed input
             builder.connect(constrained_commitment[i], limb);
+            // constrain the board shot to be the board of the player named by turn
+            builder.connect(constrained_commitment[i], shot.commitment[i]);
         }
         // return as a success
         Ok(())
@@ -250,6 +282,8 @@ impl StateIncrementCircuit {
     /**
      * Apply copy constraints to shot coordinates between prev state increment proof and shot proof
      * @dev shot coordinate checked in shot proof must be equal to the "next shot" made in the previous state increment proof
+     * @dev both shot targets are bound to the public inputs of their proofs so the first increment re-checks the
+     *      opening shot exported by channel open ([12] of the game state layout, [1] of the shot layout)
      *
      * @param builder - circuit builder to construct circuit with
      * @param prev - previous state increment proof targets
@@ -261,6 +295,9 @@ impl StateIncrementCircuit {
         prev: &GameTargets,
         shot: &ShotProofTargets,
     ) -> Result<()> {
+        // bind shot targets to the shot exported by the previous state and to the shot checked in the shot proof
+        builder.connect(prev.shot, prev.prev_proof.proof.public_inputs[12]);
+        builder.connect(shot.shot, shot.proof.proof.public_inputs[1]);
         // constrain shot coordinate
         builder.connect(prev.shot, shot.shot);
         // return as a success
@@ -269,6 +306,7 @@ impl StateIncrementCircuit {
 
     /**
      * Increment damage counter for a player contingent on shot proof hit = true
+     * @dev previous damage counters are bound to [9] and [10] of the previous game state so they cannot be reset
      *
      * @param builder - circuit builder to construct circuit with
      * @param prev - previous state increment proof targets (contains previous damage values)
@@ -280,6 +318,10 @@ impl StateIncrementCircuit {
         prev: &GameTargets,
         shot: &ShotProofTargets,
     ) -> Result<[Target; 2]> {
+        // bind previous damage targets to the public inputs of the previous state proof
+        let prev_pis = &prev.prev_proof.proof.public_inputs;
+        builder.connect(prev.host_damage, prev_pis[9]);
+        builder.connect(prev.guest_damage, prev_pis[10]);
         // multiplex host damage value
         let host_damage_increment = builder.add(prev.host_damage, shot.hit.target);
         let host_damage = builder.select(prev.turn, prev.host_damage, host_damage_increment);
@@ -290,6 +332,33 @@ impl StateIncrementCircuit {
         Ok([host_damage, guest_damage])
     }
 
+    /**
+     * Constrain the damage counters so that a single shot changes at most one of them
+     * @notice (host_next - host_prev) + (guest_next - guest_prev) must equal the hit bit
+     * @dev invariant over apply_damage: a hit never increments both players and a miss increments neither
+     *
+     * @param builder - circuit builder to construct circuit with
+     * @param prev - damage values before the shot [host, guest]
+     * @param next - damage values after the shot [host, guest]
+     * @param hit - hit/ miss bool of the shot
+     * @return - success if the damage deltas sum to the hit bit, or error
+     */
+    pub fn constrain_damage_delta(
+        builder: &mut CircuitBuilder<F, D>,
+        prev: [Target; 2],
+        next: [Target; 2],
+        hit: BoolTarget,
+    ) -> Result<()> {
+        // compute damage delta of each player
+        let host_delta = builder.sub(next[0], prev[0]);
+        let guest_delta = builder.sub(next[1], prev[1]);
+        // constrain total damage delta to the hit bit
+        let delta = builder.add(host_delta, guest_delta);
+        builder.connect(delta, hit.target);
+        // return as a success
+        Ok(())
+    }
+
     /**
      * Build a circuit that proves the validity of a sequential state increment
      *
@@ -300,9 +369,25 @@ impl StateIncrementCircuit {
     pub fn build(
         prev: &CommonCircuitData<F, D>,
         shot: &CommonCircuitData<F, D>,
+    ) -> Result<StateIncrementCircuit> {
+        StateIncrementCircuit::build_with_salvo(prev, shot, false)
+    }
+
+    /**
+     * Build a state increment circuit with an optional salvo rule (a hit grants the shooter another shot)
+     *
+     * @param prev - common verifier data for previous state increment proof
+     * @param shot - common verifier data shot proof that informs the state increment
+     * @param salvo - whether the turn is kept on a hit instead of always flipping
+     * @return - a channel state increment circuit
+     */
+    pub fn build_with_salvo(
+        prev: &CommonCircuitData<F, D>,
+        shot: &CommonCircuitData<F, D>,
+        salvo: bool,
     ) -> Result<StateIncrementCircuit> {
         // CONFIG //
-        let config = CircuitConfig::standard_recursion_config();
+        let config = CircuitConfigBuilder::new().build();
         let mut builder = CircuitBuilder::<F, D>::new(config.clone());
 
         // TARGETS //
@@ -326,25 +411,52 @@ impl StateIncrementCircuit {
         StateIncrementCircuit::constrain_shot(&mut builder, &&prev_state_t, &shot_t)?;
         // multiplex and increment damage to host or guest based on calculated shot proof hit/miss bool
         let damage_t = StateIncrementCircuit::apply_damage(&mut builder, &prev_state_t, &shot_t)?;
+        StateIncrementCircuit::constrain_damage_delta(
+            &mut builder,
+            [prev_state_t.host_damage, prev_state_t.guest_damage],
+            damage_t,
+            shot_t.hit,
+        )?;
         // serialize next shot to be verified in subsequent state increment proof
         let next_shot_serialized_t = serialize_shot(next_shot_t[0], next_shot_t[1], &mut builder)?;
         // flip turn (0 = 0 -> 1; 1 = 0 -> 0)
         let zero = builder.constant(F::ZERO);
-        let next_turn_t = builder.is_equal(prev_state_t.turn.target, zero);
+        let flipped_turn_t = builder.is_equal(prev_state_t.turn.target, zero);
+        // salvo: keep the turn on a hit, flip on a miss
+        let next_turn_t = if salvo {
+            let turn_t =
+                builder.select(shot_t.hit, prev_state_t.turn.target, flipped_turn_t.target);
+            BoolTarget::new_unsafe(turn_t)
+        } else {
+            flipped_turn_t
+        };
+        // increment move counter carried by the previous state increment proof
+        builder.connect(prev_state_t.moves, prev_state_t.prev_proof.proof.public_inputs[13]);
+        let next_moves_t = builder.add_const(prev_state_t.moves, F::ONE);
 
         // PUBLIC INPUTS //
-        // pass through host board commitment ([0..4])
+        // register circuit kind tag ([0])
+        CircuitKind::StateIncrement.register(&mut builder);
+        // pass through host board commitment ([1..5])
         builder.register_public_inputs(&prev_state_t.host);
-        // pass through guest board commitment ([4..8])
+        // pass through guest board commitment ([5..9])
         builder.register_public_inputs(&prev_state_t.guest);
-        // register updated host damage ([8])
+        // register updated host damage ([9])
         builder.register_public_input(damage_t[0]);
-        // register updated guest damage ([9])
+        // register updated guest damage ([10])
         builder.register_public_input(damage_t[1]);
-        // register turn bool (10)
+        // register turn bool (11)
         builder.register_public_input(next_turn_t.target);
-        // register next shot (11)
+        // register next shot (12)
         builder.register_public_input(next_shot_serialized_t);
+        // register incremented move counter (13)
+        builder.register_public_input(next_moves_t);
+        // register the shot resolved by this increment (14) and its hit/ miss bool (15)
+        builder.register_public_input(prev_state_t.shot);
+        builder.register_public_input(shot_t.hit.target);
+        // pass through the session nonce (16) and match index (17) bound at channel open
+        builder.register_public_input(prev_state_t.prev_proof.proof.public_inputs[16]);
+        builder.register_public_input(prev_state_t.prev_proof.proof.public_inputs[17]);
 
         // return circuit data and ship targets
         Ok(Self {
@@ -352,6 +464,7 @@ impl StateIncrementCircuit {
             prev: prev_state_t,
             shot: shot_t,
             next_shot: next_shot_t,
+            verify_after_prove: true,
         })
     }
 
@@ -368,42 +481,165 @@ impl StateIncrementCircuit {
         shot_p: ProofTuple<F, C, D>,
         shot: [u8; 2],
     ) -> Result<ProofTuple<F, C, D>> {
-        // CIRCUIT //
+        StateIncrementCircuit::prove_with_salvo(prev_p, shot_p, shot, false)
+    }
+
+    /**
+     * Prove the increment of state in a channel with an optional salvo rule
+     *
+     * @param prev_p - previous state increment proof
+     * @param shot_p - shot proof informing this state increment
+     * @param shot - shot coordinate to be verified in next state increment
+     * @param salvo - whether the turn is kept on a hit instead of always flipping
+     * @return - proof of proper state increment
+     */
+    pub fn prove_with_salvo(
+        prev_p: ProofTuple<F, C, D>,
+        shot_p: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+        salvo: bool,
+    ) -> Result<ProofTuple<F, C, D>> {
+        // fail fast before building if the shot proof was made against the wrong player's board
+        StateIncrementCircuit::ensure_shot_player(&prev_p, &shot_p)?;
+
         // build the circuit that constrains the state increment
+        let circuit = StateIncrementCircuit::build_with_salvo(&prev_p.2, &shot_p.2, salvo)?;
+
+        // prove against the freshly built circuit
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = circuit.prove_unchecked(prev_p, shot_p, shot, &mut timing)?;
+        timing.print();
+        Ok(proof)
+    }
+
+    /**
+     * Ensure a shot proof was made against the board of the player being shot in the previous state
+     *
+     * @param prev_p - previous state increment proof
+     * @param shot_p - shot proof informing the state increment
+     * @return - WrongBoard error if the shot proof does not match the player being shot
+     */
+    fn ensure_shot_player(prev_p: &ProofTuple<F, C, D>, shot_p: &ProofTuple<F, C, D>) -> Result<()> {
+        let turn = StateIncrementCircuit::decode_public(prev_p.0.clone())?.turn;
+        if !shot_matches_player(&shot_p.0, &prev_p.0, turn) {
+            return Err(CircuitError::WrongBoard.into());
+        }
+        Ok(())
+    }
+
+    /**
+     * Prove the increment of state in a channel against an already built state increment circuit
+     * @dev the circuit must have been built against the common data of prev_p and shot_p (see GameCircuits)
+     *
+     * @param prev_p - previous state increment proof
+     * @param shot_p - shot proof informing this state increment
+     * @param shot - shot coordinate to be verified in next state increment
+     * @return - proof of proper state increment
+     */
+    pub fn prove_cached(
+        &self,
+        prev_p: ProofTuple<F, C, D>,
+        shot_p: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+    ) -> Result<ProofTuple<F, C, D>> {
+        // fail fast if the shot proof was made against the wrong player's board
+        StateIncrementCircuit::ensure_shot_player(&prev_p, &shot_p)?;
+
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = self.prove_unchecked(prev_p, shot_p, shot, &mut timing)?;
+        timing.print();
+        Ok(proof)
+    }
+
+    /**
+     * Prove the increment of state in a channel from scratch, recording it in a parent timing tree
+     *
+     * @param prev_p - previous state increment proof
+     * @param shot_p - shot proof informing this state increment
+     * @param shot - shot coordinate to be verified in next state increment
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - proof of proper state increment
+     */
+    pub fn prove_with_timing(
+        prev_p: ProofTuple<F, C, D>,
+        shot_p: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+        timing: &mut TimingTree,
+    ) -> Result<ProofTuple<F, C, D>> {
+        StateIncrementCircuit::ensure_shot_player(&prev_p, &shot_p)?;
         let circuit = StateIncrementCircuit::build(&prev_p.2, &shot_p.2)?;
+        circuit.prove_unchecked(prev_p, shot_p, shot, timing)
+    }
 
+    /**
+     * Prove the increment of state against an already built circuit as a "prove state increment" span of a
+     * parent timing tree
+     *
+     * @param prev_p - previous state increment proof
+     * @param shot_p - shot proof informing this state increment
+     * @param shot - shot coordinate to be verified in next state increment
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - proof of proper state increment
+     */
+    pub fn prove_cached_with_timing(
+        &self,
+        prev_p: ProofTuple<F, C, D>,
+        shot_p: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+        timing: &mut TimingTree,
+    ) -> Result<ProofTuple<F, C, D>> {
+        // fail fast if the shot proof was made against the wrong player's board
+        StateIncrementCircuit::ensure_shot_player(&prev_p, &shot_p)?;
+        self.prove_unchecked(prev_p, shot_p, shot, timing)
+    }
+
+    /**
+     * Witness and prove a state increment against this circuit
+     * @dev callers must have run ensure_shot_player: every public prove function does so exactly once
+     *
+     * @param prev_p - previous state increment proof
+     * @param shot_p - shot proof informing this state increment
+     * @param shot - shot coordinate to be verified in next state increment
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - proof of proper state increment
+     */
+    fn prove_unchecked(
+        &self,
+        prev_p: ProofTuple<F, C, D>,
+        shot_p: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+        timing: &mut TimingTree,
+    ) -> Result<ProofTuple<F, C, D>> {
         // WITNESS //
         let mut pw = PartialWitness::new();
         // witness the previous state increment proof
-        StateIncrementCircuit::witness_prev_state(&mut pw, prev_p, circuit.prev)?;
+        StateIncrementCircuit::witness_prev_state(&mut pw, prev_p, self.prev.clone())?;
         // witness inner shot proof
         StateIncrementCircuit::witness_shot(
             &mut pw,
             shot_p,
-            circuit.shot.proof,
-            circuit.shot.commitment,
-            circuit.shot.hit,
-            circuit.shot.shot
+            self.shot.proof.clone(),
+            self.shot.commitment,
+            self.shot.hit,
+            self.shot.shot
         )?;
         // witness next shot
-        StateIncrementCircuit::witness_next_shot(&mut pw, shot, circuit.next_shot)?;
+        StateIncrementCircuit::witness_next_shot(&mut pw, shot, self.next_shot)?;
 
         // PROVE //
         // generate proof
-        let mut timing = TimingTree::new("prove", Level::Debug);
-        let proof = prove(
-            &circuit.data.prover_only,
-            &circuit.data.common,
-            pw,
-            &mut timing,
-        )?;
-        timing.print();
+        timing.push("prove state increment", Level::Debug);
+        let proof = prove(&self.data.prover_only, &self.data.common, pw, timing);
+        timing.pop();
+        let proof = proof?;
 
-        // verify the proof was generated correctly
-        circuit.data.verify(proof.clone())?;
+        // verify the proof was generated correctly (skipped by trusted batch provers)
+        if self.verify_after_prove {
+            self.data.verify(proof.clone())?;
+        }
 
         // PROVE //
-        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+        Ok((proof, self.data.verifier_only.clone(), self.data.common.clone()))
     }
 
     /**
@@ -414,8 +650,11 @@ impl StateIncrementCircuit {
      * @return - GameState object that formats the previous state logically
      */
     pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<GameState> {
+        // ensure proof matches the game state layout
+        check_public_inputs(&proof, GAME_STATE_PUBLIC_INPUTS)?;
+
         // decode host board commitment
-        let host = proof.public_inputs.clone()[0..4]
+        let host = proof.public_inputs.clone()[1..5]
             .iter()
             .map(|x| x.to_canonical_u64())
             .collect::<Vec<u64>>()
@@ -423,7 +662,7 @@ impl StateIncrementCircuit {
             .unwrap();
 
         // decode guest board commitment
-        let guest = proof.public_inputs.clone()[4..8]
+        let guest = proof.public_inputs.clone()[5..9]
             .iter()
             .map(|x| x.to_canonical_u64())
             .collect::<Vec<u64>>()
@@ -431,16 +670,27 @@ impl StateIncrementCircuit {
             .unwrap();
 
         // decode # of htis made on host's board
-        let host_damage = proof.public_inputs.clone()[8].to_canonical_u64() as u8;
+        let host_damage = proof.public_inputs.clone()[9].to_canonical_u64() as u8;
 
         // decode # of hits made on guest's board
-        let guest_damage = proof.public_inputs.clone()[9].to_canonical_u64() as u8;
+        let guest_damage = proof.public_inputs.clone()[10].to_canonical_u64() as u8;
 
         // decode turn boolean specifying whether it is the host's turn or the guest's turn
-        let turn = proof.public_inputs.clone()[10].to_canonical_u64() != 0;
+        let turn = proof.public_inputs.clone()[11].to_canonical_u64() != 0;
 
         // decode the serialized shot coordinate
-        let shot = proof.public_inputs.clone()[11].to_canonical_u64() as u8;
+        let shot = proof.public_inputs.clone()[12].to_canonical_u64() as u8;
+
+        // decode the number of state increments applied to the channel
+        let moves = proof.public_inputs[13].to_canonical_u64() as u32;
+
+        // decode the shot resolved by the increment and its hit/ miss bool (0, false from channel open)
+        let resolved_shot = proof.public_inputs[14].to_canonical_u64() as u8;
+        let resolved_hit = proof.public_inputs[15].to_canonical_u64() != 0;
+
+        // decode the session nonce and match index bound at channel open
+        let nonce = proof.public_inputs[16].to_canonical_u64();
+        let match_index = proof.public_inputs[17].to_canonical_u64();
 
         // return the state marshalled into a logical option
         Ok(GameState {
@@ -450,6 +700,11 @@ impl StateIncrementCircuit {
             guest_damage,
             turn,
             shot,
+            moves,
+            resolved_shot,
+            resolved_hit,
+            nonce,
+            match_index,
         })
     }
 }
@@ -463,36 +718,77 @@ mod tests {
                 board::BoardCircuit,
                 shot::ShotCircuit
             },
-            channel::open_channel::prove_channel_open
+            channel::{is_host_opening, open_channel::prove_channel_open, OPENING_TURN},
+            test_utils::{dummy_proof, GAME_NONCE},
+            CircuitError,
+        },
+        utils::board::Board,
+    };
+    use {
+        log::{LevelFilter, Log, Metadata, Record},
+        std::{
+            sync::Mutex,
+            thread::{self, ThreadId},
         },
-        utils::{board::Board, ship::Ship},
     };
 
+    // log lines emitted by TimingTree::print, tagged with the emitting thread so parallel tests can be told apart
+    static PRINTED: Mutex<Vec<(ThreadId, String)>> = Mutex::new(Vec::new());
+
+    // logger recording every line instead of writing it out
+    struct SpanRecorder;
+
+    impl Log for SpanRecorder {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            let line = record.args().to_string();
+            PRINTED.lock().unwrap_or_else(|e| e.into_inner()).push((thread::current().id(), line));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static SPAN_RECORDER: SpanRecorder = SpanRecorder;
+
+    /**
+     * Print a timing tree through the span recorder and return the lines printed by this thread
+     * @dev TimingTree has no accessors or Debug impl, so its spans are read back from what print() logs
+     *
+     * @param timing - timing tree to print
+     * @return - printed lines ("| " per depth, then "<secs>s to <span>")
+     */
+    fn printed_spans(timing: &TimingTree) -> Vec<String> {
+        // the recorder may already be installed by another test in this process
+        let _ = log::set_logger(&SPAN_RECORDER);
+        log::set_max_level(LevelFilter::Debug);
+        let id = thread::current().id();
+        PRINTED.lock().unwrap_or_else(|e| e.into_inner()).retain(|(thread, _)| *thread != id);
+        timing.print();
+        PRINTED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|(thread, _)| *thread == id)
+            .map(|(_, line)| line.clone())
+            .collect()
+    }
+
     #[test]
     pub fn test_unshielded_state_increment_small() {
         // INPUTS
         // host board (inner)
-        let host_board = Board::new(
-            Ship::new(3, 4, false),
-            Ship::new(9, 6, true),
-            Ship::new(0, 0, false),
-            Ship::new(0, 6, false),
-            Ship::new(6, 1, true),
-        );
+        let host_board = Board::standard_host();
         // guest board (inner)
-        let guest_board = Board::new(
-            Ship::new(3, 3, true),
-            Ship::new(5, 4, false),
-            Ship::new(0, 1, false),
-            Ship::new(0, 5, true),
-            Ship::new(6, 1, false),
-        );
+        let guest_board = Board::standard_guest();
         // opening shot (outer/ main opening chanel proof)
         let shot_0 = [3u8, 4];
 
         // CHANNEL OPEN PROOF
-        let host = BoardCircuit::prove_inner(host_board.clone()).unwrap();
-        let guest = BoardCircuit::prove_inner(guest_board.clone()).unwrap();
+        let host = BoardCircuit::prove_inner_with_nonce(host_board.clone(), GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
         let open_proof = prove_channel_open(host, guest, shot_0).unwrap();
         println!("channel opened!");
 
@@ -517,31 +813,49 @@ mod tests {
         println!("state increment #2");
     }
 
+    #[test]
+    pub fn test_shared_timing_tree() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let shot_0 = [3u8, 4];
+
+        // every proof of the game records its span into one tree
+        let mut timing = TimingTree::new("game", Level::Debug);
+        let host = BoardCircuit::prove_inner_with_timing(host_board, GAME_NONCE, &mut timing).unwrap();
+        let guest =
+            BoardCircuit::prove_inner_with_timing(guest_board.clone(), GAME_NONCE, &mut timing).unwrap();
+        let open_proof = prove_channel_open(host, guest, shot_0).unwrap();
+        let shot_proof = ShotCircuit::prove_inner_with_timing(guest_board, shot_0, &mut timing).unwrap();
+        StateIncrementCircuit::prove_with_timing(open_proof, shot_proof, [0, 0], &mut timing).unwrap();
+
+        // the spans of each nested proof are direct children of the single game tree
+        let spans = printed_spans(&timing);
+        let children = |name: &str| {
+            let suffix = format!("s to {}", name);
+            spans
+                .iter()
+                .filter(|line| line.starts_with("| ") && !line.starts_with("| | ") && line.ends_with(&suffix))
+                .count()
+        };
+        assert!(spans[0].ends_with("s to game"));
+        assert_eq!(children("prove board"), 2);
+        assert_eq!(children("prove shot"), 1);
+        assert_eq!(children("prove state increment"), 1);
+    }
+
     #[test]
     pub fn test_unshielded_state_increment_med() {
         // INPUTS
         // host board (inner)
-        let host_board = Board::new(
-            Ship::new(3, 4, false),
-            Ship::new(9, 6, true),
-            Ship::new(0, 0, false),
-            Ship::new(0, 6, false),
-            Ship::new(6, 1, true),
-        );
+        let host_board = Board::standard_host();
         // guest board (inner)
-        let guest_board = Board::new(
-            Ship::new(3, 3, true),
-            Ship::new(5, 4, false),
-            Ship::new(0, 1, false),
-            Ship::new(0, 5, true),
-            Ship::new(6, 1, false),
-        );
+        let guest_board = Board::standard_guest();
         // opening shot (outer/ main opening chanel proof)
         let shot_0 = [0u8, 0]; // miss
 
         // CHANNEL OPEN PROOF
-        let host = BoardCircuit::prove_inner(host_board.clone()).unwrap();
-        let guest = BoardCircuit::prove_inner(guest_board.clone()).unwrap();
+        let host = BoardCircuit::prove_inner_with_nonce(host_board.clone(), GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
         let open_proof = prove_channel_open(host, guest, shot_0).unwrap();
         println!("channel opened!");
 
@@ -613,6 +927,289 @@ mod tests {
         let expected_guest_damage = 0u8;
         assert_eq!(output.host_damage, expected_host_damage);
         assert_eq!(output.guest_damage, expected_guest_damage);
+        assert_eq!(output.moves, 6);
+    }
+
+    #[test]
+    pub fn test_salvo_state_increment() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+
+        // host opens on the guest carrier
+        let host = BoardCircuit::prove_inner_with_nonce(host_board, GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
+        let mut state = prove_channel_open(host, guest, [3, 3]).unwrap();
+
+        // two hits on the guest carrier keep the turn, then a miss flips it
+        let shots = [([3u8, 3], [3u8, 4]), ([3, 4], [0, 0]), ([0, 0], [0, 0])];
+        let mut turns = Vec::<bool>::new();
+        for (shot, next_shot) in shots {
+            let shot_proof = ShotCircuit::prove_inner(guest_board.clone(), shot).unwrap();
+            state = StateIncrementCircuit::prove_with_salvo(state, shot_proof, next_shot, true)
+                .unwrap();
+            turns.push(StateIncrementCircuit::decode_public(state.0.clone()).unwrap().turn);
+        }
+        assert_eq!(turns, vec![true, true, false]);
+
+        let output = StateIncrementCircuit::decode_public(state.0).unwrap();
+        assert_eq!(output.guest_damage, 2);
+        assert_eq!(output.host_damage, 0);
+        assert_eq!(output.moves, 3);
+    }
+
+    #[test]
+    pub fn test_decode_public_shape() {
+        // truncated public inputs
+        let err = StateIncrementCircuit::decode_public(dummy_proof(GAME_STATE_PUBLIC_INPUTS - 1))
+            .err()
+            .unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::PublicInputShape));
+
+        // over-long public inputs
+        let err = StateIncrementCircuit::decode_public(dummy_proof(GAME_STATE_PUBLIC_INPUTS + 1))
+            .err()
+            .unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::PublicInputShape));
+
+        // well formed public inputs
+        let proof = dummy_proof(GAME_STATE_PUBLIC_INPUTS);
+        assert!(StateIncrementCircuit::decode_public(proof).is_ok());
+    }
+
+    #[test]
+    pub fn test_opening_shot_rechecked() {
+        // open channel with an opening shot against the guest
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), GAME_NONCE).unwrap();
+        let guest_board = Board::standard_guest();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
+        let open_p = prove_channel_open(host, guest, [3u8, 4]).unwrap();
+
+        // first increment checks the opening shot exported by channel open
+        let shot_p = ShotCircuit::prove_inner(guest_board, [3u8, 4]).unwrap();
+        let opening = StateIncrementCircuit::decode_public(open_p.0.clone()).unwrap().shot;
+        assert_eq!(opening, ShotCircuit::decode_public(shot_p.0.clone()).unwrap().shot);
+        let state_p = StateIncrementCircuit::prove(open_p, shot_p, [0, 0]).unwrap();
+        let state = StateIncrementCircuit::decode_public(state_p.0).unwrap();
+        assert_eq!(state.guest_damage, 1);
+        assert_eq!(state.shot, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_forged_opening_shot() {
+        // open channel with opening shot (3, 4) but prove a shot at (0, 0)
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), GAME_NONCE).unwrap();
+        let guest_board = Board::standard_guest();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
+        let open_p = prove_channel_open(host, guest, [3u8, 4]).unwrap();
+        let shot_p = ShotCircuit::prove_inner(guest_board, [0u8, 0]).unwrap();
+        let circuit = StateIncrementCircuit::build(&open_p.2, &shot_p.2).unwrap();
+
+        // witness the previous state honestly except for a forged shot matching the shot proof
+        let state = StateIncrementCircuit::decode_public(open_p.0.clone()).unwrap();
+        let mut pw = PartialWitness::new();
+        pw.set_proof_with_pis_target(&circuit.prev.prev_proof.proof, &open_p.0);
+        pw.set_verifier_data_target(&circuit.prev.prev_proof.verifier, &open_p.1);
+        for i in 0..4 {
+            pw.set_target(circuit.prev.host[i], F::from_canonical_u64(state.host[i]));
+            pw.set_target(circuit.prev.guest[i], F::from_canonical_u64(state.guest[i]));
+        }
+        pw.set_target(circuit.prev.host_damage, F::ZERO);
+        pw.set_target(circuit.prev.guest_damage, F::ZERO);
+        pw.set_bool_target(circuit.prev.turn, state.turn);
+        pw.set_target(circuit.prev.shot, F::ZERO);
+        pw.set_target(circuit.prev.moves, F::ZERO);
+        StateIncrementCircuit::witness_shot(
+            &mut pw,
+            shot_p,
+            circuit.shot.proof,
+            circuit.shot.commitment,
+            circuit.shot.hit,
+            circuit.shot.shot,
+        )
+        .unwrap();
+        StateIncrementCircuit::witness_next_shot(&mut pw, [0, 0], circuit.next_shot).unwrap();
+
+        // forged shot conflicts with the opening shot exported by channel open
+        circuit.data.prove(pw).unwrap();
     }
 
+    /**
+     * Prove the first increment of a channel with the witnessed prior host damage and hit bit overridden
+     * @dev the opening shot (3, 4) hits the guest carrier, and channel open exports zero damage
+     *
+     * @param host_damage - prior host damage to witness
+     * @param hit - hit bit to witness for the shot proof
+     */
+    fn prove_forged_increment(host_damage: u8, hit: bool) {
+        let guest_board = Board::standard_guest();
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
+        let open_p = prove_channel_open(host, guest, [3u8, 4]).unwrap();
+        let shot_p = ShotCircuit::prove_inner(guest_board, [3u8, 4]).unwrap();
+        let circuit = StateIncrementCircuit::build(&open_p.2, &shot_p.2).unwrap();
+
+        // witness everything from the proofs except the forged values
+        let state = StateIncrementCircuit::decode_public(open_p.0.clone()).unwrap();
+        let outputs = ShotCircuit::decode_public(shot_p.0.clone()).unwrap();
+        let mut pw = PartialWitness::new();
+        pw.set_proof_with_pis_target(&circuit.prev.prev_proof.proof, &open_p.0);
+        pw.set_verifier_data_target(&circuit.prev.prev_proof.verifier, &open_p.1);
+        pw.set_target_arr(circuit.prev.host, state.host.map(F::from_canonical_u64));
+        pw.set_target_arr(circuit.prev.guest, state.guest.map(F::from_canonical_u64));
+        pw.set_target(circuit.prev.host_damage, F::from_canonical_u8(host_damage));
+        pw.set_target(circuit.prev.guest_damage, F::from_canonical_u8(state.guest_damage));
+        pw.set_bool_target(circuit.prev.turn, state.turn);
+        pw.set_target(circuit.prev.shot, F::from_canonical_u8(state.shot));
+        pw.set_target(circuit.prev.moves, F::from_canonical_u32(state.moves));
+        pw.set_proof_with_pis_target(&circuit.shot.proof.proof, &shot_p.0);
+        pw.set_verifier_data_target(&circuit.shot.proof.verifier, &shot_p.1);
+        pw.set_target_arr(circuit.shot.commitment, outputs.commitment.map(F::from_canonical_u64));
+        pw.set_bool_target(circuit.shot.hit, hit);
+        pw.set_target(circuit.shot.shot, F::from_canonical_u8(outputs.shot));
+        StateIncrementCircuit::witness_next_shot(&mut pw, [0, 0], circuit.next_shot).unwrap();
+
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    pub fn test_honest_increment_witness() {
+        // the same witness with the values taken from the proofs goes through
+        prove_forged_increment(0, true);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_forged_prior_damage() {
+        // host damage conflicts with the zero damage exported by channel open
+        prove_forged_increment(5, true);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_forged_hit() {
+        // (3, 4) hits the guest carrier, so a miss conflicts with the shot proof
+        prove_forged_increment(0, false);
+    }
+
+    #[test]
+    pub fn test_wrong_board_rejected() {
+        // open channel: host shoots the guest board first
+        let host_board = Board::standard_host();
+        let host = BoardCircuit::prove_inner_with_nonce(host_board.clone(), GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(Board::standard_guest(), GAME_NONCE).unwrap();
+        let open_p = prove_channel_open(host, guest, [3u8, 4]).unwrap();
+
+        // shot proof made against the host's own board is rejected before proving
+        let shot_p = ShotCircuit::prove_inner(host_board, [3u8, 4]).unwrap();
+        let err = StateIncrementCircuit::prove(open_p, shot_p, [0, 0]).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::WrongBoard));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_damage_delta_both_players() {
+        // damage delta invariant over free damage targets
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfigBuilder::new().build());
+        let prev_t = builder.add_virtual_target_arr::<2>();
+        let next_t = builder.add_virtual_target_arr::<2>();
+        let hit_t = builder.add_virtual_bool_target_safe();
+        StateIncrementCircuit::constrain_damage_delta(&mut builder, prev_t, next_t, hit_t).unwrap();
+        let data = builder.build::<C>();
+
+        // a single hit crafted to increment both host and guest damage
+        let mut pw = PartialWitness::new();
+        pw.set_target_arr(prev_t, [F::ZERO, F::ZERO]);
+        pw.set_target_arr(next_t, [F::ONE, F::ONE]);
+        pw.set_bool_target(hit_t, true);
+        data.prove(pw).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_wrong_board_constrained() {
+        // open channel: host shoots the guest board first
+        let host_board = Board::standard_host();
+        let host = BoardCircuit::prove_inner_with_nonce(host_board.clone(), GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(Board::standard_guest(), GAME_NONCE).unwrap();
+        let open_p = prove_channel_open(host, guest, [3u8, 4]).unwrap();
+        // shot proof at the opening shot but against the host's own board
+        let shot_p = ShotCircuit::prove_inner(host_board, [3u8, 4]).unwrap();
+        let circuit = StateIncrementCircuit::build(&open_p.2, &shot_p.2).unwrap();
+
+        // witness everything honestly, skipping the native board check made by prove
+        let state = StateIncrementCircuit::decode_public(open_p.0.clone()).unwrap();
+        let mut pw = PartialWitness::new();
+        StateIncrementCircuit::witness_prev_state(&mut pw, open_p, circuit.prev.clone()).unwrap();
+        StateIncrementCircuit::witness_shot(
+            &mut pw,
+            shot_p,
+            circuit.shot.proof.clone(),
+            circuit.shot.commitment,
+            circuit.shot.hit,
+            circuit.shot.shot,
+        )
+        .unwrap();
+        StateIncrementCircuit::witness_next_shot(&mut pw, [0, 0], circuit.next_shot).unwrap();
+        assert!(state.turn);
+
+        // host commitment checked in the shot proof conflicts with the guest board named by turn
+        circuit.data.prove(pw).unwrap();
+    }
+
+    #[test]
+    pub fn test_opening_turn_flows() {
+        // host opens with a hit on the guest carrier
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), GAME_NONCE).unwrap();
+        let guest_board = Board::standard_guest();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
+        let open_p = prove_channel_open(host, guest, [3u8, 3]).unwrap();
+        assert!(is_host_opening(&open_p.0));
+        assert_eq!(StateIncrementCircuit::decode_public(open_p.0.clone()).unwrap().turn, OPENING_TURN);
+
+        // first increment reads the opening turn: the hit is charged to the guest and the turn passes to the host
+        let shot_p = ShotCircuit::prove_inner(guest_board, [3u8, 3]).unwrap();
+        let state_p = StateIncrementCircuit::prove(open_p, shot_p, [0, 0]).unwrap();
+        let state = StateIncrementCircuit::decode_public(state_p.0.clone()).unwrap();
+        assert_eq!((state.host_damage, state.guest_damage), (0, 1));
+        assert_eq!(state.turn, !OPENING_TURN);
+        assert!(!is_host_opening(&state_p.0));
+
+        // a proof without public inputs is not an opening
+        assert!(!is_host_opening(&dummy_proof(0)));
+    }
+
+    #[test]
+    pub fn test_resolved_moves_from_public_inputs() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        // alternating guest board, host board shots: hit, miss, miss, hit
+        let shots = [[3u8, 3], [5, 5], [9, 9], [3, 4], [0, 0]];
+
+        let host = BoardCircuit::prove_inner_with_nonce(host_board.clone(), GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
+        let mut state_p = prove_channel_open(host, guest, shots[0]).unwrap();
+        let open = StateIncrementCircuit::decode_public(state_p.0.clone()).unwrap();
+        assert_eq!((open.resolved_shot, open.resolved_hit), (0, false));
+
+        // chain increments, keeping only the public inputs of each proof
+        let mut public_inputs = Vec::new();
+        for k in 1..shots.len() {
+            let board = if k % 2 == 1 { guest_board.clone() } else { host_board.clone() };
+            let shot_p = ShotCircuit::prove_inner(board, shots[k - 1]).unwrap();
+            state_p = StateIncrementCircuit::prove(state_p, shot_p, shots[k]).unwrap();
+            public_inputs.push(state_p.0.clone());
+        }
+
+        // reconstruct the resolved move sequence from the public inputs alone
+        let moves = public_inputs
+            .into_iter()
+            .map(|proof| {
+                let state = StateIncrementCircuit::decode_public(proof).unwrap();
+                (state.resolved_shot, state.resolved_hit)
+            })
+            .collect::<Vec<(u8, bool)>>();
+        assert_eq!(moves, vec![(33, true), (55, false), (99, false), (43, true)]);
+    }
 }
@@ -1,12 +1,31 @@
 use {
-    super::RecursiveTargets,
-    plonky2::iop::target::{Target, BoolTarget},
+    super::{
+        game::{board::BoardCircuit, shot::ShotCircuit},
+        CircuitError, CircuitKind, ProofTuple, RecursiveTargets, C, D, F,
+    },
+    crate::utils::{board::Board, commitment::to_hex},
+    close_channel::WIN_THRESHOLD,
+    anyhow::{ensure, Result},
+    increment_channel::StateIncrementCircuit,
+    plonky2::{
+        field::types::PrimeField64,
+        iop::target::{Target, BoolTarget},
+        plonk::proof::ProofWithPublicInputs,
+    },
+    std::fmt,
 };
 
 pub mod open_channel;
 pub mod increment_channel;
 pub mod close_channel;
+pub mod prove_channel;
+pub mod player_keys;
+pub mod win_proof;
 
+// number of public inputs exported by channel open and state increment proofs (kind tag + GameState)
+pub const GAME_STATE_PUBLIC_INPUTS: usize = 18;
+
+#[derive(Clone)]
 pub struct GameTargets {
     // @dev underconstrained without ecc keypairs
     pub prev_proof: RecursiveTargets,
@@ -15,14 +34,410 @@ pub struct GameTargets {
     pub host_damage: Target, // track hits on host board
     pub guest_damage: Target, // track hits on gues board
     pub turn: BoolTarget, // define the turn order
-    pub shot: Target // serialized shot coordinate to check
+    pub shot: Target, // serialized shot coordinate to check
+    pub moves: Target // number of state increments applied to the channel
 }
 
+// turn exported by channel open: the host makes the opening shot, so it is checked against the guest board first
+pub const OPENING_TURN: bool = true;
+
+// Turn convention shared by channel open, state increments and channel close:
+//  - turn names the board the pending shot is checked against: true = guest board (host shooting), false = host board
+//  - channel open sets turn = true (the host's opening shot is checked against the guest board)
+//  - each state increment checks the pending shot against the board named by turn, then flips turn
+//  - at close, the board shot in the final increment is therefore the one NOT named by turn:
+//    turn = true -> host board was shot last (host damage must be 17, guest wins)
+//    turn = false -> guest board was shot last (guest damage must be 17, host wins)
 pub struct GameState {
     pub host: [u64; 4],
     pub guest: [u64; 4],
     pub host_damage: u8,
     pub guest_damage: u8,
     pub turn: bool,
-    pub shot: u8
-}
\ No newline at end of file
+    pub shot: u8,
+    pub moves: u32,
+    pub resolved_shot: u8, // serialized shot resolved by the increment producing this state (0 at channel open)
+    pub resolved_hit: bool, // whether the resolved shot hit (false at channel open)
+    pub nonce: u64, // session nonce of the channel, unique per game or best-of-N series (never 0)
+    pub match_index: u64, // index of the game within its series (0 = standalone game)
+}
+
+// byte length of a serialized GameState
+// (2 x 32 byte commitments, damage x 2, turn, shot, u32 moves, resolved shot/ hit, u64 session nonce, u64 match index)
+pub const GAME_STATE_BYTES: usize = 90;
+
+impl GameState {
+    /**
+     * Natively apply a state increment to the game state
+     * @notice mirrors StateIncrementCircuit (without the salvo rule) under the turn convention above
+     *
+     * @param hit - whether the pending shot hit the board named by turn
+     * @param next_shot - serialized shot coordinate to be checked by the following increment
+     * @return - game state after the increment
+     */
+    pub fn next(&self, hit: bool, next_shot: u8) -> GameState {
+        GameState {
+            host: self.host,
+            guest: self.guest,
+            host_damage: self.host_damage + (!self.turn && hit) as u8,
+            guest_damage: self.guest_damage + (self.turn && hit) as u8,
+            turn: !self.turn,
+            shot: next_shot,
+            moves: self.moves + 1,
+            resolved_shot: self.shot,
+            resolved_hit: hit,
+            nonce: self.nonce,
+            match_index: self.match_index,
+        }
+    }
+
+    /**
+     * Serialize the game state into a fixed size byte layout
+     * @notice commitments as LE u64 limbs, then host damage, guest damage, turn, shot, LE u32 moves, resolved shot,
+     *         resolved hit, LE u64 session nonce, LE u64 match index
+     *
+     * @return - serialized game state
+     */
+    pub fn to_bytes(&self) -> [u8; GAME_STATE_BYTES] {
+        let mut bytes = [0u8; GAME_STATE_BYTES];
+        for i in 0..4 {
+            bytes[i * 8..(i + 1) * 8].copy_from_slice(&self.host[i].to_le_bytes());
+            bytes[32 + i * 8..32 + (i + 1) * 8].copy_from_slice(&self.guest[i].to_le_bytes());
+        }
+        bytes[64] = self.host_damage;
+        bytes[65] = self.guest_damage;
+        bytes[66] = self.turn as u8;
+        bytes[67] = self.shot;
+        bytes[68..72].copy_from_slice(&self.moves.to_le_bytes());
+        bytes[72] = self.resolved_shot;
+        bytes[73] = self.resolved_hit as u8;
+        bytes[74..82].copy_from_slice(&self.nonce.to_le_bytes());
+        bytes[82..90].copy_from_slice(&self.match_index.to_le_bytes());
+        bytes
+    }
+
+    /**
+     * Deserialize a game state from the fixed size byte layout produced by to_bytes
+     *
+     * @param bytes - serialized game state
+     * @return - game state, or error if the layout is malformed
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<GameState> {
+        ensure!(
+            bytes.len() == GAME_STATE_BYTES,
+            "game state must be {} bytes, got {}",
+            GAME_STATE_BYTES,
+            bytes.len()
+        );
+        ensure!(bytes[66] <= 1, "game state turn byte must be 0 or 1");
+        ensure!(bytes[73] <= 1, "game state resolved hit byte must be 0 or 1");
+        let limb = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        Ok(GameState {
+            host: [limb(0), limb(8), limb(16), limb(24)],
+            guest: [limb(32), limb(40), limb(48), limb(56)],
+            host_damage: bytes[64],
+            guest_damage: bytes[65],
+            turn: bytes[66] == 1,
+            shot: bytes[67],
+            moves: u32::from_le_bytes(bytes[68..72].try_into().unwrap()),
+            resolved_shot: bytes[72],
+            resolved_hit: bytes[73] == 1,
+            nonce: limb(74),
+            match_index: limb(82),
+        })
+    }
+
+    /**
+     * Match the host and guest commitments against boards revealed at the end of a game
+     * @notice each candidate is hashed once; the first candidate matching a commitment is returned for that side
+     *
+     * @param candidates - revealed boards in any order
+     * @return - (board matching the host commitment, board matching the guest commitment), None if no candidate matches
+     */
+    pub fn identify_boards(&self, candidates: &[Board]) -> (Option<Board>, Option<Board>) {
+        let hashed = candidates
+            .iter()
+            .map(|board| (board.hash(), board))
+            .collect::<Vec<([u64; 4], &Board)>>();
+        let find = |commitment: [u64; 4]| {
+            hashed
+                .iter()
+                .find(|(hash, _)| *hash == commitment)
+                .map(|(_, board)| (*board).clone())
+        };
+        (find(self.host), find(self.guest))
+    }
+}
+
+impl fmt::Display for GameState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "host: {} (damage {}), guest: {} (damage {}), turn: {}, shot: {}, moves: {}, nonce: {}, match: {}",
+            to_hex(self.host),
+            self.host_damage,
+            to_hex(self.guest),
+            self.guest_damage,
+            if self.turn { "guest" } else { "host" },
+            self.shot,
+            self.moves,
+            self.nonce,
+            self.match_index
+        )
+    }
+}
+
+/**
+ * Natively check that a shot proof was made against the board of the player being shot in a game state
+ * @notice mirrors the commitment multiplexing of StateIncrementCircuit::constrain_commitment
+ * @dev lets clients fail fast before attempting to prove a state increment with a mismatched shot proof
+ *
+ * @param shot_proof - shot proof to apply to the game state
+ * @param game_state - channel open or state increment proof the shot is applied to
+ * @param expected_turn - turn the shot is expected for (false = host board shot, true = guest board shot)
+ * @return - true if the game state is on the expected turn and the shot commitment matches that player's board
+ */
+pub fn shot_matches_player(
+    shot_proof: &ProofWithPublicInputs<F, C, D>,
+    game_state: &ProofWithPublicInputs<F, C, D>,
+    expected_turn: bool,
+) -> bool {
+    let (shot, state) = match (
+        ShotCircuit::decode_public(shot_proof.clone()),
+        StateIncrementCircuit::decode_public(game_state.clone()),
+    ) {
+        (Ok(shot), Ok(state)) => (shot, state),
+        _ => return false,
+    };
+    // select the commitment of the player being shot this turn
+    let commitment = if state.turn { state.guest } else { state.host };
+    state.turn == expected_turn && shot.commitment == commitment
+}
+
+/**
+ * Natively check that a channel open proof hands the first increment the host's opening shot
+ * @notice the first increment multiplexes the board shot by the exported turn, so it must be OPENING_TURN
+ *
+ * @param open_p - channel open proof
+ * @return - true if the proof is a channel open proof with no moves whose turn names the guest board
+ */
+pub fn is_host_opening(open_p: &ProofWithPublicInputs<F, C, D>) -> bool {
+    // a proof without public inputs has no kind tag
+    let kind = match open_p.public_inputs.first() {
+        Some(tag) => CircuitKind::from_tag(tag.to_canonical_u64()),
+        None => return false,
+    };
+    match StateIncrementCircuit::decode_public(open_p.clone()) {
+        Ok(state) => {
+            kind == Some(CircuitKind::ChannelOpen) && state.turn == OPENING_TURN && state.moves == 0
+        }
+        Err(_) => false,
+    }
+}
+
+/**
+ * Natively check that every shot proof against a player was made against the board committed in their board proof
+ * @notice catches a player swapping boards mid-game before any state increment is proven
+ *
+ * @param board_proof - board validity proof of the player being shot
+ * @param shot_proofs - shot proofs made against that player's board
+ * @return - error if a proof cannot be decoded, or CircuitError::WrongBoard if a shot commitment differs
+ */
+pub fn assert_commitment_chain(
+    board_proof: &ProofTuple<F, C, D>,
+    shot_proofs: &[ProofTuple<F, C, D>],
+) -> Result<()> {
+    let commitment = BoardCircuit::decode_public(board_proof.0.clone())?.commitment;
+    for shot_proof in shot_proofs.iter() {
+        if ShotCircuit::decode_public(shot_proof.0.clone())?.commitment != commitment {
+            return Err(CircuitError::WrongBoard.into());
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Natively check whether either board in a game state has been sunk
+ * @notice cheap poll for clients waiting on a channel; unlike close_channel::can_close it accepts both boards sunk
+ *
+ * @param state_p - channel open or state increment proof
+ * @return - true if the host or guest damage is at least the fleet tonnage, false otherwise (or if not a game state)
+ */
+pub fn is_game_over(state_p: &ProofWithPublicInputs<F, C, D>) -> bool {
+    match StateIncrementCircuit::decode_public(state_p.clone()) {
+        Ok(state) => state.host_damage >= WIN_THRESHOLD || state.guest_damage >= WIN_THRESHOLD,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuits::{game::shot::SHOT_PUBLIC_INPUTS, test_utils::dummy_proof, CircuitKind},
+        utils::ship::Ship,
+    };
+    use plonky2::field::types::Field;
+
+    const HOST: [u64; 4] = [1, 2, 3, 4];
+    const GUEST: [u64; 4] = [5, 6, 7, 8];
+
+    // dummy shot proof against a board commitment
+    fn shot_against(commitment: [u64; 4]) -> ProofWithPublicInputs<F, C, D> {
+        let mut proof = dummy_proof(SHOT_PUBLIC_INPUTS);
+        proof.public_inputs[0] = F::from_canonical_u64(CircuitKind::Shot as u64);
+        for i in 0..4 {
+            proof.public_inputs[i + 3] = F::from_canonical_u64(commitment[i]);
+        }
+        proof
+    }
+
+    // dummy game state proof between the host and guest boards
+    fn state_on_turn(turn: bool) -> ProofWithPublicInputs<F, C, D> {
+        let mut proof = dummy_proof(GAME_STATE_PUBLIC_INPUTS);
+        proof.public_inputs[0] = F::from_canonical_u64(CircuitKind::StateIncrement as u64);
+        for i in 0..4 {
+            proof.public_inputs[i + 1] = F::from_canonical_u64(HOST[i]);
+            proof.public_inputs[i + 5] = F::from_canonical_u64(GUEST[i]);
+        }
+        proof.public_inputs[11] = F::from_bool(turn);
+        proof
+    }
+
+    #[test]
+    fn test_game_state_bytes() {
+        let state = GameState {
+            host: HOST,
+            guest: [u64::MAX, 0, 7, 1 << 63],
+            host_damage: 17,
+            guest_damage: 3,
+            turn: true,
+            shot: 99,
+            moves: 41,
+            resolved_shot: 54,
+            resolved_hit: true,
+            nonce: u64::MAX - 1,
+            match_index: 5,
+        };
+        let bytes = state.to_bytes();
+        assert_eq!(bytes.len(), GAME_STATE_BYTES);
+
+        // round trip
+        let decoded = GameState::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.host, state.host);
+        assert_eq!(decoded.guest, state.guest);
+        assert_eq!(decoded.host_damage, state.host_damage);
+        assert_eq!(decoded.guest_damage, state.guest_damage);
+        assert_eq!(decoded.turn, state.turn);
+        assert_eq!(decoded.shot, state.shot);
+        assert_eq!(decoded.moves, state.moves);
+        assert_eq!(decoded.resolved_shot, state.resolved_shot);
+        assert_eq!(decoded.resolved_hit, state.resolved_hit);
+        assert_eq!(decoded.nonce, state.nonce);
+        assert_eq!(decoded.match_index, state.match_index);
+
+        // length is constant regardless of contents
+        let empty = GameState::from_bytes(&[0u8; GAME_STATE_BYTES]).unwrap();
+        assert_eq!(empty.to_bytes().len(), GAME_STATE_BYTES);
+
+        // malformed layouts
+        assert!(GameState::from_bytes(&bytes[..GAME_STATE_BYTES - 1]).is_err());
+        let mut bad_turn = bytes;
+        bad_turn[66] = 2;
+        assert!(GameState::from_bytes(&bad_turn).is_err());
+    }
+
+    #[test]
+    fn test_shot_matches_player() {
+        // shot at guest board on guest turn
+        assert!(shot_matches_player(&shot_against(GUEST), &state_on_turn(true), true));
+        // shot at host board on host turn
+        assert!(shot_matches_player(&shot_against(HOST), &state_on_turn(false), false));
+    }
+
+    #[test]
+    fn test_shot_mismatches_player() {
+        // shot made against the wrong player's board
+        assert!(!shot_matches_player(&shot_against(HOST), &state_on_turn(true), true));
+        assert!(!shot_matches_player(&shot_against(GUEST), &state_on_turn(false), false));
+        // shot made against an unknown board
+        assert!(!shot_matches_player(&shot_against([9; 4]), &state_on_turn(true), true));
+        // game state is not on the expected turn
+        assert!(!shot_matches_player(&shot_against(GUEST), &state_on_turn(true), false));
+        // proofs swapped
+        assert!(!shot_matches_player(&state_on_turn(true), &shot_against(GUEST), true));
+    }
+
+    #[test]
+    fn test_is_game_over() {
+        // unfinished game: both boards damaged, neither sunk
+        let mut state = state_on_turn(true);
+        state.public_inputs[9] = F::from_canonical_u8(WIN_THRESHOLD - 1);
+        state.public_inputs[10] = F::from_canonical_u8(12);
+        assert!(!is_game_over(&state));
+
+        // finished game: either board sunk
+        for damage in [9, 10] {
+            let mut finished = state.clone();
+            finished.public_inputs[damage] = F::from_canonical_u8(WIN_THRESHOLD);
+            assert!(is_game_over(&finished));
+            // damage counted past the fleet tonnage is still game over
+            finished.public_inputs[damage] = F::from_canonical_u8(WIN_THRESHOLD + 1);
+            assert!(is_game_over(&finished));
+        }
+
+        // not a game state
+        assert!(!is_game_over(&shot_against(HOST)));
+    }
+
+    #[test]
+    fn test_commitment_chain() {
+        let host = Board::standard_host();
+        let guest = Board::standard_guest();
+        let board_proof = BoardCircuit::prove_inner(host.clone()).unwrap();
+        let shots = [[3, 4], [0, 0], [5, 5]]
+            .iter()
+            .map(|shot| ShotCircuit::prove_inner(host.clone(), *shot).unwrap())
+            .collect::<Vec<ProofTuple<F, C, D>>>();
+
+        // every shot made against the committed board
+        assert_commitment_chain(&board_proof, &shots).unwrap();
+
+        // board swapped for the final shot
+        let mut swapped = shots.clone();
+        swapped.push(ShotCircuit::prove_inner(guest, [5, 5]).unwrap());
+        let err = assert_commitment_chain(&board_proof, &swapped).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::WrongBoard));
+    }
+
+    #[test]
+    fn test_identify_boards() {
+        let host = Board::standard_host();
+        let guest = Board::standard_guest();
+        let state = GameState {
+            host: host.hash(),
+            guest: guest.hash(),
+            host_damage: 0,
+            guest_damage: WIN_THRESHOLD,
+            turn: false,
+            shot: 0,
+            moves: 33,
+            resolved_shot: 0,
+            resolved_hit: true,
+            nonce: 1,
+            match_index: 0,
+        };
+
+        // true boards map to their side regardless of candidate order
+        let (found_host, found_guest) = state.identify_boards(&[guest.clone(), host.clone()]);
+        assert_eq!(found_host.unwrap().hash(), host.hash());
+        assert_eq!(found_guest.unwrap().hash(), guest.hash());
+
+        // a wrong board matches neither side
+        let mut wrong = host.clone();
+        wrong.destroyer = Ship::new(8, 0, true);
+        let (found_host, found_guest) = state.identify_boards(&[wrong, guest.clone()]);
+        assert!(found_host.is_none());
+        assert_eq!(found_guest.unwrap().hash(), guest.hash());
+    }
+}
@@ -1,7 +1,18 @@
 use {
-    super::super::{ProofTuple, RecursiveTargets, C, D, F},
-    crate::gadgets::shot::serialize_shot,
-    anyhow::Result,
+    super::{
+        super::{
+            check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, RecursiveTargets, C,
+            D, F, H,
+        },
+        increment_channel::StateIncrementCircuit,
+        GameState, GAME_STATE_PUBLIC_INPUTS, OPENING_TURN,
+    },
+    crate::{
+        circuits::game::board::{BoardCircuit, UNBOUND_NONCE},
+        gadgets::shot::serialize_shot,
+        utils::board::Board,
+    },
+    anyhow::{anyhow, ensure, Result},
     log::Level,
     plonky2::{
         field::types::{Field, PrimeField64},
@@ -10,8 +21,11 @@ use {
             witness::{PartialWitness, WitnessWrite},
         },
         plonk::{
-            circuit_builder::CircuitBuilder, circuit_data::CircuitConfig,
-            proof::ProofWithPublicInputs, prover::prove,
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitData, CommonCircuitData, VerifierCircuitData},
+            config::Hasher,
+            proof::ProofWithPublicInputs,
+            prover::prove,
         },
         util::timing::TimingTree,
     },
@@ -19,6 +33,53 @@ use {
 
 // BattleZips Channel Open: Recursive (non zk) proof of two valid board configurations - used to copy constrain pubkeys and board commitments
 
+// Public game identity bound at channel open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchTargets {
+    pub nonce: Target,       // session nonce: unique per game or best-of-N series (never 0)
+    pub match_index: Target, // index of the game within its series (0 = standalone game)
+}
+
+impl MatchTargets {
+    /**
+     * Witness the session nonce and match index of the game being opened
+     *
+     * @param pw - partial witness of the channel open circuit
+     * @param nonce - session nonce of the game or series
+     * @param match_index - index of the game within its series (0 = standalone game)
+     */
+    pub fn set(&self, pw: &mut PartialWitness<F>, nonce: u64, match_index: u64) {
+        pw.set_target(self.nonce, F::from_canonical_u64(nonce));
+        pw.set_target(self.match_index, F::from_canonical_u64(match_index));
+    }
+}
+
+/**
+ * Compute the nonce both board proofs of a game must be bound to
+ * @notice a standalone game (match index 0) binds its boards to the session nonce itself; a series game binds them
+ *         to poseidon(session nonce, match index, opening shot), so board proofs of one game of a series cannot be
+ *         replayed into another game of that series, another series, or under another opening shot
+ * @dev native counterpart of the board nonce constraint in ChannelOpenCircuit::build
+ *
+ * @param nonce - session nonce of the game or series (never 0)
+ * @param match_index - index of the game within its series (0 = standalone game)
+ * @param shot - opening shot to be made by host
+ * @return - nonce to prove both boards with, or error if the opening shot is off the board
+ */
+pub fn board_nonce(nonce: u64, match_index: u64, shot: [u8; 2]) -> Result<u64> {
+    let serialized = crate::utils::shot::serialize_shot(shot[0], shot[1])
+        .ok_or_else(|| anyhow!("opening shot ({}, {}) is off the board", shot[0], shot[1]))?;
+    if match_index == 0 {
+        return Ok(nonce);
+    }
+    let preimage = [
+        F::from_canonical_u64(nonce),
+        F::from_canonical_u64(match_index),
+        F::from_canonical_u8(serialized),
+    ];
+    Ok(H::hash_no_pad(&preimage).elements[0].to_canonical_u64())
+}
+
 /**
  * Construct a partial witness for the channel open circuit
  *
@@ -57,9 +118,18 @@ pub fn partial_witness(
     Ok(pw)
 }
 
+/**
+ * Decode the board commitments exported by a channel open proof
+ *
+ * @param proof - proof from channel open circuit
+ * @return - (host commitment, guest commitment)
+ */
 pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<([u64; 4], [u64; 4])> {
+    // ensure proof matches the channel open circuit layout
+    check_public_inputs(&proof, GAME_STATE_PUBLIC_INPUTS)?;
+
     // decode host commitment
-    let host: [u64; 4] = proof.clone().public_inputs[0..4]
+    let host: [u64; 4] = proof.clone().public_inputs[1..5]
         .iter()
         .map(|x| x.to_canonical_u64())
         .collect::<Vec<u64>>()
@@ -67,7 +137,7 @@ pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<([u64; 4],
         .unwrap();
 
     // decode guest commitment
-    let guest: [u64; 4] = proof.clone().public_inputs[0..4]
+    let guest: [u64; 4] = proof.clone().public_inputs[5..9]
         .iter()
         .map(|x| x.to_canonical_u64())
         .collect::<Vec<u64>>()
@@ -78,10 +148,320 @@ pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<([u64; 4],
 }
 
 /**
- * Construct a proof to open a Battleships game state channel
+ * Decode the match index of a game in a best-of-N series
+ * @notice exported at [17] by channel open and carried by every game state proof of the game
+ *
+ * @param proof - channel open or state increment proof of the game
+ * @return - index of the game within its series (0 = standalone game)
+ */
+pub fn decode_match_index(proof: &ProofWithPublicInputs<F, C, D>) -> Result<u64> {
+    Ok(StateIncrementCircuit::decode_public(proof.clone())?.match_index)
+}
+
+/**
+ * Natively check the channel opens of a best-of-N series
+ * @notice every game must verify against the trusted channel open circuit, share the session nonce and both board
+ *         commitments of the first game, and carry a match index strictly greater than the game before it (from 1)
+ * @dev the verifier data is built by the verifier (e.g. GameCircuits::open.data.verifier_data()), never taken from
+ *      the proofs, so a proof of a look-alike circuit exporting an arbitrary match index is rejected
+ *
+ * @param open - verifier data of the trusted channel open circuit
+ * @param games - channel open proofs in the order the games were played
+ * @return - error if a proof does not verify, belongs to another series or players, or repeats a match index
+ */
+pub fn verify_series(
+    open: &VerifierCircuitData<F, C, D>,
+    games: &[ProofWithPublicInputs<F, C, D>],
+) -> Result<()> {
+    let mut previous: Option<GameState> = None;
+    for game in games.iter() {
+        open.verify(game.clone())?;
+        let state = StateIncrementCircuit::decode_public(game.clone())?;
+        match &previous {
+            None => ensure!(state.match_index != 0, "a standalone game is not part of a series"),
+            Some(previous) => {
+                ensure!(
+                    state.nonce == previous.nonce,
+                    "game {} belongs to another series",
+                    state.match_index
+                );
+                ensure!(
+                    state.host == previous.host && state.guest == previous.guest,
+                    "game {} was opened by other boards than the series",
+                    state.match_index
+                );
+                ensure!(
+                    state.match_index > previous.match_index,
+                    "match index {} does not follow match index {}",
+                    state.match_index,
+                    previous.match_index
+                );
+            }
+        }
+        previous = Some(state);
+    }
+    Ok(())
+}
+
+// Channel Open Circuit Object
+pub struct ChannelOpenCircuit {
+    pub data: CircuitData<F, C, D>, // circuit data for channel open
+    pub host: RecursiveTargets,     // targets for host board proof
+    pub guest: RecursiveTargets,    // targets for guest board proof
+    pub shot: [Target; 2],          // targets for opening shot coordinates
+    pub session: MatchTargets,      // targets for session nonce and match index
+}
+
+impl ChannelOpenCircuit {
+    /**
+     * Return the number of public inputs exported by proofs of this circuit
+     * @notice GAME_STATE_PUBLIC_INPUTS: the opening game state
+     *
+     * @return - length of the public input vector to allocate when verifying
+     */
+    pub fn num_public_inputs(&self) -> usize {
+        self.data.common.num_public_inputs
+    }
+
+    /**
+     * Build the channel open circuit against the board proofs of both players
+     * @notice the board proofs must carry board_nonce(session nonce, match index, opening shot), so the opening shot
+     *         and both boards are bound to one game of one series (or to the session of a standalone game)
+     *
+     * @param host - common circuit data of the host board proof
+     * @param guest - common circuit data of the guest board proof
+     * @return - channel open circuit data and targets
+     */
+    pub fn build(
+        host: &CommonCircuitData<F, D>,
+        guest: &CommonCircuitData<F, D>,
+    ) -> Result<ChannelOpenCircuit> {
+        // instantiate config for channel open circuit
+        let config = CircuitConfigBuilder::new().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // TARGETS ///
+
+        // host board proof targets
+        let host_pt = builder.add_virtual_proof_with_pis(host);
+        let host_data = builder.add_virtual_verifier_data(host.config.fri_config.cap_height);
+        let host_t = RecursiveTargets {
+            proof: host_pt.clone(),
+            verifier: host_data.clone(),
+        };
+
+        // guest board proof targets
+        let guest_pt = builder.add_virtual_proof_with_pis(guest);
+        let guest_data = builder.add_virtual_verifier_data(guest.config.fri_config.cap_height);
+        let guest_t = RecursiveTargets {
+            proof: guest_pt.clone(),
+            verifier: guest_data.clone(),
+        };
+
+        // opening shot coordinate targets
+        let shot_t: [Target; 2] = builder.add_virtual_targets(2).try_into().unwrap();
+
+        // session nonce and match index targets
+        let match_t = MatchTargets {
+            nonce: builder.add_virtual_target(),
+            match_index: builder.add_virtual_target(),
+        };
+
+        // SYNTHESIZE //
+        // verify commitments from each player
+        builder.verify_proof::<C>(&host_pt, &host_data, host);
+        builder.verify_proof::<C>(&guest_pt, &guest_data, guest);
+
+        // constrain the opening shot from the host
+        let serialized_t = serialize_shot(shot_t[0], shot_t[1], &mut builder).unwrap();
+
+        // unbound sessions (nonce 0) could be replayed in any game, so they cannot open a channel
+        let zero = builder.zero();
+        let unbound_t = builder.is_equal(match_t.nonce, zero);
+        builder.connect(unbound_t.target, zero);
+
+        // derive the board nonce: the session nonce for a standalone game, else bound to the match index and shot
+        let preimage = vec![match_t.nonce, match_t.match_index, serialized_t];
+        let series_nonce_t = builder.hash_n_to_hash_no_pad::<H>(preimage).elements[0];
+        let standalone_t = builder.is_equal(match_t.match_index, zero);
+        let board_nonce_t = builder.select(standalone_t, match_t.nonce, series_nonce_t);
+
+        // both board proofs must be bound to this game
+        // @dev board proofs export [0] kind tag, [1..5] commitment, [5] game nonce
+        builder.connect(host_pt.public_inputs[5], board_nonce_t);
+        builder.connect(guest_pt.public_inputs[5], board_nonce_t);
+
+        // constant game state targets on channel open
+        let host_damage_t = builder.constant(F::ZERO);
+        let guest_damage_t = builder.constant(F::ZERO);
+        let turn_t = builder.constant_bool(OPENING_TURN);
+        let moves_t = builder.constant(F::ZERO);
+        let resolved_t = builder.constant(F::ZERO);
+
+        // export board commitments publicly
+        //  - [0] = circuit kind tag (channel open)
+        //  - [1..5] = host commitment
+        //  - [5..9] = guest commitment
+        //  - [9] = host damage (constant 0 from channel open)
+        //  - [10] = guest damage (constant 0 from channel open)
+        //  - [11] = turn boolean (0 = host board, 1 = guest board; OPENING_TURN from channel open)
+        //  - [12] = serialized opening shot coordinate
+        //  - [13] = move counter (constant 0 from channel open)
+        //  - [14] = resolved shot (constant 0 from channel open: no shot resolved yet)
+        //  - [15] = resolved hit (constant 0 from channel open)
+        //  - [16] = session nonce (never 0)
+        //  - [17] = match index (0 for a standalone game)
+        // @dev player keys are bound to the commitments by a player keys proof (see player_keys::check_channel_players)
+        CircuitKind::ChannelOpen.register(&mut builder);
+        builder.register_public_inputs(&host_pt.public_inputs[1..5]);
+        builder.register_public_inputs(&guest_pt.public_inputs[1..5]);
+        builder.register_public_input(host_damage_t);
+        builder.register_public_input(guest_damage_t);
+        builder.register_public_input(turn_t.target);
+        builder.register_public_input(serialized_t);
+        builder.register_public_input(moves_t);
+        builder.register_public_input(resolved_t);
+        builder.register_public_input(resolved_t);
+        builder.register_public_input(match_t.nonce);
+        builder.register_public_input(match_t.match_index);
+
+        // construct circuit data
+        Ok(Self {
+            data: builder.build::<C>(),
+            host: host_t,
+            guest: guest_t,
+            shot: shot_t,
+            session: match_t,
+        })
+    }
+
+    /**
+     * Prove a standalone channel open against an already built channel open circuit
+     * @dev the circuit must have been built against the common data of both board proofs (see GameCircuits)
+     *
+     * @param host - proof of valid board made by host, bound to the game nonce
+     * @param guest - proof of valid board made by guest, bound to the same game nonce
+     * @param shot - opening shot to be made by host
+     * @return - proof that a valid game state channel has been opened
+     */
+    pub fn prove_cached(
+        &self,
+        host: ProofTuple<F, C, D>,
+        guest: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+    ) -> Result<ProofTuple<F, C, D>> {
+        // a standalone game uses the nonce of its board proofs as session nonce
+        let nonce = BoardCircuit::decode_public(host.0.clone())?.nonce;
+        self.prove_cached_in_series(host, guest, shot, nonce, 0)
+    }
+
+    /**
+     * Prove the channel open of one game of a best-of-N series against an already built channel open circuit
+     *
+     * @param host - proof of valid board made by host, bound to board_nonce(nonce, match_index, shot)
+     * @param guest - proof of valid board made by guest, bound to the same board nonce
+     * @param shot - opening shot to be made by host
+     * @param nonce - session nonce of the series (never 0)
+     * @param match_index - index of the game within its series (0 = standalone game)
+     * @return - proof that a valid game state channel has been opened for the match index
+     */
+    pub fn prove_cached_in_series(
+        &self,
+        host: ProofTuple<F, C, D>,
+        guest: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+        nonce: u64,
+        match_index: u64,
+    ) -> Result<ProofTuple<F, C, D>> {
+        // reject unbound or foreign board proofs before proving
+        check_game_nonce(&host, &guest, nonce, match_index, shot)?;
+
+        // compute partial witness
+        let mut pw = partial_witness(
+            self.host.clone(),
+            self.guest.clone(),
+            host,
+            guest,
+            shot,
+            self.shot,
+        )?;
+        self.session.set(&mut pw, nonce, match_index);
+
+        // prove outer proof provides valid shielding of a board validity circuit
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&self.data.prover_only, &self.data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the outer proof's integrity
+        self.data.verify(proof.clone())?;
+
+        // return outer proof artifacts
+        Ok((proof, self.data.verifier_only.clone(), self.data.common.clone()))
+    }
+}
+
+/**
+ * Natively range check the opening shot before any circuit is built
+ * @notice mirrors the serialize_shot range check of the channel open circuit, which would only fail when proving
+ *
+ * @param shot - opening shot to be made by host
+ * @return - error if either coordinate of the shot is off the board
+ */
+fn check_opening_shot(shot: [u8; 2]) -> Result<()> {
+    ensure!(
+        crate::utils::shot::serialize_shot(shot[0], shot[1]).is_some(),
+        "opening shot ({}, {}) is off the board",
+        shot[0],
+        shot[1]
+    );
+    Ok(())
+}
+
+/**
+ * Natively check both board proofs are bound to the game being opened before proving a channel open
+ * @notice mirrors the nonce constraints of the channel open circuit, which would only fail when proving
  *
  * @param host - proof of valid board made by host
  * @param guest - proof of valid board made by guest
+ * @param nonce - session nonce of the game or series
+ * @param match_index - index of the game within its series (0 = standalone game)
+ * @param shot - opening shot to be made by host
+ * @return - error if the session is unbound (nonce 0) or either proof is bound to another game
+ */
+fn check_game_nonce(
+    host: &ProofTuple<F, C, D>,
+    guest: &ProofTuple<F, C, D>,
+    nonce: u64,
+    match_index: u64,
+    shot: [u8; 2],
+) -> Result<()> {
+    ensure!(
+        nonce != UNBOUND_NONCE,
+        "board proofs must be bound to a game nonce to open a channel"
+    );
+    let expected = board_nonce(nonce, match_index, shot)?;
+    let host_nonce = BoardCircuit::decode_public(host.0.clone())?.nonce;
+    let guest_nonce = BoardCircuit::decode_public(guest.0.clone())?.nonce;
+    ensure!(
+        host_nonce == expected,
+        "host board is bound to game {} but the channel opens game {}",
+        host_nonce,
+        expected
+    );
+    ensure!(
+        guest_nonce == expected,
+        "guest board is bound to game {} but the channel opens game {}",
+        guest_nonce,
+        expected
+    );
+    Ok(())
+}
+
+/**
+ * Construct a proof to open a Battleships game state channel
+ *
+ * @param host - proof of valid board made by host, bound to the game nonce
+ * @param guest - proof of valid board made by guest, bound to the same game nonce
  * @param shot - opening shot to be made by host
  * @return - proof that a valid game state channel has been opened
  */
@@ -90,113 +470,90 @@ pub fn prove_channel_open(
     guest: ProofTuple<F, C, D>,
     shot: [u8; 2],
 ) -> Result<ProofTuple<F, C, D>> {
-    // instantiate config for channel open circuit
-    let config = CircuitConfig::standard_recursion_config();
-    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
-
-    // TARGETS ///
-
-    // host board proof targets
-    let host_pt = builder.add_virtual_proof_with_pis(&host.2);
-    let host_data = builder.add_virtual_verifier_data(host.2.config.fri_config.cap_height);
-    let host_t = RecursiveTargets {
-        proof: host_pt.clone(),
-        verifier: host_data.clone(),
-    };
+    check_opening_shot(shot)?;
+    let circuit = ChannelOpenCircuit::build(&host.2, &guest.2)?;
+    circuit.prove_cached(host, guest, shot)
+}
 
-    // guest board proof targets
-    let guest_pt = builder.add_virtual_proof_with_pis(&guest.2);
-    let guest_data = builder.add_virtual_verifier_data(guest.2.config.fri_config.cap_height);
-    let guest_t = RecursiveTargets {
-        proof: guest_pt.clone(),
-        verifier: guest_data.clone(),
-    };
+/**
+ * Construct a proof to open one game of a best-of-N series
+ *
+ * @param host - proof of valid board made by host, bound to board_nonce(nonce, match_index, shot)
+ * @param guest - proof of valid board made by guest, bound to the same board nonce
+ * @param shot - opening shot to be made by host
+ * @param nonce - session nonce of the series: drawn at random once per series (never 0)
+ * @param match_index - index of the game within its series, from 1
+ * @return - proof that the game of the series has been opened
+ */
+pub fn prove_series_open(
+    host: ProofTuple<F, C, D>,
+    guest: ProofTuple<F, C, D>,
+    shot: [u8; 2],
+    nonce: u64,
+    match_index: u64,
+) -> Result<ProofTuple<F, C, D>> {
+    check_opening_shot(shot)?;
+    let circuit = ChannelOpenCircuit::build(&host.2, &guest.2)?;
+    circuit.prove_cached_in_series(host, guest, shot, nonce, match_index)
+}
+
+/**
+ * Open a Battleships game state channel directly from both players' board configurations
+ * @notice runs the full pipeline: inner board proof -> shielding outer board proof -> channel open, for both players
+ *
+ * @param host_board - board configuration of the host
+ * @param guest_board - board configuration of the guest
+ * @param shot - opening shot to be made by host
+ * @param nonce - game/ session id to bind both board proofs to (must not be 0)
+ * @return - proof that a valid game state channel has been opened
+ */
+pub fn open_channel_from_boards(
+    host_board: Board,
+    guest_board: Board,
+    shot: [u8; 2],
+    nonce: u64,
+) -> Result<ProofTuple<F, C, D>> {
+    // reject an off-board opening shot or an unbound game before proving either board
+    check_opening_shot(shot)?;
+    ensure!(nonce != UNBOUND_NONCE, "board proofs must be bound to a game nonce to open a channel");
+
+    // prove and shield host board validity
+    let host_inner = BoardCircuit::prove_inner_with_nonce(host_board, nonce)?;
+    let host_p = BoardCircuit::prove_outer(host_inner)?;
+
+    // prove and shield guest board validity
+    let guest_inner = BoardCircuit::prove_inner_with_nonce(guest_board, nonce)?;
+    let guest_p = BoardCircuit::prove_outer(guest_inner)?;
 
-    // opening shot coordinate targets
-    let shot_t: [Target; 2] = builder.add_virtual_targets(2).try_into().unwrap();
-
-    // SYNTHESIZE //
-    // verify commitments from each player
-    builder.verify_proof::<C>(&host_pt, &host_data, &host.2);
-    builder.verify_proof::<C>(&guest_pt, &guest_data, &guest.2);
-
-    // constrain the opening shot from the host
-    let serialized_t = serialize_shot(shot_t[0], shot_t[1], &mut builder).unwrap();
-
-    // constant game state targets on channel open
-    let host_damage_t = builder.constant(F::ZERO);
-    let guest_damage_t = builder.constant(F::ZERO);
-    let turn_t = builder.constant_bool(true);
-
-    // export board commitments publicly
-    //  - [0..4] = host commitment
-    //  - [4..8] = guest commitment
-    //  - [8] = host damage (constant 0 from channel open)
-    //  - [9] = guest damage (constant 0 from channel open)
-    //  - [10] = turn boolean (0 = host, 1 = guest; constant 1 from channel open)
-    //  - [11] = serialized opening shot coordinate
-    // @todo: add pubkeys
-    builder.register_public_inputs(&host_pt.public_inputs);
-    builder.register_public_inputs(&guest_pt.public_inputs);
-    builder.register_public_input(host_damage_t);
-    builder.register_public_input(guest_damage_t);
-    builder.register_public_input(turn_t.target);
-    builder.register_public_input(serialized_t);
-
-    // construct circuit data
-    let data = builder.build::<C>();
-
-    // compute partial witness
-    let pw = partial_witness(host_t, guest_t, host, guest, shot, shot_t)?;
-
-    // prove outer proof provides valid shielding of a board validity circuit
-    let mut timing = TimingTree::new("prove", Level::Debug);
-    let proof = prove(&data.prover_only, &data.common, pw, &mut timing)?;
-    timing.print();
-
-    // verify the outer proof's integrity
-    data.verify(proof.clone())?;
-
-    // return outer proof artifacts
-    Ok((proof, data.verifier_only, data.common))
+    // open the channel with the shielded board proofs
+    prove_channel_open(host_p, guest_p, shot)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{
-        circuits::game::board::BoardCircuit,
-        utils::{board::Board, ship::Ship},
+    use crate::circuits::{
+        test_utils::{dummy_proof, GAME_NONCE},
+        CircuitError, IntoVerifierBundle,
     };
 
     #[test]
     pub fn test_shielded_channel_open() {
         // INPUTS
         // host board (inner)
-        let host_board = Board::new(
-            Ship::new(3, 4, false),
-            Ship::new(9, 6, true),
-            Ship::new(0, 0, false),
-            Ship::new(0, 6, false),
-            Ship::new(6, 1, true),
-        );
+        let host_board = Board::standard_host();
         // guest board (inner)
-        let guest_board = Board::new(
-            Ship::new(3, 3, true),
-            Ship::new(5, 4, false),
-            Ship::new(0, 1, false),
-            Ship::new(0, 5, true),
-            Ship::new(6, 1, false),
-        );
+        let guest_board = Board::standard_guest();
         // opening shot (outer/ main opening chanel proof)
         let shot = [3u8, 4];
 
         // prove inner proofs
-        let host_inner = BoardCircuit::prove_inner(host_board.clone()).unwrap();
+        let host_inner = BoardCircuit::prove_inner_with_nonce(host_board.clone(), GAME_NONCE).unwrap();
         println!("1. Host inner proof successful");
         let host_p = BoardCircuit::prove_outer(host_inner).unwrap();
         println!("2. Host outer proof successful");
-        let guest_inner = BoardCircuit::prove_inner(guest_board.clone()).unwrap();
+        let guest_inner =
+            BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
         println!("3. Guest inner proof successful");
         let guest_p = BoardCircuit::prove_outer(guest_inner).unwrap();
         println!("4. Guest outer proof successful");
@@ -212,33 +569,228 @@ mod tests {
 
         // INPUTS
         // host board (inner)
-        let host_board = Board::new(
-            Ship::new(3, 4, false),
-            Ship::new(9, 6, true),
-            Ship::new(0, 0, false),
-            Ship::new(0, 6, false),
-            Ship::new(6, 1, true),
-        );
+        let host_board = Board::standard_host();
         
         // guest board (inner)
-        let guest_board = Board::new(
-            Ship::new(3, 3, true),
-            Ship::new(5, 4, false),
-            Ship::new(0, 1, false),
-            Ship::new(0, 5, true),
-            Ship::new(6, 1, false),
-        );
+        let guest_board = Board::standard_guest();
         // opening shot (outer/ main opening chanel proof)
         let shot = [3u8, 4];
 
         // prove inner proofs
-        let host = BoardCircuit::prove_inner(host_board.clone()).unwrap();
+        let host = BoardCircuit::prove_inner_with_nonce(host_board.clone(), GAME_NONCE).unwrap();
         println!("1. Host board proof successful");
-        let guest = BoardCircuit::prove_inner(guest_board.clone()).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
         println!("2. Guest board proof successful");
 
         // recursively prove the integrity of a zk state channel opening
         _ = prove_channel_open(host, guest, shot).unwrap();
         println!("channel opened!");
     }
+
+    #[test]
+    pub fn test_open_channel_nonce() {
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let shot = [3u8, 4];
+
+        // both boards bound to the same game
+        let host = BoardCircuit::prove_inner_with_nonce(host_board, 42).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(guest_board, 42).unwrap();
+        assert_eq!(BoardCircuit::decode_public(host.0.clone()).unwrap().nonce, 42);
+        let open_p = prove_channel_open(host, guest, shot).unwrap();
+
+        // the shared nonce is exported with the opening game state of a standalone game
+        let state = StateIncrementCircuit::decode_public(open_p.0).unwrap();
+        assert_eq!(state.nonce, 42);
+        assert_eq!(state.match_index, 0);
+    }
+
+    #[test]
+    pub fn test_open_channel_unbound() {
+        // board proofs not bound to any game are rejected before proving
+        let host = BoardCircuit::prove_inner(Board::standard_host()).unwrap();
+        let guest = BoardCircuit::prove_inner(Board::standard_guest()).unwrap();
+        let err = prove_channel_open(host, guest, [3, 4]).err().unwrap();
+        assert!(err.to_string().contains("game nonce"));
+        assert!(
+            open_channel_from_boards(Board::standard_host(), Board::standard_guest(), [3, 4], 0)
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_open_channel_unbound_in_circuit() {
+        // skip the native check: the circuit itself rejects a shared nonce of 0
+        let host = BoardCircuit::prove_inner(Board::standard_host()).unwrap();
+        let guest = BoardCircuit::prove_inner(Board::standard_guest()).unwrap();
+        let circuit = ChannelOpenCircuit::build(&host.2, &guest.2).unwrap();
+        let mut pw = partial_witness(
+            circuit.host.clone(),
+            circuit.guest.clone(),
+            host,
+            guest,
+            [3, 4],
+            circuit.shot,
+        )
+        .unwrap();
+        circuit.session.set(&mut pw, UNBOUND_NONCE, 0);
+        circuit.data.prove(pw).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_open_channel_nonce_mismatch() {
+        // guest board proof replayed from another game
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), 42).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(Board::standard_guest(), 7).unwrap();
+        prove_channel_open(host, guest, [3, 4]).unwrap();
+    }
+
+    // session nonce of the test series
+    const SERIES_NONCE: u64 = 0x5eed;
+
+    #[test]
+    pub fn test_series_open() {
+        let shot = [3u8, 4];
+        let nonce = board_nonce(SERIES_NONCE, 2, shot).unwrap();
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), nonce).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(Board::standard_guest(), nonce).unwrap();
+        let open_p = prove_series_open(host, guest, shot, SERIES_NONCE, 2).unwrap();
+
+        // the match index is exported next to the session nonce
+        assert_eq!(decode_match_index(&open_p.0).unwrap(), 2);
+        assert_eq!(StateIncrementCircuit::decode_public(open_p.0).unwrap().nonce, SERIES_NONCE);
+
+        // board nonces of a series game depend on the session, the match index and the opening shot
+        assert_ne!(nonce, board_nonce(SERIES_NONCE, 3, shot).unwrap());
+        assert_ne!(nonce, board_nonce(SERIES_NONCE + 1, 2, shot).unwrap());
+        assert_ne!(nonce, board_nonce(SERIES_NONCE, 2, [4, 3]).unwrap());
+        assert_eq!(board_nonce(SERIES_NONCE, 0, shot).unwrap(), SERIES_NONCE);
+    }
+
+    #[test]
+    pub fn test_series_open_replayed_boards() {
+        // boards bound to the first game of the series cannot open the second
+        let shot = [3u8, 4];
+        let nonce = board_nonce(SERIES_NONCE, 1, shot).unwrap();
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), nonce).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(Board::standard_guest(), nonce).unwrap();
+        assert!(prove_series_open(host.clone(), guest.clone(), shot, SERIES_NONCE, 2).is_err());
+
+        // nor the first game under another opening shot
+        assert!(prove_series_open(host, guest, [4, 3], SERIES_NONCE, 1).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_series_open_replayed_boards_in_circuit() {
+        // skip the native check: the circuit itself binds the board nonce to the match index
+        let shot = [3u8, 4];
+        let nonce = board_nonce(SERIES_NONCE, 1, shot).unwrap();
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), nonce).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(Board::standard_guest(), nonce).unwrap();
+        let circuit = ChannelOpenCircuit::build(&host.2, &guest.2).unwrap();
+        let mut pw = partial_witness(
+            circuit.host.clone(),
+            circuit.guest.clone(),
+            host,
+            guest,
+            shot,
+            circuit.shot,
+        )
+        .unwrap();
+        circuit.session.set(&mut pw, SERIES_NONCE, 2);
+        circuit.data.prove(pw).unwrap();
+    }
+
+    #[test]
+    pub fn test_verify_series() {
+        let board = BoardCircuit::build(&BoardCircuit::config_inner().unwrap()).unwrap();
+        let circuit = ChannelOpenCircuit::build(&board.data.common, &board.data.common).unwrap();
+        let open = circuit.data.verifier_data();
+        let game = |host: Board, guest: Board, nonce: u64, match_index: u64| {
+            let shot = [3u8, 4];
+            let bound = board_nonce(nonce, match_index, shot).unwrap();
+            let host = board.prove_cached_with_nonce(host, bound).unwrap();
+            let guest = board.prove_cached_with_nonce(guest, bound).unwrap();
+            circuit.prove_cached_in_series(host, guest, shot, nonce, match_index).unwrap().0
+        };
+        let (host, guest) = (Board::standard_host(), Board::standard_guest());
+        let first = game(host.clone(), guest.clone(), SERIES_NONCE, 1);
+        let second = game(host.clone(), guest.clone(), SERIES_NONCE, 2);
+        verify_series(&open, &[first.clone(), second.clone()]).unwrap();
+
+        // a game replayed under the same match index is rejected, as is a series played out of order
+        assert!(verify_series(&open, &[first.clone(), second.clone(), second.clone()]).is_err());
+        assert!(verify_series(&open, &[second.clone(), first.clone()]).is_err());
+
+        // a game of another series or between other boards is rejected
+        let foreign = game(host.clone(), guest.clone(), SERIES_NONCE + 1, 3);
+        assert!(verify_series(&open, &[first.clone(), second.clone(), foreign]).is_err());
+        let swapped = game(guest.clone(), host.clone(), SERIES_NONCE, 3);
+        assert!(verify_series(&open, &[first.clone(), second.clone(), swapped]).is_err());
+
+        // a standalone game does not start a series
+        let standalone = game(host, guest, SERIES_NONCE, 0);
+        assert!(verify_series(&open, &[standalone, first.clone()]).is_err());
+
+        // a proof of another circuit exporting a later match index is rejected
+        let mut forged = dummy_proof(GAME_STATE_PUBLIC_INPUTS);
+        forged.public_inputs = second.public_inputs.clone();
+        forged.public_inputs[17] = F::from_canonical_u64(3);
+        assert!(verify_series(&open, &[first, second, forged]).is_err());
+    }
+
+    #[test]
+    pub fn test_opening_shot_off_board() {
+        let host = BoardCircuit::prove_inner_with_nonce(Board::standard_host(), GAME_NONCE).unwrap();
+        let guest = BoardCircuit::prove_inner_with_nonce(Board::standard_guest(), GAME_NONCE).unwrap();
+        let err = prove_channel_open(host.clone(), guest.clone(), [10, 5]).err().unwrap();
+        assert!(err.to_string().contains("off the board"));
+        let (host_board, guest_board) = (Board::standard_host(), Board::standard_guest());
+        assert!(open_channel_from_boards(host_board, guest_board, [5, 10], GAME_NONCE).is_err());
+
+        // the same board proofs open a channel with an on-board shot
+        prove_channel_open(host, guest, [9, 9]).unwrap();
+    }
+
+    #[test]
+    pub fn test_decode_public_shape() {
+        // truncated public inputs
+        let err = decode_public(dummy_proof(GAME_STATE_PUBLIC_INPUTS - 1)).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::PublicInputShape));
+
+        // over-long public inputs
+        let err = decode_public(dummy_proof(GAME_STATE_PUBLIC_INPUTS + 1)).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::PublicInputShape));
+
+        // well formed public inputs
+        assert!(decode_public(dummy_proof(GAME_STATE_PUBLIC_INPUTS)).is_ok());
+    }
+
+    #[test]
+    pub fn test_open_channel_from_boards() {
+        // INPUTS
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let shot = [3u8, 4];
+
+        // open channel in one call
+        let open_p =
+            open_channel_from_boards(host_board.clone(), guest_board.clone(), shot, GAME_NONCE)
+                .unwrap();
+
+        // open channel through the manual pipeline
+        let host_inner = BoardCircuit::prove_inner_with_nonce(host_board, GAME_NONCE).unwrap();
+        let host_p = BoardCircuit::prove_outer(host_inner).unwrap();
+        let guest_inner = BoardCircuit::prove_inner_with_nonce(guest_board, GAME_NONCE).unwrap();
+        let guest_p = BoardCircuit::prove_outer(guest_inner).unwrap();
+        let manual_p = prove_channel_open(host_p, guest_p, shot).unwrap();
+
+        // same circuit and same public game state
+        assert_eq!(open_p.1.circuit_digest, manual_p.1.circuit_digest);
+        assert_eq!(open_p.0.public_inputs, manual_p.0.public_inputs);
+        open_p.into_verifier_bundle().verify().unwrap();
+    }
 }
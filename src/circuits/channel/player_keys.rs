@@ -0,0 +1,344 @@
+use {
+    super::{
+        super::{check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, C, D, F},
+        open_channel,
+    },
+    crate::{
+        gadgets::keys::{
+            assert_not_equal, distinct_weights, verify_commitment_signature, SignatureTargets,
+        },
+        utils::ecdsa::{
+            commitment_message, public_key_limbs, signature_limbs, verify, PublicKey, Signature,
+        },
+    },
+    anyhow::{anyhow, ensure, Result},
+    log::Level,
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        hash::poseidon::PoseidonHash,
+        iop::{
+            target::Target,
+            witness::{PartialWitness, WitnessWrite},
+        },
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::CircuitData,
+            proof::ProofWithPublicInputs,
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// BattleZips Player Keys: binds the host and guest board commitments of a channel to two distinct player keys
+// @dev each commitment must be signed under its player's key (see BoardCircuit::prove_and_sign), and the keys must
+//      differ, so one key cannot open a channel against itself (self-play/ sybil); keys are exported as poseidon ids
+//      so the commitments can be matched against channel open (see check_channel_players)
+
+// number of public inputs exported by the player keys circuit (kind tag, commitments, key ids)
+pub const PLAYER_KEYS_PUBLIC_INPUTS: usize = 17;
+
+// Board commitment signed by the player holding the board
+pub struct SignedCommitment {
+    pub commitment: [u64; 4], // board commitment exported by the board proof
+    pub key: PublicKey,       // public key of the player
+    pub signature: Signature, // signature over commitment_message(commitment) under key
+}
+
+// Public outputs of a player keys proof
+pub struct PlayerKeysOutputs {
+    pub host: [u64; 4],      // host board commitment
+    pub guest: [u64; 4],     // guest board commitment
+    pub host_key: [u64; 4],  // poseidon id of the host public key
+    pub guest_key: [u64; 4], // poseidon id of the guest public key
+}
+
+// Player Keys Circuit Object
+pub struct PlayerKeysCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub host: [Target; 4],                 // host board commitment
+    pub guest: [Target; 4],                // guest board commitment
+    pub host_signature: SignatureTargets,  // host public key and signature over the host commitment
+    pub guest_signature: SignatureTargets, // guest public key and signature over the guest commitment
+    pub weights: Vec<Target>,              // witnessed inverse of the key difference
+}
+
+impl PlayerKeysCircuit {
+    /**
+     * Build a circuit proving each board commitment is signed by its player and the two player keys differ
+     *
+     * @return - player keys circuit
+     */
+    pub fn build() -> Result<PlayerKeysCircuit> {
+        // CONFIG //
+        let config = CircuitConfigBuilder::new().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // TARGETS //
+        let host = builder.add_virtual_target_arr::<4>();
+        let guest = builder.add_virtual_target_arr::<4>();
+
+        // SYNTHESIZE //
+        // each commitment must be signed under the key of its player
+        let host_signature = verify_commitment_signature(&host, &mut builder)?;
+        let guest_signature = verify_commitment_signature(&guest, &mut builder)?;
+        // host and guest keys must differ in at least one coordinate limb
+        let weights = assert_not_equal(&host_signature.key, &guest_signature.key, &mut builder)?;
+        // identify each key by the hash of its limbs
+        let host_id = builder.hash_n_to_hash_no_pad::<PoseidonHash>(host_signature.key.to_vec());
+        let guest_id = builder.hash_n_to_hash_no_pad::<PoseidonHash>(guest_signature.key.to_vec());
+
+        // PUBLIC INPUTS //
+        //  - [0] = circuit kind tag (player keys)
+        //  - [1..5] = host commitment
+        //  - [5..9] = guest commitment
+        //  - [9..13] = host key id
+        //  - [13..17] = guest key id
+        CircuitKind::PlayerKeys.register(&mut builder);
+        builder.register_public_inputs(&host);
+        builder.register_public_inputs(&guest);
+        builder.register_public_inputs(&host_id.elements);
+        builder.register_public_inputs(&guest_id.elements);
+
+        Ok(Self {
+            data: builder.build::<C>(),
+            host,
+            guest,
+            host_signature,
+            guest_signature,
+            weights,
+        })
+    }
+
+    /**
+     * Witness both signed commitments and the weights proving the keys differ
+     *
+     * @param host - host board commitment signed by the host
+     * @param guest - guest board commitment signed by the guest
+     * @param weights - witnessed inverse of the key difference (see distinct_weights)
+     * @return - partial witness for the player keys circuit
+     */
+    pub fn partial_witness(
+        &self,
+        host: &SignedCommitment,
+        guest: &SignedCommitment,
+        weights: &[F],
+    ) -> PartialWitness<F> {
+        let mut pw = PartialWitness::new();
+        for (commitment_t, signature_t, signed) in [
+            (self.host, &self.host_signature, host),
+            (self.guest, &self.guest_signature, guest),
+        ] {
+            pw.set_target_arr(commitment_t, signed.commitment.map(F::from_canonical_u64));
+            pw.set_target_arr(
+                signature_t.key,
+                public_key_limbs(&signed.key).map(F::from_canonical_u32),
+            );
+            pw.set_target_arr(
+                signature_t.signature,
+                signature_limbs(&signed.signature).map(F::from_canonical_u32),
+            );
+        }
+        for (target, weight) in self.weights.iter().zip(weights) {
+            pw.set_target(*target, *weight);
+        }
+        pw
+    }
+
+    /**
+     * Prove the boards of a channel are committed by two different players
+     *
+     * @param host - host board commitment signed by the host
+     * @param guest - guest board commitment signed by the guest
+     * @return - proof binding both commitments to distinct keys, or error if a signature does not match its
+     *           commitment or both boards are held by the same key
+     */
+    pub fn prove(host: &SignedCommitment, guest: &SignedCommitment) -> Result<ProofTuple<F, C, D>> {
+        // reject unsigned commitments and self-play before building the circuit
+        for (player, signed) in [("host", host), ("guest", guest)] {
+            ensure!(
+                verify(commitment_message(signed.commitment), signed.signature, signed.key),
+                "{} signature does not match its board commitment",
+                player
+            );
+        }
+        let weights = distinct_weights(&public_key_limbs(&host.key), &public_key_limbs(&guest.key))
+            .map_err(|_| anyhow!("host and guest boards are held by the same key"))?;
+
+        // CIRCUIT //
+        let circuit = PlayerKeysCircuit::build()?;
+
+        // WITNESS //
+        let pw = circuit.partial_witness(host, guest, &weights);
+
+        // PROVE //
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&circuit.data.prover_only, &circuit.data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        circuit.data.verify(proof.clone())?;
+
+        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+    }
+
+    /**
+     * Decode the commitments and key ids exported by a player keys proof
+     *
+     * @param proof - proof from player keys circuit
+     * @return - formatted outputs from player keys circuit
+     */
+    pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<PlayerKeysOutputs> {
+        // ensure proof matches the player keys circuit layout
+        check_public_inputs(&proof, PLAYER_KEYS_PUBLIC_INPUTS)?;
+
+        let limbs = |offset: usize| -> [u64; 4] {
+            proof.public_inputs[offset..offset + 4]
+                .iter()
+                .map(|x| x.to_canonical_u64())
+                .collect::<Vec<u64>>()
+                .try_into()
+                .unwrap()
+        };
+        Ok(PlayerKeysOutputs {
+            host: limbs(1),
+            guest: limbs(5),
+            host_key: limbs(9),
+            guest_key: limbs(13),
+        })
+    }
+}
+
+/**
+ * Natively check a player keys proof names the players of a channel
+ * @notice both proofs must already be verified against trusted verifier data; this only matches their statements
+ *
+ * @param open - channel open proof
+ * @param keys - player keys proof
+ * @return - key ids of the host and guest, or error if the proofs commit to different boards
+ */
+pub fn check_channel_players(
+    open: &ProofWithPublicInputs<F, C, D>,
+    keys: &ProofWithPublicInputs<F, C, D>,
+) -> Result<PlayerKeysOutputs> {
+    let (host, guest) = open_channel::decode_public(open.clone())?;
+    let players = PlayerKeysCircuit::decode_public(keys.clone())?;
+    ensure!(
+        players.host == host && players.guest == guest,
+        "player keys are bound to other boards than the channel"
+    );
+    Ok(players)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            circuits::{channel::GAME_STATE_PUBLIC_INPUTS, test_utils::dummy_proof},
+            utils::{
+                board::Board,
+                ecdsa::{key_id, keypair, sign},
+            },
+        },
+    };
+
+    /**
+     * Sign a board commitment with a fresh key
+     *
+     * @param board - board whose commitment is signed
+     * @return - commitment signed under the fresh key
+     */
+    fn signed(board: &Board) -> SignedCommitment {
+        let (secret_key, key) = keypair();
+        let commitment = board.hash();
+        SignedCommitment {
+            commitment,
+            key,
+            signature: sign(commitment_message(commitment), secret_key),
+        }
+    }
+
+    #[test]
+    pub fn test_distinct_player_keys() {
+        let host = signed(&Board::standard_host());
+        let guest = signed(&Board::standard_guest());
+
+        let proof = PlayerKeysCircuit::prove(&host, &guest).unwrap();
+        let outputs = PlayerKeysCircuit::decode_public(proof.0.clone()).unwrap();
+        assert_eq!(outputs.host, host.commitment);
+        assert_eq!(outputs.guest, guest.commitment);
+        assert_eq!(outputs.host_key, key_id(&host.key));
+        assert_eq!(outputs.guest_key, key_id(&guest.key));
+
+        // the keys are matched against the channel opened by the same boards
+        let mut open = dummy_proof(GAME_STATE_PUBLIC_INPUTS);
+        for i in 0..4 {
+            open.public_inputs[1 + i] = F::from_canonical_u64(host.commitment[i]);
+            open.public_inputs[5 + i] = F::from_canonical_u64(guest.commitment[i]);
+        }
+        check_channel_players(&open, &proof.0).unwrap();
+
+        // and rejected against a channel between other boards
+        open.public_inputs[1] = F::ZERO;
+        assert!(check_channel_players(&open, &proof.0).is_err());
+    }
+
+    #[test]
+    pub fn test_same_player_keys() {
+        // one key cannot commit both boards of a channel
+        let (secret_key, key) = keypair();
+        let sign_board = |board: Board| SignedCommitment {
+            commitment: board.hash(),
+            key,
+            signature: sign(commitment_message(board.hash()), secret_key),
+        };
+        let host = sign_board(Board::standard_host());
+        let guest = sign_board(Board::standard_guest());
+        let err = PlayerKeysCircuit::prove(&host, &guest).err().unwrap();
+        assert!(err.to_string().contains("same key"));
+    }
+
+    #[test]
+    pub fn test_unsigned_commitment() {
+        // a signature over another board does not bind the commitment
+        let host = signed(&Board::standard_host());
+        let mut guest = signed(&Board::standard_guest());
+        guest.commitment = host.commitment;
+        let err = PlayerKeysCircuit::prove(&host, &guest).err().unwrap();
+        assert!(err.to_string().contains("guest signature"));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_unsigned_commitment_in_circuit() {
+        // bypass the native check: the circuit itself verifies each signature against its commitment
+        let host = signed(&Board::standard_host());
+        let mut guest = signed(&Board::standard_guest());
+        guest.signature = host.signature;
+        let weights =
+            distinct_weights(&public_key_limbs(&host.key), &public_key_limbs(&guest.key)).unwrap();
+        let circuit = PlayerKeysCircuit::build().unwrap();
+        let pw = circuit.partial_witness(&host, &guest, &weights);
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_same_player_keys_forged_weights() {
+        // bypass the native check: the circuit itself rejects any weights for equal keys
+        let (secret_key, key) = keypair();
+        let sign_board = |board: Board| SignedCommitment {
+            commitment: board.hash(),
+            key,
+            signature: sign(commitment_message(board.hash()), secret_key),
+        };
+        let host = sign_board(Board::standard_host());
+        let guest = sign_board(Board::standard_guest());
+        let circuit = PlayerKeysCircuit::build().unwrap();
+        let pw = circuit.partial_witness(&host, &guest, &[F::ONE; 16]);
+        let proof = circuit.data.prove(pw).unwrap();
+        circuit.data.verify(proof).unwrap();
+    }
+}
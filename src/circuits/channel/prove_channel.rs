@@ -0,0 +1,126 @@
+use {
+    super::{
+        super::{
+            game::board::BoardCircuit, game::shot::ShotCircuit, prove_classified, ProofTuple, C, D, F,
+        },
+        close_channel::{can_close, prove_close_channel},
+        increment_channel::StateIncrementCircuit,
+        open_channel::prove_channel_open,
+    },
+    crate::utils::board::Board,
+    anyhow::{ensure, Result},
+    std::time::{Duration, Instant},
+};
+
+// BattleZips Channel Prover: drive a full state channel (open -> increments -> close) from a shot log
+
+// Stage of the channel that has just been proven
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelStage {
+    Open,
+    Increment,
+    Close,
+}
+
+// Progress event emitted after each proof in the channel
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    pub stage: ChannelStage, // proof that was just generated
+    pub move_index: usize,   // 0 for open, n for the nth increment, # of increments for close
+    pub elapsed: Duration,   // time since proving the channel started
+}
+
+/**
+ * Prove a full ZK State Channel from a shot log, reporting progress after every proof
+ * @notice shots[0] is the opening shot; increment k checks shots[k - 1] and commits to shots[k] as the next shot
+ * @dev the final shot is only committed to as a next shot and is never checked (any coordinate works)
+ * @dev every proof consuming earlier proofs runs through prove_classified, so a failure downcasts to a VerifyError
+ *
+ * @param host - host board configuration
+ * @param guest - guest board configuration
+ * @param nonce - game/ session id both board proofs are bound to (must not be 0)
+ * @param shots - ordered shot log alternating guest board, host board (starting with the host's opening shot)
+ * @param callback - invoked after channel open, each state increment, and channel close
+ * @return - proof that the channel closed with a winner
+ */
+pub fn prove_channel_with_progress(
+    host: Board,
+    guest: Board,
+    nonce: u64,
+    shots: &[[u8; 2]],
+    mut callback: impl FnMut(Progress),
+) -> Result<ProofTuple<F, C, D>> {
+    ensure!(shots.len() >= 2, "shot log must contain an opening shot and a next shot");
+    let start = Instant::now();
+
+    // CHANNEL OPEN //
+    let host_p = BoardCircuit::prove_inner_with_nonce(host.clone(), nonce)?;
+    let guest_p = BoardCircuit::prove_inner_with_nonce(guest.clone(), nonce)?;
+    let mut state_p = prove_classified(&[&host_p, &guest_p], || {
+        prove_channel_open(host_p.clone(), guest_p.clone(), shots[0])
+    })?;
+    callback(Progress {
+        stage: ChannelStage::Open,
+        move_index: 0,
+        elapsed: start.elapsed(),
+    });
+
+    // STATE INCREMENTS //
+    for k in 1..shots.len() {
+        // shot the board selected by the turn of the previous state
+        let turn = StateIncrementCircuit::decode_public(state_p.0.clone())?.turn;
+        let board = if turn { guest.clone() } else { host.clone() };
+        let shot_p = ShotCircuit::prove_inner(board, shots[k - 1])?;
+        state_p = prove_classified(&[&state_p, &shot_p], || {
+            StateIncrementCircuit::prove(state_p.clone(), shot_p.clone(), shots[k])
+        })?;
+        callback(Progress {
+            stage: ChannelStage::Increment,
+            move_index: k,
+            elapsed: start.elapsed(),
+        });
+    }
+
+    // CHANNEL CLOSE //
+    ensure!(can_close(&state_p.0), "shot log does not finish the game");
+    let close_p = prove_classified(&[&state_p], || prove_close_channel(state_p.clone()))?;
+    callback(Progress {
+        stage: ChannelStage::Close,
+        move_index: shots.len() - 1,
+        elapsed: start.elapsed(),
+    });
+
+    Ok(close_p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::test_utils::GAME_NONCE;
+
+    #[test]
+    pub fn test_progress_unfinished_channel() {
+        let mut events = Vec::<Progress>::new();
+        let shots = [[0u8, 0], [5, 5], [5, 5]];
+
+        // two increments do not finish the game
+        let result = prove_channel_with_progress(
+            Board::standard_host(),
+            Board::standard_guest(),
+            GAME_NONCE,
+            &shots,
+            |progress| events.push(progress),
+        );
+        assert!(result.is_err());
+
+        // open and each increment reported in order, no close
+        let stages = events.iter().map(|event| event.stage).collect::<Vec<ChannelStage>>();
+        assert_eq!(
+            stages,
+            vec![ChannelStage::Open, ChannelStage::Increment, ChannelStage::Increment]
+        );
+        let moves = events.iter().map(|event| event.move_index).collect::<Vec<usize>>();
+        assert_eq!(moves, vec![0, 1, 2]);
+        assert!(events.windows(2).all(|pair| pair[0].elapsed <= pair[1].elapsed));
+    }
+}
@@ -0,0 +1,324 @@
+use {
+    super::{
+        super::{
+            check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, RecursiveTargets, C,
+            D, F,
+        },
+        close_channel::WIN_THRESHOLD,
+        increment_channel::StateIncrementCircuit,
+    },
+    anyhow::Result,
+    log::Level,
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        iop::{
+            target::{BoolTarget, Target},
+            witness::{PartialWitness, WitnessWrite},
+        },
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitData, CommonCircuitData, VerifierOnlyCircuitData},
+            proof::ProofWithPublicInputs,
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// BattleZips Win Proof: proof of a clean win - the loser was sunk while the winner's board was not
+// @dev the final state increment proof is verified in-circuit against verifier data fixed at build time, so a proof
+//      from any other circuit exporting the same layout is rejected
+
+// number of public inputs exported by the win proof circuit
+// (kind tag, host damage, guest damage, winner, winner and loser commitments, session nonce, match index)
+pub const WIN_PUBLIC_INPUTS: usize = 14;
+
+// Public outputs of a win proof
+pub struct WinOutputs {
+    pub host_damage: u8,
+    pub guest_damage: u8,
+    pub winner: bool, // true = guest won (host sunk), false = host won (guest sunk)
+    pub winner_commitment: [u64; 4],
+    pub loser_commitment: [u64; 4],
+    pub nonce: u64,       // session nonce of the channel
+    pub match_index: u64, // index of the game within its series (0 = standalone game)
+}
+
+// Win Proof Circuit Object
+pub struct WinProofCircuit {
+    pub data: CircuitData<F, C, D>, // circuit data for the win proof
+    pub state: RecursiveTargets,    // targets for the final state increment proof
+    pub host_damage: Target,        // hits made on host board
+    pub guest_damage: Target,       // hits made on guest board
+    pub winner: BoolTarget,         // claimed winner (true = guest, false = host)
+}
+
+impl WinProofCircuit {
+    /**
+     * Build a circuit proving the claimed winner's damage < 17 while the loser's damage == 17
+     * @notice damage counts are read from [9] and [10] of the final state increment proof
+     * @dev the state verifier data is a circuit constant (see GameCircuits::prove_win)
+     *
+     * @param verifier - verifier data of the trusted state increment circuit
+     * @param state - common circuit data of the final state increment proof
+     * @return - win proof circuit
+     */
+    pub fn build(
+        verifier: &VerifierOnlyCircuitData<C, D>,
+        state: &CommonCircuitData<F, D>,
+    ) -> Result<WinProofCircuit> {
+        // CONFIG //
+        let config = CircuitConfigBuilder::new().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // TARGETS //
+        // state proof targets, with the verifier data fixed to the trusted state increment circuit
+        let state_pt = RecursiveTargets {
+            proof: builder.add_virtual_proof_with_pis(state),
+            verifier: builder.constant_verifier_data(verifier),
+        };
+        let host_damage = builder.add_virtual_target();
+        let guest_damage = builder.add_virtual_target();
+        let winner = builder.add_virtual_bool_target_safe();
+
+        // SYNTHESIZE //
+        // verify the final state increment proof
+        builder.verify_proof::<C>(&state_pt.proof, &state_pt.verifier, state);
+        // the verified proof must export a state increment
+        let state_pis = state_pt.proof.public_inputs.clone();
+        let tag = builder.constant(F::from_canonical_u64(CircuitKind::StateIncrement as u64));
+        builder.connect(state_pis[0], tag);
+        // bind damage targets to the damage counters of the final state
+        builder.connect(host_damage, state_pis[9]);
+        builder.connect(guest_damage, state_pis[10]);
+        // multiplex winner and loser damage (guest wins -> host is loser)
+        let winner_damage = builder.select(winner, guest_damage, host_damage);
+        let loser_damage = builder.select(winner, host_damage, guest_damage);
+        // loser must be sunk
+        let threshold = builder.constant(F::from_canonical_u8(WIN_THRESHOLD));
+        builder.connect(loser_damage, threshold);
+        // winner must not be sunk: (threshold - 1) - damage underflows outside of 8 bits if damage >= threshold
+        let ceiling = builder.constant(F::from_canonical_u8(WIN_THRESHOLD - 1));
+        let headroom = builder.sub(ceiling, winner_damage);
+        builder.range_check(headroom, 8);
+        // multiplex winner and loser boards: guest wins -> host is loser
+        let mut winner_commitment = Vec::new();
+        let mut loser_commitment = Vec::new();
+        for i in 0..4 {
+            winner_commitment.push(builder.select(winner, state_pis[5 + i], state_pis[1 + i]));
+            loser_commitment.push(builder.select(winner, state_pis[1 + i], state_pis[5 + i]));
+        }
+
+        // PUBLIC INPUTS //
+        // register circuit kind tag ([0])
+        CircuitKind::Win.register(&mut builder);
+        // register host damage ([1])
+        builder.register_public_input(host_damage);
+        // register guest damage ([2])
+        builder.register_public_input(guest_damage);
+        // register winner ([3])
+        builder.register_public_input(winner.target);
+        // register winner commitment ([4..8])
+        builder.register_public_inputs(&winner_commitment);
+        // register loser commitment ([8..12])
+        builder.register_public_inputs(&loser_commitment);
+        // pass through the session nonce and match index bound at channel open ([12] and [13])
+        builder.register_public_input(state_pis[16]);
+        builder.register_public_input(state_pis[17]);
+
+        Ok(Self {
+            data: builder.build::<C>(),
+            state: state_pt,
+            host_damage,
+            guest_damage,
+            winner,
+        })
+    }
+
+    /**
+     * Prove a clean win against an already built win proof circuit
+     * @dev the verifier data bundled with state_p is ignored: the circuit verifies against its pinned state verifier
+     *
+     * @param state_p - final state increment proof of the channel
+     * @param winner - claimed winner (true = guest, false = host)
+     * @return - proof that the claimed winner won cleanly
+     */
+    pub fn prove_cached(
+        &self,
+        state_p: ProofTuple<F, C, D>,
+        winner: bool,
+    ) -> Result<ProofTuple<F, C, D>> {
+        // WITNESS //
+        let state = StateIncrementCircuit::decode_public(state_p.0.clone())?;
+        let mut pw = PartialWitness::new();
+        pw.set_proof_with_pis_target(&self.state.proof, &state_p.0);
+        pw.set_target(self.host_damage, F::from_canonical_u8(state.host_damage));
+        pw.set_target(self.guest_damage, F::from_canonical_u8(state.guest_damage));
+        pw.set_bool_target(self.winner, winner);
+
+        // PROVE //
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&self.data.prover_only, &self.data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        self.data.verify(proof.clone())?;
+
+        Ok((proof, self.data.verifier_only.clone(), self.data.common.clone()))
+    }
+
+    /**
+     * Decode the public outputs of a win proof
+     *
+     * @param proof - proof from win proof circuit
+     * @return - damage counts, winner, winner and loser commitments, session nonce and match index
+     */
+    pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<WinOutputs> {
+        // ensure proof matches the win proof circuit layout
+        check_public_inputs(&proof, WIN_PUBLIC_INPUTS)?;
+
+        // decode winner and loser commitments
+        let winner_commitment: [u64; 4] = proof.public_inputs[4..8]
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap();
+        let loser_commitment: [u64; 4] = proof.public_inputs[8..12]
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap();
+
+        Ok(WinOutputs {
+            host_damage: proof.public_inputs[1].to_canonical_u64() as u8,
+            guest_damage: proof.public_inputs[2].to_canonical_u64() as u8,
+            winner: proof.public_inputs[3].to_canonical_u64() != 0,
+            winner_commitment,
+            loser_commitment,
+            nonce: proof.public_inputs[12].to_canonical_u64(),
+            match_index: proof.public_inputs[13].to_canonical_u64(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuits::{
+            channel::GameState,
+            test_utils::{mock_state_proof, mock_tagged_state_proof, GAME_NONCE},
+        },
+        utils::board::Board,
+    };
+
+    /**
+     * Build a mock final state carrying the given damage counts
+     *
+     * @param host_damage - hits made on host board
+     * @param guest_damage - hits made on guest board
+     * @return - final game state
+     */
+    fn final_state(host_damage: u8, guest_damage: u8) -> GameState {
+        GameState {
+            host: Board::standard_host().hash(),
+            guest: Board::standard_guest().hash(),
+            host_damage,
+            guest_damage,
+            turn: true,
+            shot: 0,
+            moves: 40,
+            resolved_shot: 0,
+            resolved_hit: true,
+            nonce: GAME_NONCE,
+            match_index: 0,
+        }
+    }
+
+    /**
+     * Prove a clean win with the win circuit pinned to the mock state circuit
+     *
+     * @param state_p - mock final state proof
+     * @param winner - claimed winner (true = guest, false = host)
+     * @return - win proof
+     */
+    fn prove_win(state_p: ProofTuple<F, C, D>, winner: bool) -> Result<ProofTuple<F, C, D>> {
+        let circuit = WinProofCircuit::build(&state_p.1, &state_p.2)?;
+        circuit.prove_cached(state_p, winner)
+    }
+
+    #[test]
+    pub fn test_clean_win() {
+        // host sunk while guest still afloat
+        let state_p = mock_state_proof(&final_state(WIN_THRESHOLD, 12));
+        let proof = prove_win(state_p, true).unwrap();
+        let outputs = WinProofCircuit::decode_public(proof.0).unwrap();
+        assert_eq!(outputs.host_damage, WIN_THRESHOLD);
+        assert_eq!(outputs.guest_damage, 12);
+        assert!(outputs.winner);
+        assert_eq!(outputs.winner_commitment, Board::standard_guest().hash());
+        assert_eq!(outputs.loser_commitment, Board::standard_host().hash());
+        assert_eq!(outputs.nonce, GAME_NONCE);
+        assert_eq!(outputs.match_index, 0);
+    }
+
+    #[test]
+    pub fn test_clean_host_win() {
+        // guest sunk while host still afloat
+        let state_p = mock_state_proof(&final_state(5, WIN_THRESHOLD));
+        let proof = prove_win(state_p, false).unwrap();
+        let outputs = WinProofCircuit::decode_public(proof.0).unwrap();
+        assert!(!outputs.winner);
+        assert_eq!(outputs.winner_commitment, Board::standard_host().hash());
+        assert_eq!(outputs.loser_commitment, Board::standard_guest().hash());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_draw_no_guest_win() {
+        // both boards sunk: guest cannot claim a clean win
+        let state_p = mock_state_proof(&final_state(WIN_THRESHOLD, WIN_THRESHOLD));
+        prove_win(state_p, true).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_draw_no_host_win() {
+        // both boards sunk: host cannot claim a clean win
+        let state_p = mock_state_proof(&final_state(WIN_THRESHOLD, WIN_THRESHOLD));
+        prove_win(state_p, false).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_unsunk_loser() {
+        // neither board sunk
+        let state_p = mock_state_proof(&final_state(16, 3));
+        prove_win(state_p, true).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_not_state_increment() {
+        // a proof from the pinned circuit exporting another kind tag is rejected
+        let state_p = mock_tagged_state_proof(&final_state(WIN_THRESHOLD, 12), CircuitKind::ChannelOpen);
+        let circuit = WinProofCircuit::build(&state_p.1, &state_p.2).unwrap();
+        circuit.prove_cached(state_p, true).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_forged_damage() {
+        // unfinished game: damage witnessed as if the host were sunk conflicts with the verified state
+        let state_p = mock_state_proof(&final_state(16, 3));
+        let circuit = WinProofCircuit::build(&state_p.1, &state_p.2).unwrap();
+        let mut pw = PartialWitness::new();
+        pw.set_proof_with_pis_target(&circuit.state.proof, &state_p.0);
+        pw.set_target(circuit.host_damage, F::from_canonical_u8(WIN_THRESHOLD));
+        pw.set_target(circuit.guest_damage, F::from_canonical_u8(3));
+        pw.set_bool_target(circuit.winner, true);
+        circuit.data.prove(pw).unwrap();
+    }
+}
@@ -1,8 +1,19 @@
 use {
-    super::super::{ProofTuple, RecursiveTargets, C, D, F},
+    super::super::{
+        check_public_inputs, recursion::{fold_with_timing, prove_with_retries}, shape::CircuitShape,
+        CircuitConfigBuilder, CircuitError, CircuitKind, IntoVerifierBundle, ProofTiming, ProofTuple,
+        RecursiveTargets, VerifierCache, C, D, F,
+    },
     crate::{
-        gadgets::board::{decompose_board, hash_board, place_ship, recompose_board},
-        utils::board::Board,
+        gadgets::board::{
+            constrain_ship_order, decompose_board, hash_board, place_typed_ship, recompose_board,
+            TypedShipTarget,
+        },
+        utils::{
+            board::Board,
+            commitment::to_hex,
+            ecdsa::{commitment_message, sign, SecretKey, Signature},
+        },
     },
     plonky2::{
         util::timing::TimingTree,
@@ -13,41 +24,66 @@ use {
         },
         plonk::{
             circuit_builder::CircuitBuilder,
-            circuit_data::{CircuitConfig, CircuitData, VerifierCircuitTarget},
+            circuit_data::{
+                CircuitConfig, CircuitData, CommonCircuitData, VerifierCircuitData,
+                VerifierCircuitTarget, VerifierOnlyCircuitData,
+            },
             proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget},
             prover::prove,
         },
     },
     anyhow::Result,
     log::Level,
+    std::time::Instant,
 };
 
 pub struct BoardCircuitOutputs {
-    commitment: [u64; 4],
+    pub commitment: [u64; 4],
+    pub nonce: u64, // game/ session id the proof is bound to (0 = unbound)
+}
+
+impl std::fmt::Display for BoardCircuitOutputs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "commitment: {}, nonce: {}", to_hex(self.commitment), self.nonce)
+    }
 }
 
 pub type ShipTarget = (Target, Target, BoolTarget);
 
+// number of public inputs exported by the board circuit (kind tag, board commitment, game nonce)
+pub const BOARD_PUBLIC_INPUTS: usize = 6;
+
+// game nonce of board proofs that are not bound to a specific game
+pub const UNBOUND_NONCE: u64 = 0;
+
 pub struct BoardCircuit {
-    data: CircuitData<F, C, D>,
-    ships: [ShipTarget; 5],
+    pub data: CircuitData<F, C, D>,
+    pub ships: [ShipTarget; 5],
+    pub nonce: Target, // game/ session id exported publicly to prevent replaying the proof in another game
+    pub verify_after_prove: bool, // natively verify each proof after generating it (default true)
 }
 
 
 // Argument of knowledge proving board commitment is the hash of a valid board config
 // @dev inner proof that is recursively verified by outer proof to apply shielding
 impl BoardCircuit {
+    /**
+     * Return the number of public inputs exported by proofs of this circuit
+     * @notice BOARD_PUBLIC_INPUTS: kind tag, board commitment and game nonce
+     *
+     * @return - length of the public input vector to allocate when verifying
+     */
+    pub fn num_public_inputs(&self) -> usize {
+        self.data.common.num_public_inputs
+    }
+
     /**
      * Generate a circuit config capable of handling 128 bit random access gates
      *
      * @return - circuit config
      */
     pub fn config_inner() -> Result<CircuitConfig> {
-        let mut config = CircuitConfig::standard_recursion_config();
-        // set wires for random access gate
-        config.num_wires = 137;
-        config.num_routed_wires = 130;
-        Ok(config)
+        Ok(CircuitConfigBuilder::new().with_random_access().build())
     }
 
     /**
@@ -56,10 +92,7 @@ impl BoardCircuit {
      * @return - circuit config
      */
     pub fn config_outer() -> Result<CircuitConfig> {
-        let mut config = CircuitConfig::standard_recursion_config();
-        // toggle zero knowledge blinding
-        config.zero_knowledge = true;
-        Ok(config)
+        Ok(CircuitConfigBuilder::new().with_zk(true).build())
     }
 
     /**
@@ -122,22 +155,69 @@ impl BoardCircuit {
      * @return - circuit data and ship targets
      */
     pub fn build(config: &CircuitConfig) -> Result<BoardCircuit> {
+        BoardCircuit::build_with_ordering(config, false)
+    }
+
+    /**
+     * Layout the board circuit with an optional constraint that ship inputs are canonically ordered
+     * @notice the cruiser and submarine are both length 3, so canonical order requires cruiser head < submarine head
+     * @dev used for fraud-proof reveal so a revealed board maps to exactly one ship input vector
+     *
+     * @param config - circuit config
+     * @param canonical - whether to constrain interchangeable ships to canonical order
+     * @return - circuit data and ship targets
+     */
+    pub fn build_with_ordering(config: &CircuitConfig, canonical: bool) -> Result<BoardCircuit> {
+        BoardCircuit::synthesize(config, canonical, None)
+    }
+
+    /**
+     * Layout the board circuit padded to a common circuit shape
+     * @notice padding public inputs are zeros appended after the commitment
+     *
+     * @param config - circuit config
+     * @param shape - common shape to pad the circuit to
+     * @return - circuit data and ship targets
+     */
+    pub fn build_padded(config: &CircuitConfig, shape: &CircuitShape) -> Result<BoardCircuit> {
+        BoardCircuit::synthesize(config, false, Some(shape))
+    }
+
+    /**
+     * Layout the board circuit
+     *
+     * @param config - circuit config
+     * @param canonical - whether to constrain interchangeable ships to canonical order
+     * @param shape - optional common shape to pad the circuit to
+     * @return - circuit data and ship targets
+     */
+    fn synthesize(
+        config: &CircuitConfig,
+        canonical: bool,
+        shape: Option<&CircuitShape>,
+    ) -> Result<BoardCircuit> {
         // define circuit builder
         let mut builder = CircuitBuilder::<F, D>::new(config.clone());
 
         // ship //
-        let ships: [ShipTarget; 5] = {
-            (0..5)
-                .map(|_| {
-                    let x = builder.add_virtual_target();
-                    let y = builder.add_virtual_target();
-                    let z = builder.add_virtual_bool_target_safe();
-                    (x, y, z)
-                })
-                .collect::<Vec<ShipTarget>>()
-                .try_into()
-                .unwrap()
-        };
+        // @dev each ship target carries its length, so placement below cannot mismatch a ship and its length
+        let carrier = TypedShipTarget::<5>::new(&mut builder);
+        let battleship = TypedShipTarget::<4>::new(&mut builder);
+        let cruiser = TypedShipTarget::<3>::new(&mut builder);
+        let submarine = TypedShipTarget::<3>::new(&mut builder);
+        let destroyer = TypedShipTarget::<2>::new(&mut builder);
+        let ships: [ShipTarget; 5] = [
+            carrier.tuple(),
+            battleship.tuple(),
+            cruiser.tuple(),
+            submarine.tuple(),
+            destroyer.tuple(),
+        ];
+
+        // optionally constrain interchangeable ships (cruiser, submarine) to canonical order
+        if canonical {
+            constrain_ship_order(ships[2], ships[3], &mut builder)?;
+        }
 
         // board (init) //
         let board_blank: [Target; 4] = builder
@@ -147,11 +227,11 @@ impl BoardCircuit {
         let board_initial = decompose_board(board_blank, &mut builder).unwrap();
 
         // place ships on board
-        let board_0 = place_ship::<5>(ships[0], board_initial, &mut builder).unwrap();
-        let board_1 = place_ship::<4>(ships[1], board_0, &mut builder).unwrap();
-        let board_2 = place_ship::<3>(ships[2], board_1, &mut builder).unwrap();
-        let board_3 = place_ship::<3>(ships[3], board_2, &mut builder).unwrap();
-        let board_5 = place_ship::<2>(ships[4], board_3, &mut builder).unwrap();
+        let board_0 = place_typed_ship(carrier, board_initial, &mut builder).unwrap();
+        let board_1 = place_typed_ship(battleship, board_0, &mut builder).unwrap();
+        let board_2 = place_typed_ship(cruiser, board_1, &mut builder).unwrap();
+        let board_3 = place_typed_ship(submarine, board_2, &mut builder).unwrap();
+        let board_5 = place_typed_ship(destroyer, board_3, &mut builder).unwrap();
 
         // recompose board into u128
         let board_final = recompose_board(board_5.clone(), &mut builder).unwrap();
@@ -159,14 +239,29 @@ impl BoardCircuit {
         // // hash the board into the commitment
         let commitment = hash_board(board_final, &mut builder).unwrap();
 
-        // register public inputs (board commitment)
+        // game nonce: unconstrained, only bound to the proof by being exported
+        let nonce = builder.add_virtual_target();
+
+        // register public inputs (circuit kind tag, board commitment, game nonce)
+        CircuitKind::Board.register(&mut builder);
         builder.register_public_inputs(&commitment.elements);
+        builder.register_public_input(nonce);
+
+        // optionally pad to a common circuit shape
+        if let Some(shape) = shape {
+            shape.pad(&mut builder)?;
+        }
 
         // export circuit data
         let data = builder.build::<C>();
 
         // return circuit data and ship targets
-        Ok(Self { data, ships })
+        Ok(Self {
+            data,
+            ships,
+            nonce,
+            verify_after_prove: true,
+        })
     }
 
     /**
@@ -176,73 +271,332 @@ impl BoardCircuit {
      * @return - proof tuple of everything needed to verify the proof natively or recursively
      */
     pub fn prove_inner(board: Board) -> Result<ProofTuple<F, C, D>> {
-        // generate circuit config
-        let config = BoardCircuit::config_inner()?;
+        BoardCircuit::prove_inner_with_ordering(board, false)
+    }
+
+    /**
+     * Prove board validity and return the commitment the proof exports alongside it
+     *
+     * @param board - board configuration
+     * @return - proof tuple and the board commitment decoded from its public inputs
+     */
+    pub fn prove_with_commitment(board: Board) -> Result<(ProofTuple<F, C, D>, [u64; 4])> {
+        let proof = BoardCircuit::prove_inner(board)?;
+        let commitment = BoardCircuit::decode_public(proof.0.clone())?.commitment;
+        Ok((proof, commitment))
+    }
+
+    /**
+     * Prove board validity with an optional constraint that ship inputs are canonically ordered
+     *
+     * @param board - board configuration
+     * @param canonical - whether to constrain interchangeable ships to canonical order
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_inner_with_ordering(board: Board, canonical: bool) -> Result<ProofTuple<F, C, D>> {
+        Ok(BoardCircuit::prove_inner_timed_with_ordering(board, canonical)?.0)
+    }
+
+    /**
+     * Prove board validity bound to a specific game
+     * @notice channel open requires both board proofs to carry the same nonce, so a proof cannot be replayed in
+     *         a game with a different nonce
+     *
+     * @param board - board configuration
+     * @param nonce - game/ session id to bind the proof to
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_inner_with_nonce(board: Board, nonce: u64) -> Result<ProofTuple<F, C, D>> {
+        let circuit = BoardCircuit::build(&BoardCircuit::config_inner()?)?;
+        circuit.prove_cached_with_nonce(board, nonce)
+    }
+
+    /**
+     * Prove board validity and return wall clock timings alongside the proof
+     *
+     * @param board - board configuration
+     * @return - proof tuple and build/ prove/ verify timings
+     */
+    pub fn prove_inner_timed(board: Board) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
+        BoardCircuit::prove_inner_timed_with_ordering(board, false)
+    }
+
+    /**
+     * Prove board validity with optional canonical ordering, returning timings alongside the proof
+     *
+     * @param board - board configuration
+     * @param canonical - if true, additionally constrain ships to canonical order
+     * @return - proof tuple and build/ prove/ verify timings
+     */
+    pub fn prove_inner_timed_with_ordering(
+        board: Board,
+        canonical: bool,
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
+        BoardCircuit::prove_inner_configured(board, canonical, &BoardCircuit::config_inner()?)
+    }
+
+    /**
+     * Prove board validity under a caller supplied circuit config
+     * @notice e.g. CircuitConfigBuilder::new().with_random_access().with_zk(true) makes the inner proof zero knowledge
+     * @dev the config must support 128 bit random access gates (see CircuitConfigBuilder::with_random_access)
+     *
+     * @param board - board configuration
+     * @param config - circuit config to build the board circuit with
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_inner_with_config(
+        board: Board,
+        config: &CircuitConfig,
+    ) -> Result<ProofTuple<F, C, D>> {
+        Ok(BoardCircuit::prove_inner_configured(board, false, config)?.0)
+    }
+
+    /**
+     * Build, prove and verify the board circuit, recording timings
+     *
+     * @param board - board configuration
+     * @param canonical - if true, additionally constrain ships to canonical order
+     * @param config - circuit config to build the board circuit with
+     * @return - proof tuple and build/ prove/ verify timings
+     */
+    fn prove_inner_configured(
+        board: Board,
+        canonical: bool,
+        config: &CircuitConfig,
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
+        let start = Instant::now();
 
         // build inner proof circuit
-        let circuit = BoardCircuit::build(&config)?;
+        let circuit = BoardCircuit::build_with_ordering(config, canonical)?;
+        let build = start.elapsed();
 
-        // witness ships
-        let pw = BoardCircuit::partial_witness_inner(circuit.ships, board)?;
+        // prove against the freshly built circuit
+        let (proof, mut timings) = circuit.prove_cached_timed(board, UNBOUND_NONCE)?;
+        timings.build = build;
+        Ok((proof, timings))
+    }
 
-        // generate proof
+    /**
+     * Prove board validity against an already built board circuit
+     * @dev lets callers build the circuit once (see GameCircuits) and prove many boards with it
+     *
+     * @param board - board configuration
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_cached(&self, board: Board) -> Result<ProofTuple<F, C, D>> {
+        self.prove_cached_with_nonce(board, UNBOUND_NONCE)
+    }
+
+    /**
+     * Prove board validity bound to a game nonce against an already built board circuit
+     *
+     * @param board - board configuration
+     * @param nonce - game/ session id to bind the proof to
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_cached_with_nonce(&self, board: Board, nonce: u64) -> Result<ProofTuple<F, C, D>> {
+        Ok(self.prove_cached_timed(board, nonce)?.0)
+    }
+
+    /**
+     * Prove and verify board validity against an already built board circuit, recording timings
+     *
+     * @param board - board configuration
+     * @param nonce - game/ session id to bind the proof to
+     * @return - proof tuple and prove/ verify timings (build is left at zero)
+     */
+    fn prove_cached_timed(
+        &self,
+        board: Board,
+        nonce: u64,
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
         let mut timing = TimingTree::new("prove", Level::Debug);
-        let proof = prove(
-            &circuit.data.prover_only,
-            &circuit.data.common,
-            pw,
-            &mut timing,
-        )?;
+        let proof = self.prove_cached_in(board, nonce, &mut timing)?;
         timing.print();
+        Ok(proof)
+    }
 
-        // verify the proof was generated correctly
-        circuit.data.verify(proof.clone())?;
+    /**
+     * Prove board validity against an already built board circuit under a "prove board" span of a parent tree
+     *
+     * @param board - board configuration
+     * @param nonce - game/ session id to bind the proof to
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - proof tuple and prove/ verify timings (build is left at zero)
+     */
+    fn prove_cached_in(
+        &self,
+        board: Board,
+        nonce: u64,
+        timing: &mut TimingTree,
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
+        let mut timings = ProofTiming::default();
+
+        // witness ships and game nonce
+        let mut pw = BoardCircuit::partial_witness_inner(self.ships, board)?;
+        pw.set_target(self.nonce, F::from_canonical_u64(nonce));
+
+        // generate proof
+        let start = Instant::now();
+        timing.push("prove board", Level::Debug);
+        let proof = prove(&self.data.prover_only, &self.data.common, pw, timing);
+        timing.pop();
+        let proof = proof?;
+        timings.prove = start.elapsed();
+
+        // verify the proof was generated correctly (skipped by trusted batch provers)
+        if self.verify_after_prove {
+            let start = Instant::now();
+            self.data.verify(proof.clone())?;
+            timings.verify = start.elapsed();
+        }
 
         // PROVE //
-        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+        Ok(((proof, self.data.verifier_only.clone(), self.data.common.clone()), timings))
     }
 
     /**
-     * Recursive outer proof that obfuscates information of inner proof
+     * Prove board validity, recording the proof in a parent timing tree instead of printing its own
+     * @notice pass the same tree through every proof of a game to print one aggregate tree at the end
      *
-     * @param inner - the proof tuple from the execution of the inner BoardCircuit proof
-     * @return - outer proof tuple of everything needed to verify the proof natively or recursively
+     * @param board - board configuration
+     * @param nonce - game/ session id to bind the proof to
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
      */
-    pub fn prove_outer(inner: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
-        // generate circuit config
-        let config = BoardCircuit::config_outer()?;
+    pub fn prove_inner_with_timing(
+        board: Board,
+        nonce: u64,
+        timing: &mut TimingTree,
+    ) -> Result<ProofTuple<F, C, D>> {
+        let circuit = BoardCircuit::build(&BoardCircuit::config_inner()?)?;
+        Ok(circuit.prove_cached_in(board, nonce, timing)?.0)
+    }
 
-        // define targets
-        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
-        let pt = builder.add_virtual_proof_with_pis(&inner.2);
-        let inner_data = builder.add_virtual_verifier_data(inner.2.config.fri_config.cap_height);
-        let outer_targets = RecursiveTargets {
-            proof: pt.clone(),
-            verifier: inner_data.clone(),
-        };
+    /**
+     * Prove a commitment to the empty board (the blank state ships are placed onto)
+     * @notice tagged CircuitKind::EmptyBoard so it can never stand in for a board validity proof
+     * @dev base case for protocols committing to a board before the placement phase
+     *
+     * @return - proof tuple exporting the commitment to the all-zero board
+     */
+    pub fn prove_empty() -> Result<ProofTuple<F, C, D>> {
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfigBuilder::new().build());
 
-        // synthesize outer proof
-        builder.verify_proof::<C>(&pt, &inner_data, &inner.2);
+        // board (blank) //
+        let board_blank: [Target; 4] = builder
+            .constants(&[F::from_canonical_u32(0); 4])
+            .try_into()
+            .unwrap();
+        let commitment = hash_board(board_blank, &mut builder)?;
 
-        // pipe commitment to outer proof public inputs
-        builder.register_public_inputs(&pt.public_inputs);
+        // PUBLIC INPUTS //
+        // @dev same layout as board validity proofs, never bound to a game
+        let nonce = builder.constant(F::from_canonical_u64(UNBOUND_NONCE));
+        CircuitKind::EmptyBoard.register(&mut builder);
+        builder.register_public_inputs(&commitment.elements);
+        builder.register_public_input(nonce);
 
-        // construct circuit data
+        // PROVE //
         let data = builder.build::<C>();
+        let proof = data.prove(PartialWitness::new())?;
+        data.verify(proof.clone())?;
+        Ok((proof, data.verifier_only, data.common))
+    }
+
+    /**
+     * Prove board validity and sign the board commitment, binding the commitment to the player's key
+     *
+     * @param board - board configuration
+     * @param secret_key - secp256k1 secret key of the player
+     * @return - board validity proof and signature over the commitment it exports
+     */
+    pub fn prove_and_sign(
+        board: Board,
+        secret_key: SecretKey,
+    ) -> Result<(ProofTuple<F, C, D>, Signature)> {
+        // sign the commitment exported by the proof
+        let (proof, commitment) = BoardCircuit::prove_with_commitment(board)?;
+        let signature = sign(commitment_message(commitment), secret_key);
+        Ok((proof, signature))
+    }
 
-        // compute partial witness
-        let pw = BoardCircuit::partial_witness_outer(inner, outer_targets)?;
+    /**
+     * Recursive outer proof retried on recoverable (e.g. zero knowledge blinding) proving failures
+     *
+     * @param inner - the proof tuple from the execution of the inner BoardCircuit proof
+     * @param retries - number of additional attempts after the first failure
+     * @return - outer proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_outer_with_retries(
+        inner: ProofTuple<F, C, D>,
+        retries: usize,
+    ) -> Result<ProofTuple<F, C, D>> {
+        prove_with_retries(retries, || BoardCircuit::prove_outer(inner.clone()))
+    }
 
-        // prove outer proof provides valid shielding of a board validity circuit
+    /**
+     * Recursive outer proof that obfuscates information of inner proof
+     *
+     * @param inner - the proof tuple from the execution of the inner BoardCircuit proof
+     * @return - outer proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_outer(inner: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
         let mut timing = TimingTree::new("prove", Level::Debug);
-        let proof = prove(&data.prover_only, &data.common, pw, &mut timing)?;
+        let proof = BoardCircuit::prove_outer_with_timing(inner, &mut timing)?;
         timing.print();
+        Ok(proof)
+    }
 
-        // verify the outer proof's integrity
-        data.verify(proof.clone())?;
+    /**
+     * Recursive outer proof recorded as a "prove board outer" span of a parent timing tree
+     *
+     * @param inner - the proof tuple from the execution of the inner BoardCircuit proof
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - outer proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_outer_with_timing(
+        inner: ProofTuple<F, C, D>,
+        timing: &mut TimingTree,
+    ) -> Result<ProofTuple<F, C, D>> {
+        // generate circuit config
+        let config = BoardCircuit::config_outer()?;
+
+        // decode inner commitment before the inner proof is consumed by the fold
+        let inner_commitment = BoardCircuit::decode_public(inner.0.clone())?.commitment;
+
+        // verify the inner proof and pipe its public inputs to the outer proof
+        let proof = fold_with_timing(inner, &config, "prove board outer", timing, |builder, pt, _| {
+            builder.register_public_inputs(&pt.public_inputs);
+            Ok(())
+        })?;
+
+        // defensively check the commitment survived the inner -> outer transition
+        let outer_commitment = BoardCircuit::decode_public(proof.0.clone())?.commitment;
+        if inner_commitment != outer_commitment {
+            return Err(CircuitError::CommitmentMismatch.into());
+        }
 
         // return outer proof artifacts
-        Ok((proof, data.verifier_only, data.common))
+        Ok(proof)
+    }
+
+    /**
+     * Verify a board proof through a verifier cache and extract its board commitment
+     * @notice verifier data is prepared once per circuit digest, so repeated verifications skip re-deriving it
+     * @dev the board circuit (inner or outer) must have been trusted by the cache, see VerifierCache::trust
+     *
+     * @param cache - verifier cache shared across verifications
+     * @param proof - board validity proof (inner or outer)
+     * @return - board commitment exported by the proof, or error if it does not verify against trusted data
+     */
+    pub fn verify(
+        cache: &VerifierCache,
+        proof: ProofTuple<F, C, D>,
+    ) -> Result<BoardCircuitOutputs> {
+        let outputs = BoardCircuit::decode_public(proof.0.clone())?;
+        cache.verify(&proof.into_verifier_bundle())?;
+        Ok(outputs)
     }
 
     /**
@@ -252,38 +606,148 @@ impl BoardCircuit {
      * @return - 256-bit board commitment as a LE-serialized u64 array
      */
     pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<BoardCircuitOutputs> {
-        let commitment: [u64; 4] = proof
-            .clone()
-            .public_inputs
+        // ensure proof matches the board circuit layout
+        check_public_inputs(&proof, BOARD_PUBLIC_INPUTS)?;
+
+        let commitment: [u64; 4] = proof.public_inputs[1..5]
             .iter()
             .map(|x| x.to_canonical_u64())
             .collect::<Vec<u64>>()
             .try_into()
             .unwrap();
-        Ok(BoardCircuitOutputs { commitment })
+        let nonce = proof.public_inputs[5].to_canonical_u64();
+        Ok(BoardCircuitOutputs { commitment, nonce })
+    }
+}
+
+// Verifier pinned to the standard (inner) board circuit of this crate
+// @dev the verifier data bundled with an untrusted proof is never used: a malicious prover could bundle the data of
+//      a variant circuit that skips placement constraints, so its circuit digest must match the trusted circuit
+pub struct BoardVerifier {
+    pub verifier_only: VerifierOnlyCircuitData<C, D>,
+    pub common: CommonCircuitData<F, D>,
+}
+
+impl BoardVerifier {
+    /**
+     * Build the standard board circuit and keep only the verifier data needed to check its proofs
+     *
+     * @return - verifier trusting the board circuit built under BoardCircuit::config_inner
+     */
+    pub fn new() -> Result<BoardVerifier> {
+        let circuit = BoardCircuit::build(&BoardCircuit::config_inner()?)?;
+        Ok(BoardVerifier::from_circuit(&circuit))
+    }
+
+    /**
+     * Trust the verifier data of an already built board circuit (see GameCircuits)
+     *
+     * @param circuit - board circuit to trust
+     * @return - verifier trusting the given board circuit
+     */
+    pub fn from_circuit(circuit: &BoardCircuit) -> BoardVerifier {
+        BoardVerifier {
+            verifier_only: circuit.data.verifier_only.clone(),
+            common: circuit.data.common.clone(),
+        }
+    }
+
+    /**
+     * Verify a board proof received from an untrusted prover against the trusted board circuit
+     *
+     * @param proof - board proof tuple as sent by the prover
+     * @return - board commitment and nonce, or UntrustedCircuit if the proof claims a different circuit digest
+     */
+    pub fn verify(&self, proof: ProofTuple<F, C, D>) -> Result<BoardCircuitOutputs> {
+        if proof.1.circuit_digest != self.verifier_only.circuit_digest {
+            return Err(CircuitError::UntrustedCircuit.into());
+        }
+
+        // verify against the trusted verifier data, never the data bundled with the proof
+        let verifier = VerifierCircuitData {
+            verifier_only: self.verifier_only.clone(),
+            common: self.common.clone(),
+        };
+        verifier.verify(proof.0.clone())?;
+        BoardCircuit::decode_public(proof.0)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::{board::Board, ship::Ship};
+    use crate::{
+        circuits::{
+            decode_any,
+            test_utils::{assert_common_data_eq, dummy_proof},
+            CircuitError, DecodedPublic, IntoVerifierBundle, H,
+        },
+        utils::{board::Board, ecdsa::keypair, ship::Ship},
+    };
+    use plonky2::plonk::config::Hasher;
+    use plonky2_ecdsa::curve::ecdsa::verify_message;
+    use proptest::{
+        prelude::*,
+        test_runner::{Config, TestRunner},
+    };
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    // number of random boards proven per fuzz run (each case is a full board proof)
+    const FUZZ_CASES: u32 = 16;
+
+    /**
+     * Strategy for a ship of length L placed fully on the grid, in either orientation
+     * @dev shrinks towards the top-left corner in horizontal orientation
+     */
+    fn on_board_ship<const L: usize>() -> impl Strategy<Value = Ship<L>> {
+        (any::<bool>(), 0..=(10 - L as u8), 0u8..10).prop_map(|(z, along, across)| match z {
+            true => Ship::new(across, along, true),
+            false => Ship::new(along, across, false),
+        })
+    }
+
+    /**
+     * Strategy for a ship of length L with an arbitrary head, which may run off the grid
+     */
+    fn any_ship<const L: usize>() -> impl Strategy<Value = Ship<L>> {
+        (0u8..10, 0u8..10, any::<bool>()).prop_map(|(x, y, z)| Ship::new(x, y, z))
+    }
+
+    /**
+     * Strategy for boards passing native validation (on the grid, no overlaps)
+     */
+    fn valid_board() -> impl Strategy<Value = Board> {
+        (
+            on_board_ship::<5>(),
+            on_board_ship::<4>(),
+            on_board_ship::<3>(),
+            on_board_ship::<3>(),
+            on_board_ship::<2>(),
+        )
+            .prop_map(|(a, b, c, d, e)| Board::new(a, b, c, d, e))
+            .prop_filter("ships overlap", |board| board.validate().is_ok())
+    }
+
+    /**
+     * Strategy for boards failing native validation (off the grid or overlapping)
+     */
+    fn invalid_board() -> impl Strategy<Value = Board> {
+        (any_ship::<5>(), any_ship::<4>(), any_ship::<3>(), any_ship::<3>(), any_ship::<2>())
+            .prop_map(|(a, b, c, d, e)| Board::new(a, b, c, d, e))
+            .prop_filter("board is valid", |board| board.validate().is_err())
+    }
 
     #[test]
     fn test_shielded() {
         // define circuit input (valid board)
-        let board = Board::new(
-            Ship::new(3, 4, false),
-            Ship::new(9, 6, true),
-            Ship::new(0, 0, false),
-            Ship::new(0, 6, false),
-            Ship::new(6, 1, true),
-        );
+        let board = Board::standard_host();
 
         // prove inner proof
         let inner = BoardCircuit::prove_inner(board.clone()).unwrap();
         println!("Inner proof successful");
 
+        let inner_commitment = BoardCircuit::decode_public(inner.0.clone()).unwrap().commitment;
+
         // prove outer proof
         let outer = BoardCircuit::prove_outer(inner).unwrap();
         println!("Outer proof successful");
@@ -292,5 +756,248 @@ mod tests {
         let commitment = BoardCircuit::decode_public(outer.0).unwrap().commitment;
         let expected_commitment = board.hash();
         assert_eq!(commitment, expected_commitment);
+
+        // commitment survives the inner -> outer transition unchanged
+        assert_eq!(commitment, inner_commitment);
+    }
+
+    #[test]
+    fn test_canonical_ordering() {
+        // cruiser head (0, 0) precedes submarine head (0, 6)
+        let board = Board::standard_host();
+        assert!(board.is_canonical());
+
+        // prove board with canonical ordering constraint
+        let inner = BoardCircuit::prove_inner_with_ordering(board.clone(), true).unwrap();
+        let commitment = BoardCircuit::decode_public(inner.0).unwrap().commitment;
+        assert_eq!(commitment, board.hash());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_non_canonical_ordering() {
+        // same board as canonical test but with cruiser and submarine swapped
+        let board = Board::new(
+            Ship::new(3, 4, false),
+            Ship::new(9, 6, true),
+            Ship::new(0, 6, false),
+            Ship::new(0, 0, false),
+            Ship::new(6, 1, true),
+        );
+        assert!(!board.is_canonical());
+
+        // copy constraint on ship order fails
+        BoardCircuit::prove_inner_with_ordering(board, true).unwrap();
+    }
+
+    #[test]
+    fn test_decode_public_shape() {
+        // truncated public inputs
+        let err = BoardCircuit::decode_public(dummy_proof(BOARD_PUBLIC_INPUTS - 1)).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::PublicInputShape));
+
+        // over-long public inputs
+        let err = BoardCircuit::decode_public(dummy_proof(BOARD_PUBLIC_INPUTS + 1)).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::PublicInputShape));
+
+        // well formed public inputs
+        assert!(BoardCircuit::decode_public(dummy_proof(BOARD_PUBLIC_INPUTS)).is_ok());
+    }
+
+    #[test]
+    fn test_timed_board() {
+        // prove a board and collect timings
+        let board = Board::standard_host();
+        let (proof, timing) = BoardCircuit::prove_inner_timed(board.clone()).unwrap();
+        let commitment = BoardCircuit::decode_public(proof.0).unwrap().commitment;
+        assert_eq!(commitment, board.hash());
+
+        // total covers build, prove and verify
+        assert!(timing.total() > std::time::Duration::ZERO);
+        assert_eq!(timing.total(), timing.build + timing.prove + timing.verify);
+    }
+
+    #[test]
+    fn test_zk_inner_proof() {
+        // blind the inner board proof directly instead of shielding it with an outer proof
+        let board = Board::standard_host();
+        let config = CircuitConfigBuilder::new().with_random_access().with_zk(true).build();
+        let inner = BoardCircuit::prove_inner_with_config(board.clone(), &config).unwrap();
+        assert!(inner.2.config.zero_knowledge);
+        assert!(!BoardCircuit::config_inner().unwrap().zero_knowledge);
+
+        // still verifies and decodes
+        inner.clone().into_verifier_bundle().verify().unwrap();
+        let commitment = BoardCircuit::decode_public(inner.0).unwrap().commitment;
+        assert_eq!(commitment, board.hash());
+    }
+
+    #[test]
+    fn test_prove_with_commitment() {
+        let board = Board::standard_guest();
+        let (proof, commitment) = BoardCircuit::prove_with_commitment(board.clone()).unwrap();
+        assert_eq!(commitment, board.hash());
+        assert_eq!(BoardCircuit::decode_public(proof.0).unwrap().commitment, commitment);
+    }
+
+    #[test]
+    fn test_prove_and_sign() {
+        let board = Board::standard_host();
+        let (secret_key, public_key) = keypair();
+        let (proof, signature) = BoardCircuit::prove_and_sign(board.clone(), secret_key).unwrap();
+
+        // signature binds the decoded commitment to the player's key
+        let commitment = BoardCircuit::decode_public(proof.0).unwrap().commitment;
+        assert_eq!(commitment, board.hash());
+        assert!(verify_message(commitment_message(commitment), signature, public_key));
+
+        // signature does not verify against another board's commitment or another key
+        let other = commitment_message(Board::standard_guest().hash());
+        assert!(!verify_message(other, signature, public_key));
+        let (_, other_key) = keypair();
+        assert!(!verify_message(commitment_message(commitment), signature, other_key));
+    }
+
+    #[test]
+    fn test_build_deterministic() {
+        // recursive verifiers are built against the board circuit's common data, so it must not vary between builds
+        let config = BoardCircuit::config_inner().unwrap();
+        let first = BoardCircuit::build(&config).unwrap();
+        let second = BoardCircuit::build(&config).unwrap();
+        assert_common_data_eq(&first.data.common, &second.data.common);
+        assert_eq!(
+            first.data.verifier_only.circuit_digest,
+            second.data.verifier_only.circuit_digest
+        );
+    }
+
+    #[test]
+    fn test_prove_empty() {
+        let proof = BoardCircuit::prove_empty().unwrap();
+
+        // exported tag marks the proof as an empty board proof
+        assert_eq!(
+            proof.0.public_inputs[0].to_canonical_u64(),
+            CircuitKind::EmptyBoard as u64
+        );
+        let commitment = match decode_any(proof.0).unwrap() {
+            DecodedPublic::EmptyBoard(output) => output.commitment,
+            _ => panic!("expected empty board proof"),
+        };
+
+        // commitment is the poseidon hash of the empty board
+        let empty = Board::pack_bits(&[false; 100]).map(F::from_canonical_u32);
+        let expected = H::hash_no_pad(&empty).elements.map(|x| x.to_canonical_u64());
+        assert_eq!(commitment, expected);
+
+        // distinct from the commitment of any real board
+        assert_ne!(commitment, Board::standard_host().hash());
+        assert_ne!(commitment, Board::standard_guest().hash());
+    }
+
+    #[test]
+    fn test_skip_self_verify() {
+        // trusted batch prover skips the verification done inside the prove call
+        let mut circuit = BoardCircuit::build(&BoardCircuit::config_inner().unwrap()).unwrap();
+        circuit.verify_after_prove = false;
+        let (proof, timings) =
+            circuit.prove_cached_timed(Board::standard_host(), UNBOUND_NONCE).unwrap();
+        assert_eq!(timings.verify, std::time::Duration::ZERO);
+
+        // the proof still verifies externally
+        proof.into_verifier_bundle().verify().unwrap();
+    }
+
+    #[test]
+    fn fuzz_valid_boards() {
+        // one circuit proves every generated board; failures shrink to a minimal placement
+        let circuit = BoardCircuit::build(&BoardCircuit::config_inner().unwrap()).unwrap();
+        let mut runner = TestRunner::new(Config {
+            cases: FUZZ_CASES,
+            ..Config::default()
+        });
+        runner
+            .run(&valid_board(), |board| {
+                let proof = circuit
+                    .prove_cached(board.clone())
+                    .map_err(|e| TestCaseError::fail(e.to_string()))?;
+                let outputs = BoardCircuit::decode_public(proof.0)
+                    .map_err(|e| TestCaseError::fail(e.to_string()))?;
+                prop_assert_eq!(outputs.commitment, board.hash());
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn fuzz_invalid_boards() {
+        // off-board or overlapping placements must either error or fail witness generation
+        let circuit = BoardCircuit::build(&BoardCircuit::config_inner().unwrap()).unwrap();
+        let mut runner = TestRunner::new(Config {
+            cases: FUZZ_CASES,
+            ..Config::default()
+        });
+        runner
+            .run(&invalid_board(), |board| {
+                let proven = catch_unwind(AssertUnwindSafe(|| circuit.prove_cached(board.clone())));
+                prop_assert!(!matches!(proven, Ok(Ok(_))), "invalid board was proven: {:?}", board);
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    /**
+     * Prove a board against a variant of the board circuit that never places the destroyer
+     * @dev same public input layout as the standard circuit, so only the circuit digest gives it away
+     *
+     * @param board - board configuration (destroyer ignored)
+     * @return - proof tuple of the variant circuit
+     */
+    fn prove_without_destroyer(board: &Board) -> ProofTuple<F, C, D> {
+        let mut builder = CircuitBuilder::<F, D>::new(BoardCircuit::config_inner().unwrap());
+        let carrier = TypedShipTarget::<5>::new(&mut builder);
+        let battleship = TypedShipTarget::<4>::new(&mut builder);
+        let cruiser = TypedShipTarget::<3>::new(&mut builder);
+        let submarine = TypedShipTarget::<3>::new(&mut builder);
+        let blank: [Target; 4] = builder.constants(&[F::ZERO; 4]).try_into().unwrap();
+        let mut bits = decompose_board(blank, &mut builder).unwrap();
+        bits = place_typed_ship(carrier, bits, &mut builder).unwrap();
+        bits = place_typed_ship(battleship, bits, &mut builder).unwrap();
+        bits = place_typed_ship(cruiser, bits, &mut builder).unwrap();
+        bits = place_typed_ship(submarine, bits, &mut builder).unwrap();
+        let limbs = recompose_board(bits, &mut builder).unwrap();
+        let commitment = hash_board(limbs, &mut builder).unwrap();
+        let nonce = builder.constant(F::from_canonical_u64(UNBOUND_NONCE));
+        CircuitKind::Board.register(&mut builder);
+        builder.register_public_inputs(&commitment.elements);
+        builder.register_public_input(nonce);
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        carrier.witness(&mut pw, &board.carrier);
+        battleship.witness(&mut pw, &board.battleship);
+        cruiser.witness(&mut pw, &board.cruiser);
+        submarine.witness(&mut pw, &board.submarine);
+        (data.prove(pw).unwrap(), data.verifier_only, data.common)
+    }
+
+    #[test]
+    fn test_board_verifier() {
+        let verifier = BoardVerifier::new().unwrap();
+        let board = Board::standard_host();
+
+        // proof from the standard circuit is accepted
+        let outputs = verifier.verify(BoardCircuit::prove_inner(board.clone()).unwrap()).unwrap();
+        assert_eq!(outputs.commitment, board.hash());
+
+        // proof from a circuit placing only four ships is rejected by its digest
+        let variant = prove_without_destroyer(&board);
+        assert_eq!(variant.0.public_inputs.len(), BOARD_PUBLIC_INPUTS);
+        let err = verifier.verify(variant.clone()).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::UntrustedCircuit));
+
+        // claiming the trusted digest does not help: the proof fails under the trusted verifier data
+        let forged = (variant.0, verifier.verifier_only.clone(), variant.2);
+        assert!(verifier.verify(forged).is_err());
     }
 }
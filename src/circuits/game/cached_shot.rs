@@ -0,0 +1,251 @@
+use {
+    super::{
+        super::{CircuitKind, ProofTuple, RecursiveTargets, C, D, F},
+        board::{BoardCircuit, BoardVerifier},
+        shot::ShotCircuit,
+    },
+    crate::{
+        gadgets::{
+            board::hash_board,
+            shot::{check_hit, serialize_shot},
+        },
+        utils::board::Board,
+    },
+    anyhow::Result,
+    log::Level,
+    plonky2::{
+        iop::{target::Target, witness::WitnessWrite},
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitConfig, CircuitData, CommonCircuitData},
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// BattleZips Cached Shot: shot proofs that recursively reference a single board validity proof
+//  - the board validity proof is generated once per game and verified inside every shot proof
+//  - the shot circuit is built once and reused for every shot against the board
+//  - the board verifier data is a circuit constant pinned to the trusted BoardCircuit digest (see BoardVerifier)
+//  - the raw board is still witnessed privately since hit/ miss is read from the board bits; it is bound to the
+//    commitment of the board proof, which is exported as is rather than a commitment recomputed by the shot circuit
+//  - exports the same public layout as ShotCircuit, but NOT the same common data: state increments are built against
+//    ShotCircuit and reject cached shot proofs
+// @dev every shot proof costs more than a ShotCircuit proof (board hash plus a recursive board verification). The hash
+//      cannot be dropped: the board proof only exports a Poseidon commitment, and nothing short of rehashing the
+//      witnessed board ties a single hit bit to it. The cache saves board proving, not shot proving
+
+pub struct CachedShotCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub board_t: [Target; 4],
+    pub shot_t: [Target; 2],
+    pub board_proof_t: RecursiveTargets,
+}
+
+pub struct CachedShotProver {
+    pub board: Board,
+    pub board_proof: ProofTuple<F, C, D>,
+    pub circuit: CachedShotCircuit,
+}
+
+impl CachedShotCircuit {
+    /**
+     * Layout the circuit for proving a shot against a board committed in a board validity proof
+     *
+     * @param config - circuit config
+     * @param board - verifier trusting the board circuit whose proofs are referenced
+     * @return - circuit data and board/ shot/ board proof targets
+     */
+    pub fn build(config: &CircuitConfig, board: &BoardVerifier) -> Result<CachedShotCircuit> {
+        // define circuit builder
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // input targets
+        let board_t: [Target; 4] = builder.add_virtual_targets(4).try_into().unwrap();
+        let shot_t: [Target; 2] = builder.add_virtual_targets(2).try_into().unwrap();
+
+        // board validity proof targets, with the verifier data fixed to the trusted board circuit
+        let board_common: &CommonCircuitData<F, D> = &board.common;
+        let board_pt = builder.add_virtual_proof_with_pis(board_common);
+        let board_data = builder.constant_verifier_data(&board.verifier_only);
+        let board_proof_t = RecursiveTargets {
+            proof: board_pt.clone(),
+            verifier: board_data.clone(),
+        };
+
+        // verify the cached board validity proof
+        builder.verify_proof::<C>(&board_pt, &board_data, board_common);
+
+        // serialize shot coordinate
+        let serialized_t = serialize_shot(shot_t[0], shot_t[1], &mut builder).unwrap();
+
+        // export circuit kind tag
+        CircuitKind::Shot.register(&mut builder);
+
+        // export serialized shot value
+        builder.register_public_input(serialized_t);
+
+        // check for hit or miss
+        let hit = check_hit(board_t, serialized_t, &mut builder).unwrap();
+
+        // export hit/ miss boolean
+        builder.register_public_input(hit);
+
+        // bind witnessed board to the commitment of the board validity proof
+        let board_hash_t = hash_board(board_t, &mut builder).unwrap();
+        let commitment_t = &board_pt.public_inputs[1..5];
+        for i in 0..4 {
+            builder.connect(board_hash_t.elements[i], commitment_t[i]);
+        }
+
+        // export the commitment of the board validity proof publicly
+        builder.register_public_inputs(commitment_t);
+
+        // return circuit data and input targets
+        let data = builder.build::<C>();
+        Ok(Self {
+            data,
+            board_t,
+            shot_t,
+            board_proof_t,
+        })
+    }
+}
+
+impl CachedShotProver {
+    /**
+     * Prove board validity once and build the shot circuit that references it
+     *
+     * @param board - board configuration
+     * @return - prover caching the board proof and shot circuit for the whole game
+     */
+    pub fn new(board: Board) -> Result<CachedShotProver> {
+        // prove board validity once
+        let board_circuit = BoardCircuit::build(&BoardCircuit::config_inner()?)?;
+        let board_proof = board_circuit.prove_cached(board.clone())?;
+        let verifier = BoardVerifier::from_circuit(&board_circuit);
+        CachedShotProver::from_proof(board, board_proof, &verifier)
+    }
+
+    /**
+     * Build the shot circuit referencing a board proof made elsewhere
+     *
+     * @param board - board configuration committed to by the board proof
+     * @param board_proof - board validity proof to reference in every shot proof
+     * @param verifier - verifier trusting the board circuit
+     * @return - prover caching the board proof, or UntrustedCircuit if the proof claims a different circuit digest
+     */
+    pub fn from_proof(
+        board: Board,
+        board_proof: ProofTuple<F, C, D>,
+        verifier: &BoardVerifier,
+    ) -> Result<CachedShotProver> {
+        // reject board proofs that do not come from the trusted board circuit
+        verifier.verify(board_proof.clone())?;
+
+        // build shot circuit against the board proof once
+        let config = ShotCircuit::config_inner()?;
+        let circuit = CachedShotCircuit::build(&config, verifier)?;
+
+        Ok(Self {
+            board,
+            board_proof,
+            circuit,
+        })
+    }
+
+    /**
+     * Return the board commitment exported by the cached board proof
+     *
+     * @return - board commitment
+     */
+    pub fn commitment(&self) -> Result<[u64; 4]> {
+        Ok(BoardCircuit::decode_public(self.board_proof.0.clone())?.commitment)
+    }
+
+    /**
+     * Generate a shot proof against the cached board proof
+     *
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove(&self, shot: [u8; 2]) -> Result<ProofTuple<F, C, D>> {
+        // witness board and shot
+        let mut pw = ShotCircuit::partial_witness_inner(
+            shot,
+            self.board.clone(),
+            self.circuit.shot_t,
+            self.circuit.board_t,
+        )?;
+
+        // witness cached board proof (verifier data is a circuit constant)
+        pw.set_proof_with_pis_target(&self.circuit.board_proof_t.proof, &self.board_proof.0);
+
+        // generate proof
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(
+            &self.circuit.data.prover_only,
+            &self.circuit.data.common,
+            pw,
+            &mut timing,
+        )?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        self.circuit.data.verify(proof.clone())?;
+
+        Ok((
+            proof,
+            self.circuit.data.verifier_only.clone(),
+            self.circuit.data.common.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circuits::CircuitError, utils::shot::serialize_shot};
+
+    #[test]
+    fn test_cached_board_shots() {
+        // prove board once
+        let board = Board::standard_host();
+        let prover = CachedShotProver::new(board.clone()).unwrap();
+        let commitment = prover.commitment().unwrap();
+        assert_eq!(commitment, board.hash());
+
+        // prove a sequence of shots against the same cached board proof
+        let shots = [([0u8, 0], true), ([0u8, 1], false), ([9u8, 7], true), ([5u8, 5], false)];
+        for (shot, expected_hit) in shots {
+            let proof = prover.prove(shot).unwrap();
+            let output = ShotCircuit::decode_public(proof.0).unwrap();
+            assert_eq!(Some(output.shot), serialize_shot(shot[0], shot[1]));
+            assert_eq!(output.hit.is_hit(), expected_hit);
+            assert_eq!(output.commitment, commitment);
+        }
+    }
+
+    #[test]
+    fn test_not_interchangeable_with_shot_circuit() {
+        // same public layout as ShotCircuit but different common data, so state increments reject cached shots
+        let prover = CachedShotProver::new(Board::standard_host()).unwrap();
+        let shot = ShotCircuit::build(&ShotCircuit::config_inner().unwrap()).unwrap();
+        assert_eq!(prover.circuit.data.common.num_public_inputs, shot.data.common.num_public_inputs);
+        assert!(prover.circuit.data.common != shot.data.common);
+    }
+
+    #[test]
+    fn test_untrusted_board_proof() {
+        // a board proof from a variant of the board circuit is rejected by its digest
+        let board = Board::standard_host();
+        let variant = BoardCircuit::build_with_ordering(&BoardCircuit::config_inner().unwrap(), true)
+            .unwrap()
+            .prove_cached(board.clone())
+            .unwrap();
+        let verifier = BoardVerifier::new().unwrap();
+        let err = CachedShotProver::from_proof(board, variant, &verifier).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::UntrustedCircuit));
+    }
+}
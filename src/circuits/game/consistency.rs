@@ -0,0 +1,256 @@
+use {
+    super::super::{check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, C, D, F},
+    crate::{
+        gadgets::board::{decompose_board, hash_board},
+        utils::{board::Board, shot::serialize_shot},
+    },
+    anyhow::{anyhow, ensure, Result},
+    log::Level,
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        iop::{
+            target::Target,
+            witness::{PartialWitness, WitnessWrite},
+        },
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitConfig, CircuitData},
+            proof::ProofWithPublicInputs,
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// number of public inputs exported by the consistency circuit (kind tag, board, shots, hits, commitment)
+pub const CONSISTENCY_PUBLIC_INPUTS: usize = 17;
+
+pub struct ConsistencyCircuitOutputs {
+    pub board: [u32; 4], // revealed board as a u128 serialized in LE by 4 u32s
+    pub shots: [u32; 4], // recorded shot coordinates as a u128 serialized in LE by 4 u32s
+    pub hits: [u32; 4],  // recorded hits as a u128 serialized in LE by 4 u32s
+    pub commitment: [u64; 4],
+}
+
+pub struct ConsistencyCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub board_t: [Target; 4],
+    pub shots_t: [Target; 4],
+    pub hits_t: [Target; 4],
+}
+
+// Fraud proof that a board revealed at the end of a game is consistent with every hit/ miss the channel recorded
+// @dev the recorded moves are accumulated into a shot bitmap and a hit bitmap; every cell must satisfy
+//      hit = shot * occupied, so a recorded hit on an empty cell or a recorded miss on a ship fails the proof
+// @dev the bitmaps are free private witnesses: the channel state only carries the latest shot, so nothing in-circuit
+//      ties them to the recorded moves. A verifier must rebuild them from the transcript and compare them to the
+//      exported bitmaps (see check_transcript), otherwise a prover can pick bitmaps that happen to fit the board
+impl ConsistencyCircuit {
+    /**
+     * Generate a circuit config for the consistency circuit
+     *
+     * @return - circuit config
+     */
+    pub fn config_inner() -> Result<CircuitConfig> {
+        Ok(CircuitConfigBuilder::new().build())
+    }
+
+    /**
+     * Accumulate recorded shot results into shot and hit bitmaps
+     *
+     * @param results - recorded (shot coordinate, hit) pairs made against the revealed board
+     * @return - (shot bitmap, hit bitmap) indexed by 10y + x, or error if a shot is off the board or recorded twice
+     *           with different results
+     */
+    pub fn accumulate(results: &[([u8; 2], bool)]) -> Result<([bool; 100], [bool; 100])> {
+        let mut shots = [false; 100];
+        let mut hits = [false; 100];
+        for (shot, hit) in results.iter() {
+            let index = serialize_shot(shot[0], shot[1])
+                .ok_or_else(|| anyhow!("shot ({}, {}) is off the board", shot[0], shot[1]))?
+                as usize;
+            ensure!(
+                !shots[index] || hits[index] == *hit,
+                "shot ({}, {}) recorded with conflicting results",
+                shot[0],
+                shot[1]
+            );
+            shots[index] = true;
+            hits[index] = *hit;
+        }
+        Ok((shots, hits))
+    }
+
+    /**
+     * Check the bitmaps exported by a consistency proof were accumulated from the recorded shot results
+     * @notice required of every verifier: the circuit does not bind the bitmaps to the channel (see above)
+     *
+     * @param outputs - decoded outputs of a verified consistency proof
+     * @param results - recorded (shot coordinate, hit) pairs made against the revealed board, from the transcript
+     * @return - error if the exported bitmaps differ from the ones rebuilt from the results
+     */
+    pub fn check_transcript(
+        outputs: &ConsistencyCircuitOutputs,
+        results: &[([u8; 2], bool)],
+    ) -> Result<()> {
+        let (shots, hits) = ConsistencyCircuit::accumulate(results)?;
+        ensure!(
+            outputs.shots == Board::pack_bits(&shots) && outputs.hits == Board::pack_bits(&hits),
+            "consistency proof bitmaps do not match the recorded shot results"
+        );
+        Ok(())
+    }
+
+    /**
+     * Layout the circuit checking a revealed board against recorded shot results
+     *
+     * @param config - circuit config
+     * @return - circuit data and board/ shot/ hit targets
+     */
+    pub fn build(config: &CircuitConfig) -> Result<ConsistencyCircuit> {
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // TARGETS //
+        let board_t = builder.add_virtual_target_arr::<4>();
+        let shots_t = builder.add_virtual_target_arr::<4>();
+        let hits_t = builder.add_virtual_target_arr::<4>();
+
+        // SYNTHESIZE //
+        // re-derive every recorded result from the revealed board
+        let board_bits = decompose_board(board_t, &mut builder)?;
+        let shot_bits = decompose_board(shots_t, &mut builder)?;
+        let hit_bits = decompose_board(hits_t, &mut builder)?;
+        for i in 0..board_bits.len() {
+            let derived = builder.mul(shot_bits[i], board_bits[i]);
+            builder.connect(derived, hit_bits[i]);
+        }
+
+        // bind the revealed board to the commitment used throughout the channel
+        let commitment = hash_board(board_t, &mut builder)?;
+
+        // PUBLIC INPUTS //
+        //  - [0] = circuit kind tag (consistency)
+        //  - [1..5] = revealed board
+        //  - [5..9] = recorded shots
+        //  - [9..13] = recorded hits
+        //  - [13..17] = board commitment
+        CircuitKind::Consistency.register(&mut builder);
+        builder.register_public_inputs(&board_t);
+        builder.register_public_inputs(&shots_t);
+        builder.register_public_inputs(&hits_t);
+        builder.register_public_inputs(&commitment.elements);
+
+        let data = builder.build::<C>();
+        Ok(Self {
+            data,
+            board_t,
+            shots_t,
+            hits_t,
+        })
+    }
+
+    /**
+     * Prove a revealed board is consistent with the recorded results of every shot made against it
+     *
+     * @param board - revealed board configuration
+     * @param results - recorded (shot coordinate, hit) pairs made against the board
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove(board: Board, results: &[([u8; 2], bool)]) -> Result<ProofTuple<F, C, D>> {
+        let circuit = ConsistencyCircuit::build(&ConsistencyCircuit::config_inner()?)?;
+
+        // WITNESS //
+        let (shots, hits) = ConsistencyCircuit::accumulate(results)?;
+        let mut pw = PartialWitness::new();
+        pw.set_target_arr(circuit.board_t, board.canonical().map(F::from_canonical_u32));
+        pw.set_target_arr(circuit.shots_t, Board::pack_bits(&shots).map(F::from_canonical_u32));
+        pw.set_target_arr(circuit.hits_t, Board::pack_bits(&hits).map(F::from_canonical_u32));
+
+        // PROVE //
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&circuit.data.prover_only, &circuit.data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        circuit.data.verify(proof.clone())?;
+        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+    }
+
+    /**
+     * Decode the output of a consistency proof
+     *
+     * @param proof - proof from consistency circuit
+     * @return - formatted outputs from consistency circuit
+     */
+    pub fn decode_public(
+        proof: ProofWithPublicInputs<F, C, D>,
+    ) -> Result<ConsistencyCircuitOutputs> {
+        // ensure proof matches the consistency circuit layout
+        check_public_inputs(&proof, CONSISTENCY_PUBLIC_INPUTS)?;
+
+        let public_inputs = proof.public_inputs;
+        let limbs = |offset: usize| -> [u32; 4] {
+            public_inputs[offset..offset + 4]
+                .iter()
+                .map(|x| x.to_canonical_u64() as u32)
+                .collect::<Vec<u32>>()
+                .try_into()
+                .unwrap()
+        };
+        let commitment: [u64; 4] = public_inputs[13..17]
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap();
+        Ok(ConsistencyCircuitOutputs {
+            board: limbs(1),
+            shots: limbs(5),
+            hits: limbs(9),
+            commitment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::transcript::GameTranscript;
+
+    #[test]
+    fn test_consistent_reveal() {
+        let host = Board::standard_host();
+        let guest = Board::standard_guest();
+
+        // results recorded against the host board (odd shots of the transcript)
+        let transcript =
+            GameTranscript::record(&host, &guest, &[[5, 5], [3, 4], [1, 1], [9, 9], [2, 2], [0, 5]])
+                .unwrap();
+        let results = transcript.shots.iter().skip(1).step_by(2).copied().collect::<Vec<_>>();
+        assert_eq!(results.iter().filter(|(_, hit)| *hit).count(), 2);
+
+        let proof = ConsistencyCircuit::prove(host.clone(), &results).unwrap();
+        let outputs = ConsistencyCircuit::decode_public(proof.0).unwrap();
+        assert_eq!(outputs.board, host.canonical());
+        assert_eq!(outputs.commitment, host.hash());
+
+        // exported bitmaps match the transcript, but not a transcript missing a recorded result
+        ConsistencyCircuit::check_transcript(&outputs, &results).unwrap();
+        assert!(ConsistencyCircuit::check_transcript(&outputs, &results[1..]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inconsistent_reveal() {
+        // (9, 9) was recorded as a hit, but the revealed guest board has no ship there
+        let results = [([9u8, 9], true), ([1, 0], false)];
+        assert!(!Board::standard_guest().bits()[99]);
+        ConsistencyCircuit::prove(Board::standard_guest(), &results).unwrap();
+    }
+
+    #[test]
+    fn test_accumulate_conflicting() {
+        assert!(ConsistencyCircuit::accumulate(&[([1, 1], true), ([1, 1], false)]).is_err());
+        assert!(ConsistencyCircuit::accumulate(&[([10, 0], false)]).is_err());
+    }
+}
@@ -0,0 +1,418 @@
+use {
+    super::{
+        super::{
+            check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, RecursiveTargets, C,
+            D, F,
+        },
+        board::{BoardCircuit, BoardVerifier},
+    },
+    crate::{
+        gadgets::board::{
+            assert_ship_ends_empty, decompose_board, hash_board, ship_to_coordinates,
+        },
+        utils::{board::Board, ship::Ship},
+    },
+    anyhow::Result,
+    log::Level,
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        iop::{
+            target::{BoolTarget, Target},
+            witness::{PartialWitness, WitnessWrite},
+        },
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitConfig, CircuitData},
+            proof::ProofWithPublicInputs,
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// number of public inputs exported by the contains ship circuit (kind tag, ship x, y, z, length, commitment)
+pub const CONTAINS_SHIP_PUBLIC_INPUTS: usize = 9;
+
+pub struct ContainsShipCircuitOutputs {
+    pub ship: (u8, u8, bool),
+    pub length: u8,
+    pub commitment: [u64; 4],
+}
+
+pub struct ContainsShipCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub board_t: [Target; 4],
+    pub ship_t: (Target, Target, BoolTarget),
+    pub board_proof_t: RecursiveTargets,
+}
+
+// Argument of knowledge proving a committed board contains a given ship without revealing the other ships
+// @dev used for tutorial/ puzzle modes where a single placement is disclosed
+// @dev the board is bound to the commitment of a board validity proof verified in-circuit, so the claim is made
+//      against a legal fleet rather than an arbitrary bitmap
+impl ContainsShipCircuit {
+    /**
+     * Generate a circuit config capable of handling 128 bit random access gates
+     *
+     * @return - circuit config
+     */
+    pub fn config_inner() -> Result<CircuitConfig> {
+        Ok(CircuitConfigBuilder::new().with_random_access().build())
+    }
+
+    /**
+     * Generate a circuit config that uses zero knowledge blinding
+     *
+     * @return - circuit config
+     */
+    pub fn config_outer() -> Result<CircuitConfig> {
+        Ok(CircuitConfigBuilder::new().with_zk(true).build())
+    }
+
+    /**
+     * Generate the witness for the contains ship circuit inner proof inputs
+     *
+     * @param board - the board configuration object
+     * @param ship - the ship claimed to be on the board
+     * @param board_t - the board targets, a u128 serialized in LE by 4 u32s
+     * @param ship_t - the claimed ship targets (x, y, z)
+     * @return - inner proof witness
+     */
+    pub fn partial_witness_inner<const L: usize>(
+        board: Board,
+        ship: Ship<L>,
+        board_t: [Target; 4],
+        ship_t: (Target, Target, BoolTarget),
+    ) -> Result<PartialWitness<F>> {
+        // marshall board into canonical form
+        let board_canonical = board.canonical();
+
+        // witness board state
+        let mut pw = PartialWitness::new();
+        for i in 0..board_t.len() {
+            pw.set_target(board_t[i], F::from_canonical_u32(board_canonical[i]));
+        }
+
+        // witness claimed ship
+        let (x, y, z) = ship.canonical();
+        pw.set_target(ship_t.0, F::from_canonical_u8(x));
+        pw.set_target(ship_t.1, F::from_canonical_u8(y));
+        pw.set_bool_target(ship_t.2, z);
+
+        // return witnessed input variables
+        Ok(pw)
+    }
+
+    /**
+     * Generate the witness for the contains ship circuit outer proof inputs
+     *
+     * @param inner - the proof tuple from the execution of the inner ContainsShipCircuit proof
+     * @param targets - the targets for the outer proof
+     * @return - inner proof witnessed for outer proof synthesis
+     */
+    pub fn partial_witness_outer(
+        inner: ProofTuple<F, C, D>,
+        targets: RecursiveTargets,
+    ) -> Result<PartialWitness<F>> {
+        // instantiate partial witness
+        let mut pw = PartialWitness::new();
+
+        // input inner proof to partial witness
+        pw.set_proof_with_pis_target(&targets.proof, &inner.0);
+        pw.set_verifier_data_target(&targets.verifier, &inner.1);
+
+        // return recursive partial witness
+        Ok(pw)
+    }
+
+    /**
+     * Layout the circuit for proving that a committed board contains a ship of length L at a given head coordinate
+     * @notice the claim must cover a whole ship: the cells just past each end are empty or off the board
+     *
+     * @param config - circuit config
+     * @param board - verifier trusting the board circuit whose proof commits to the board
+     * @return - circuit data and board/ ship/ board proof targets
+     */
+    pub fn build<const L: usize>(
+        config: &CircuitConfig,
+        board: &BoardVerifier,
+    ) -> Result<ContainsShipCircuit> {
+        // define circuit builder
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // input targets
+        let board_t: [Target; 4] = builder.add_virtual_targets(4).try_into().unwrap();
+        let ship_t = (
+            builder.add_virtual_target(),
+            builder.add_virtual_target(),
+            builder.add_virtual_bool_target_safe(),
+        );
+
+        // board validity proof targets, with the verifier data fixed to the trusted board circuit
+        let board_pt = builder.add_virtual_proof_with_pis(&board.common);
+        let board_data = builder.constant_verifier_data(&board.verifier_only);
+        let board_proof_t = RecursiveTargets {
+            proof: board_pt.clone(),
+            verifier: board_data.clone(),
+        };
+
+        // verify the board validity proof
+        builder.verify_proof::<C>(&board_pt, &board_data, &board.common);
+
+        // compute coordinates occupied by the claimed ship
+        // @notice: range checks placement
+        let coordinates = ship_to_coordinates::<L>(ship_t, &mut builder)?;
+
+        // check that every coordinate of the claimed ship is occupied on the board
+        let bits = decompose_board(board_t, &mut builder)?;
+        let one_t = builder.constant(F::ONE);
+        for coordinate in coordinates {
+            let occupied = builder.random_access(coordinate, bits.clone());
+            builder.connect(occupied, one_t);
+        }
+
+        // check the claim is not part of a longer run of occupied cells
+        assert_ship_ends_empty::<L>(ship_t, bits, &mut builder)?;
+
+        // export circuit kind tag
+        CircuitKind::ContainsShip.register(&mut builder);
+
+        // export claimed ship publicly
+        let length_t = builder.constant(F::from_canonical_usize(L));
+        builder.register_public_input(ship_t.0);
+        builder.register_public_input(ship_t.1);
+        builder.register_public_input(ship_t.2.target);
+        builder.register_public_input(length_t);
+
+        // bind witnessed board to the commitment of the board validity proof
+        let board_hash_t = hash_board(board_t, &mut builder)?;
+        let commitment_t = &board_pt.public_inputs[1..5];
+        for i in 0..4 {
+            builder.connect(board_hash_t.elements[i], commitment_t[i]);
+        }
+
+        // export the commitment of the board validity proof publicly
+        builder.register_public_inputs(commitment_t);
+
+        // return circuit data and input targets
+        let data = builder.build::<C>();
+        Ok(Self {
+            data,
+            board_t,
+            ship_t,
+            board_proof_t,
+        })
+    }
+
+    /**
+     * Given a board configuration, prove the committed board contains the claimed ship
+     *
+     * @param board - board configuration
+     * @param ship - ship claimed to be placed on the board
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_inner<const L: usize>(board: Board, ship: Ship<L>) -> Result<ProofTuple<F, C, D>> {
+        // prove board validity
+        let board_circuit = BoardCircuit::build(&BoardCircuit::config_inner()?)?;
+        let board_proof = board_circuit.prove_cached(board.clone())?;
+        let verifier = BoardVerifier::from_circuit(&board_circuit);
+
+        // prove the claim against the board proof
+        ContainsShipCircuit::prove_with_board_proof(board, ship, board_proof, &verifier)
+    }
+
+    /**
+     * Given a board configuration and a proof of its validity, prove the committed board contains the claimed ship
+     *
+     * @param board - board configuration committed to by the board proof
+     * @param ship - ship claimed to be placed on the board
+     * @param board_proof - board validity proof committing to the board
+     * @param verifier - verifier trusting the board circuit
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_with_board_proof<const L: usize>(
+        board: Board,
+        ship: Ship<L>,
+        board_proof: ProofTuple<F, C, D>,
+        verifier: &BoardVerifier,
+    ) -> Result<ProofTuple<F, C, D>> {
+        // reject board proofs that do not come from the trusted board circuit
+        verifier.verify(board_proof.clone())?;
+
+        // generate circuit config
+        let config = ContainsShipCircuit::config_inner()?;
+
+        // build inner proof circuit
+        let circuit = ContainsShipCircuit::build::<L>(&config, verifier)?;
+
+        // witness board, claimed ship and board proof (verifier data is a circuit constant)
+        let mut pw = ContainsShipCircuit::partial_witness_inner(
+            board,
+            ship,
+            circuit.board_t,
+            circuit.ship_t,
+        )?;
+        pw.set_proof_with_pis_target(&circuit.board_proof_t.proof, &board_proof.0);
+
+        // generate proof
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            pw,
+            &mut timing,
+        )?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        circuit.data.verify(proof.clone())?;
+
+        // PROVE //
+        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+    }
+
+    /**
+     * Recursive outer proof that obfuscates information of inner proof
+     *
+     * @param inner - the proof tuple from the execution of the inner ContainsShipCircuit proof
+     * @return - outer proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_outer(inner: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
+        // generate circuit config
+        let config = ContainsShipCircuit::config_outer()?;
+
+        // define targets
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let pt = builder.add_virtual_proof_with_pis(&inner.2);
+        let inner_data = builder.add_virtual_verifier_data(inner.2.config.fri_config.cap_height);
+        let outer_targets = RecursiveTargets {
+            proof: pt.clone(),
+            verifier: inner_data.clone(),
+        };
+
+        // synthesize outer proof
+        builder.verify_proof::<C>(&pt, &inner_data, &inner.2);
+
+        // pipe claimed ship and commitment to outer proof public inputs
+        builder.register_public_inputs(&pt.public_inputs);
+
+        // construct circuit data
+        let data = builder.build::<C>();
+
+        // compute partial witness
+        let pw = ContainsShipCircuit::partial_witness_outer(inner, outer_targets)?;
+
+        // prove outer proof provides valid shielding of a contains ship circuit
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&data.prover_only, &data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the outer proof's integrity
+        data.verify(proof.clone())?;
+
+        // return outer proof artifacts
+        Ok((proof, data.verifier_only, data.common))
+    }
+
+    /**
+     * Decode the output of a contains ship proof
+     *
+     * @param proof - proof from contains ship circuit
+     * @return - formatted outputs from contains ship circuit
+     */
+    pub fn decode_public(
+        proof: ProofWithPublicInputs<F, C, D>,
+    ) -> Result<ContainsShipCircuitOutputs> {
+        // ensure proof matches the contains ship circuit layout
+        check_public_inputs(&proof, CONTAINS_SHIP_PUBLIC_INPUTS)?;
+
+        let public_inputs = proof.public_inputs;
+        let ship = (
+            public_inputs[1].to_canonical_u64() as u8,
+            public_inputs[2].to_canonical_u64() as u8,
+            public_inputs[3].to_canonical_u64() != 0,
+        );
+        let length = public_inputs[4].to_canonical_u64() as u8;
+        let commitment: [u64; 4] = public_inputs[5..9]
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap();
+        Ok(ContainsShipCircuitOutputs {
+            ship,
+            length,
+            commitment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Carrier: 3, 4, false
+    // Battleship: 9, 6, true
+    // Cruiser: 0, 0, false
+    // Submarine: 0, 6, false
+    // Destroyer: 6, 1, true
+
+    #[test]
+    fn test_contains_ship() {
+        // define inputs
+        let board = Board::standard_host();
+        let ship = Ship::<5>::new(3, 4, false);
+
+        // prove board contains the carrier
+        let inner = ContainsShipCircuit::prove_inner(board.clone(), ship).unwrap();
+        println!("Inner proof successful");
+
+        // verify integrity of public exports
+        let output = ContainsShipCircuit::decode_public(inner.0).unwrap();
+        assert_eq!(output.ship, (3, 4, false));
+        assert_eq!(output.length, 5);
+        assert_eq!(output.commitment, board.hash());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_contains_ship_shifted() {
+        // define inputs
+        let board = Board::standard_host();
+        // carrier shifted one coordinate right (tail lands on empty (8, 4))
+        let ship = Ship::<5>::new(4, 4, false);
+
+        // copy constraint on occupied coordinate fails
+        _ = ContainsShipCircuit::prove_inner(board, ship);
+    }
+
+    #[test]
+    fn test_contains_ship_at_edges() {
+        // cruiser starts on the first column and battleship ends on the last row: the off-board ends are skipped
+        let board = Board::standard_host();
+        let cruiser = ContainsShipCircuit::prove_inner(board.clone(), Ship::<3>::new(0, 0, false));
+        let output = ContainsShipCircuit::decode_public(cruiser.unwrap().0).unwrap();
+        assert_eq!(output.ship, (0, 0, false));
+        let battleship = ContainsShipCircuit::prove_inner(board.clone(), Ship::<4>::new(9, 6, true));
+        let output = ContainsShipCircuit::decode_public(battleship.unwrap().0).unwrap();
+        assert_eq!(output.commitment, board.hash());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_contains_partial_ship() {
+        // length 4 claim over the first four cells of the carrier: (7, 4) past the tail is occupied
+        let board = Board::standard_host();
+        let ship = Ship::<4>::new(3, 4, false);
+        ContainsShipCircuit::prove_inner(board, ship).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_contains_partial_ship_tail() {
+        // length 3 claim over the last three cells of the carrier: (4, 4) before the head is occupied
+        let board = Board::standard_host();
+        let ship = Ship::<3>::new(5, 4, false);
+        ContainsShipCircuit::prove_inner(board, ship).unwrap();
+    }
+}
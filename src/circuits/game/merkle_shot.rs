@@ -0,0 +1,221 @@
+use {
+    super::{
+        super::{check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, C, D, F},
+        shot::ShotResult,
+    },
+    crate::{
+        gadgets::shot::{check_hit_merkle, serialize_shot_sized},
+        utils::merkle_board::MerkleBoard,
+    },
+    anyhow::{ensure, Result},
+    log::Level,
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        hash::{hash_types::HashOutTarget, merkle_proofs::MerkleProofTarget},
+        iop::{
+            target::{BoolTarget, Target},
+            witness::{PartialWitness, WitnessWrite},
+        },
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitConfig, CircuitData},
+            proof::ProofWithPublicInputs,
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// number of public inputs exported by the Merkle shot circuit (kind tag, shot, hit, board root)
+pub const MERKLE_SHOT_PUBLIC_INPUTS: usize = 7;
+
+pub struct MerkleShotCircuitOutputs {
+    pub shot: u64, // serialized shot coordinate (side * y + x)
+    pub hit: ShotResult,
+    pub root: [u64; 4],
+}
+
+pub struct MerkleShotCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub side: usize,
+    pub root_t: HashOutTarget,
+    pub shot_t: [Target; 2],
+    pub hit_t: BoolTarget,
+    pub proof_t: MerkleProofTarget,
+}
+
+// Shot circuit for huge boards: hit/ miss is proven with a Merkle opening of a Merkle-committed board
+// @dev unlike ShotCircuit, the board is never decomposed so the circuit grows with log2(cells) instead of cells
+impl MerkleShotCircuit {
+    /**
+     * Layout the circuit for proving hit/ miss of a shot against a Merkle-committed board
+     *
+     * @param config - circuit config
+     * @param side - width/ height of the board
+     * @return - circuit data and root/ shot/ opening targets
+     */
+    pub fn build(config: &CircuitConfig, side: usize) -> Result<MerkleShotCircuit> {
+        // define circuit builder
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // input targets
+        let root_t = builder.add_virtual_hash();
+        let shot_t: [Target; 2] = builder.add_virtual_targets(2).try_into().unwrap();
+
+        // serialize shot coordinate
+        let serialized_t = serialize_shot_sized(shot_t[0], shot_t[1], side, &mut builder)?;
+
+        // open the board at the shot coordinate
+        let depth = MerkleBoard::depth_for(side);
+        let (hit_t, proof_t) = check_hit_merkle(root_t, serialized_t, depth, &mut builder)?;
+
+        // export circuit kind tag
+        CircuitKind::MerkleShot.register(&mut builder);
+
+        // export serialized shot value and hit/ miss boolean
+        builder.register_public_input(serialized_t);
+        builder.register_public_input(hit_t.target);
+
+        // export board root publicly
+        builder.register_public_inputs(&root_t.elements);
+
+        // return circuit data and input targets
+        let data = builder.build::<C>();
+        Ok(Self {
+            data,
+            side,
+            root_t,
+            shot_t,
+            hit_t,
+            proof_t,
+        })
+    }
+
+    /**
+     * Generate a proof of hit/ miss for a shot against a Merkle-committed board
+     *
+     * @param board - Merkle-committed board
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove(board: &MerkleBoard, shot: [usize; 2]) -> Result<ProofTuple<F, C, D>> {
+        ensure!(shot[0] < board.side && shot[1] < board.side, "shot is off the board");
+
+        // build circuit for the board size
+        let config = CircuitConfigBuilder::new().build();
+        let circuit = MerkleShotCircuit::build(&config, board.side)?;
+
+        // witness root, shot and opening
+        let mut pw = PartialWitness::new();
+        let root = board.root().map(F::from_canonical_u64);
+        pw.set_target_arr(circuit.root_t.elements, root);
+        pw.set_target(circuit.shot_t[0], F::from_canonical_usize(shot[0]));
+        pw.set_target(circuit.shot_t[1], F::from_canonical_usize(shot[1]));
+        pw.set_bool_target(circuit.hit_t, board.occupied(shot));
+        let opening = board.open(shot);
+        for (sibling_t, sibling) in circuit.proof_t.siblings.iter().zip(opening.siblings) {
+            pw.set_hash_target(*sibling_t, sibling);
+        }
+
+        // generate proof
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            pw,
+            &mut timing,
+        )?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        circuit.data.verify(proof.clone())?;
+
+        // PROVE //
+        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+    }
+
+    /**
+     * Decode the output of a Merkle shot proof
+     *
+     * @param proof - proof from Merkle shot circuit
+     * @return - formatted outputs from Merkle shot circuit
+     */
+    pub fn decode_public(
+        proof: ProofWithPublicInputs<F, C, D>,
+    ) -> Result<MerkleShotCircuitOutputs> {
+        // ensure proof matches the Merkle shot circuit layout
+        check_public_inputs(&proof, MERKLE_SHOT_PUBLIC_INPUTS)?;
+
+        let public_inputs = proof.public_inputs;
+        let shot = public_inputs[1].to_canonical_u64();
+        let hit = ShotResult::from(public_inputs[2].to_canonical_u64() != 0);
+        let root: [u64; 4] = public_inputs[3..7]
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap();
+        Ok(MerkleShotCircuitOutputs { shot, hit, root })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * Build a large board with a single ship along the diagonal
+     *
+     * @param side - width/ height of the board
+     * @return - Merkle-committed board
+     */
+    fn diagonal_board(side: usize) -> MerkleBoard {
+        let mut bits = vec![false; side * side];
+        for i in 0..side {
+            bits[i * side + i] = true;
+        }
+        MerkleBoard::new(side, &bits).unwrap()
+    }
+
+    #[test]
+    fn test_merkle_shot_hit() {
+        // 64x64 board: 4096 cells would be far too costly to decompose
+        let board = diagonal_board(64);
+        let proof = MerkleShotCircuit::prove(&board, [41, 41]).unwrap();
+
+        // hit is proven against the board root
+        let output = MerkleShotCircuit::decode_public(proof.0).unwrap();
+        assert_eq!(output.hit, ShotResult::Hit);
+        assert_eq!(output.shot, 41 * 64 + 41);
+        assert_eq!(output.root, board.root());
+    }
+
+    #[test]
+    fn test_merkle_shot_miss() {
+        // miss on an empty cell of the same board
+        let board = diagonal_board(64);
+        let proof = MerkleShotCircuit::prove(&board, [3, 40]).unwrap();
+        let output = MerkleShotCircuit::decode_public(proof.0).unwrap();
+        assert_eq!(output.hit, ShotResult::Miss);
+        assert_eq!(output.root, board.root());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merkle_shot_forged_hit() {
+        // claim a hit on an empty cell: the opening does not hash to the root
+        let board = diagonal_board(64);
+        let circuit =
+            MerkleShotCircuit::build(&CircuitConfig::standard_recursion_config(), 64).unwrap();
+        let mut pw = PartialWitness::new();
+        pw.set_target_arr(circuit.root_t.elements, board.root().map(F::from_canonical_u64));
+        pw.set_target(circuit.shot_t[0], F::from_canonical_u8(3));
+        pw.set_target(circuit.shot_t[1], F::from_canonical_u8(40));
+        pw.set_bool_target(circuit.hit_t, true);
+        let opening = board.open([3, 40]);
+        for (sibling_t, sibling) in circuit.proof_t.siblings.iter().zip(opening.siblings) {
+            pw.set_hash_target(*sibling_t, sibling);
+        }
+        circuit.data.prove(pw).unwrap();
+    }
+}
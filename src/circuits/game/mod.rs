@@ -1,2 +1,9 @@
 pub mod board;
-pub mod shot;
\ No newline at end of file
+pub mod cached_shot;
+pub mod consistency;
+pub mod contains_ship;
+pub mod merkle_shot;
+pub mod remaining_ships;
+pub mod shot;
+pub mod sunk_ships;
+pub mod tutorial_board;
\ No newline at end of file
@@ -0,0 +1,203 @@
+use {
+    super::{
+        super::{check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, C, D, F},
+        sunk_ships::SunkShipsCircuit,
+    },
+    crate::{
+        gadgets::board::{
+            count_sunk_ships, decompose_board, hash_board, witness_standard_fleet, FleetShipTarget,
+            STANDARD_FLEET,
+        },
+        utils::board::Board,
+    },
+    anyhow::Result,
+    log::Level,
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        iop::{
+            target::Target,
+            witness::{PartialWitness, WitnessWrite},
+        },
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitConfig, CircuitData},
+            proof::ProofWithPublicInputs,
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// number of public inputs exported by the remaining ships circuit (kind tag, hits, threshold, result, commitment)
+pub const REMAINING_SHIPS_PUBLIC_INPUTS: usize = 11;
+
+pub struct RemainingShipsOutputs {
+    pub hits: [u32; 4], // shot coordinates as a u128 serialized in LE by 4 u32s
+    pub threshold: u8,  // minimum number of unsunk ships claimed
+    pub at_least: bool, // true if at least threshold ships are still afloat
+    pub commitment: [u64; 4],
+}
+
+pub struct RemainingShipsCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub board_t: [Target; 4],
+    pub hits_t: [Target; 4],
+    pub threshold_t: Target,
+    pub ships: Vec<FleetShipTarget>, // placements of the standard fleet, in fleet order
+}
+
+// Surrender threshold check: proves whether at least N ships of a committed board survive a public set of shots
+// @dev unlike SunkShipsCircuit the count itself stays private; only the comparison against N is exported
+impl RemainingShipsCircuit {
+    /**
+     * Generate a circuit config for the remaining ships circuit
+     *
+     * @return - circuit config
+     */
+    pub fn config_inner() -> Result<CircuitConfig> {
+        Ok(CircuitConfigBuilder::new().with_random_access().build())
+    }
+
+    /**
+     * Layout the circuit comparing the number of unsunk ships of a committed board against a public threshold
+     *
+     * @param config - circuit config
+     * @return - circuit data and board/ hit/ threshold targets
+     */
+    pub fn build(config: &CircuitConfig) -> Result<RemainingShipsCircuit> {
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // TARGETS //
+        let board_t = builder.add_virtual_target_arr::<4>();
+        let hits_t = builder.add_virtual_target_arr::<4>();
+        let threshold_t = builder.add_virtual_target();
+
+        // SYNTHESIZE //
+        // count sunk ships (constrains the board to the standard fleet shape)
+        let board_bits = decompose_board(board_t, &mut builder)?;
+        let hit_bits = decompose_board(hits_t, &mut builder)?;
+        let (sunk_t, ships) =
+            count_sunk_ships(board_bits, hit_bits, &STANDARD_FLEET, &mut builder)?;
+        let fleet_t = builder.constant(F::from_canonical_usize(STANDARD_FLEET.len()));
+        let remaining_t = builder.sub(fleet_t, sunk_t);
+
+        // at_least = sum over remaining = k, threshold = j with j <= k; at most one pair matches so the sum is boolean
+        // @dev a threshold above the fleet size matches no pair and is reported false
+        let mut at_least_t = builder.zero();
+        for k in 0..=STANDARD_FLEET.len() {
+            let k_t = builder.constant(F::from_canonical_usize(k));
+            let is_remaining = builder.is_equal(remaining_t, k_t);
+            for j in 0..=k {
+                let j_t = builder.constant(F::from_canonical_usize(j));
+                let is_threshold = builder.is_equal(threshold_t, j_t);
+                let matched = builder.and(is_remaining, is_threshold);
+                at_least_t = builder.add(at_least_t, matched.target);
+            }
+        }
+
+        // bind the board to its commitment
+        let commitment = hash_board(board_t, &mut builder)?;
+
+        // PUBLIC INPUTS //
+        //  - [0] = circuit kind tag (remaining ships)
+        //  - [1..5] = hits
+        //  - [5] = threshold
+        //  - [6] = at least threshold ships remaining
+        //  - [7..11] = board commitment
+        CircuitKind::RemainingShips.register(&mut builder);
+        builder.register_public_inputs(&hits_t);
+        builder.register_public_input(threshold_t);
+        builder.register_public_input(at_least_t);
+        builder.register_public_inputs(&commitment.elements);
+
+        let data = builder.build::<C>();
+        Ok(Self {
+            data,
+            board_t,
+            hits_t,
+            threshold_t,
+            ships,
+        })
+    }
+
+    /**
+     * Prove whether at least a threshold of ships survive a set of shots
+     *
+     * @param board - board configuration
+     * @param shots - shot coordinates (x, y) made against the board
+     * @param threshold - minimum number of unsunk ships to check for
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove(board: Board, shots: &[[u8; 2]], threshold: u8) -> Result<ProofTuple<F, C, D>> {
+        let circuit = RemainingShipsCircuit::build(&RemainingShipsCircuit::config_inner()?)?;
+
+        // WITNESS //
+        let hits = Board::pack_bits(&SunkShipsCircuit::hit_bits(shots));
+        let mut pw = PartialWitness::new();
+        pw.set_target_arr(circuit.board_t, board.canonical().map(F::from_canonical_u32));
+        pw.set_target_arr(circuit.hits_t, hits.map(F::from_canonical_u32));
+        pw.set_target(circuit.threshold_t, F::from_canonical_u8(threshold));
+        witness_standard_fleet(&mut pw, &circuit.ships, &board);
+
+        // PROVE //
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&circuit.data.prover_only, &circuit.data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        circuit.data.verify(proof.clone())?;
+        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+    }
+
+    /**
+     * Decode the output of a remaining ships proof
+     *
+     * @param proof - proof from remaining ships circuit
+     * @return - formatted outputs from remaining ships circuit
+     */
+    pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<RemainingShipsOutputs> {
+        // ensure proof matches the remaining ships circuit layout
+        check_public_inputs(&proof, REMAINING_SHIPS_PUBLIC_INPUTS)?;
+
+        let public_inputs = proof.public_inputs;
+        let hits: [u32; 4] = public_inputs[1..5]
+            .iter()
+            .map(|x| x.to_canonical_u64() as u32)
+            .collect::<Vec<u32>>()
+            .try_into()
+            .unwrap();
+        let commitment: [u64; 4] = public_inputs[7..11]
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap();
+        Ok(RemainingShipsOutputs {
+            hits,
+            threshold: public_inputs[5].to_canonical_u64() as u8,
+            at_least: public_inputs[6].to_canonical_u64() != 0,
+            commitment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_ships_threshold() {
+        // sink the carrier (3, 4) horizontal and the destroyer (6, 1) vertical: 3 ships remain
+        let board = Board::standard_host();
+        let shots = [[3u8, 4], [4, 4], [5, 4], [6, 4], [7, 4], [6, 1], [6, 2], [0, 0]];
+
+        let proof = RemainingShipsCircuit::prove(board.clone(), &shots, 3).unwrap();
+        let outputs = RemainingShipsCircuit::decode_public(proof.0).unwrap();
+        assert!(outputs.at_least);
+        assert_eq!(outputs.threshold, 3);
+        assert_eq!(outputs.commitment, board.hash());
+
+        let proof = RemainingShipsCircuit::prove(board, &shots, 4).unwrap();
+        assert!(!RemainingShipsCircuit::decode_public(proof.0).unwrap().at_least);
+    }
+}
@@ -1,18 +1,26 @@
 use {
-    super::super::{ProofTuple, RecursiveTargets, C, D, F},
+    super::super::{
+        check_public_inputs, recursion::{fold_with_timing, prove_with_retries}, shape::CircuitShape,
+        CircuitConfigBuilder, CircuitError, CircuitKind, ProofTiming, ProofTuple, RecursiveTargets,
+        C, D, F,
+    },
     crate::{
         gadgets::{
-            board::hash_board,
+            board::{decompose_board, hash_board},
             shot::{check_hit, serialize_shot},
         },
-        utils::board::Board,
+        utils::{
+            board::Board,
+            commitment::to_hex,
+            ecdsa::{shot_message, sign, verify, PublicKey, SecretKey, Signature},
+        },
     },
     anyhow::Result,
     log::Level,
     plonky2::{
         field::types::{Field, PrimeField64},
         iop::{
-            target::Target,
+            target::{BoolTarget, Target},
             witness::{PartialWitness, WitnessWrite},
         },
         plonk::{
@@ -23,32 +31,168 @@ use {
         },
         util::timing::TimingTree,
     },
+    std::time::Instant,
 };
 
+// number of public inputs exported by the shot circuit (kind tag, shot, hit, board commitment)
+pub const SHOT_PUBLIC_INPUTS: usize = 7;
+
+// number of public inputs exported by the dual shot circuit (kind tag, turn, shot, hit, host/ guest commitments)
+pub const DUAL_SHOT_PUBLIC_INPUTS: usize = 12;
+
+// Result of a shot against a board, as decoded from the hit/ miss public input of any shot proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShotResult {
+    Hit,
+    Miss,
+}
+
+impl ShotResult {
+    /**
+     * Check whether the shot struck a ship
+     *
+     * @return - true if the shot is a hit
+     */
+    pub fn is_hit(self) -> bool {
+        self == ShotResult::Hit
+    }
+}
+
+impl From<bool> for ShotResult {
+    fn from(hit: bool) -> Self {
+        match hit {
+            true => ShotResult::Hit,
+            false => ShotResult::Miss,
+        }
+    }
+}
+
+impl std::fmt::Display for ShotResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShotResult::Hit => write!(f, "hit"),
+            ShotResult::Miss => write!(f, "miss"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ShotCircuitOutputs {
     pub shot: u8,
-    pub hit: bool,
+    pub hit: ShotResult,
     pub commitment: [u64; 4],
 }
 
+impl std::fmt::Display for ShotCircuitOutputs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "shot: {}, result: {}, commitment: {}",
+            self.shot,
+            self.hit,
+            to_hex(self.commitment)
+        )
+    }
+}
+
+// Board owner's signature over the outputs of a shot proof, trusted off-chain without the proof itself
+pub struct ShotAttestation {
+    pub outputs: ShotCircuitOutputs,
+    pub signature: Signature,
+}
+
+impl ShotAttestation {
+    /**
+     * Sign the result of a shot as the owner of the board being shot
+     *
+     * @param outputs - decoded outputs of the shot proof (commitment, shot, hit)
+     * @param secret_key - secp256k1 secret key of the board owner
+     * @return - attestation of the shot result
+     */
+    pub fn sign(outputs: ShotCircuitOutputs, secret_key: SecretKey) -> ShotAttestation {
+        let msg = shot_message(outputs.commitment, outputs.shot, outputs.hit.is_hit());
+        ShotAttestation {
+            outputs,
+            signature: sign(msg, secret_key),
+        }
+    }
+
+    /**
+     * Check the attestation is signed by the board owner
+     *
+     * @param public_key - secp256k1 public key of the board owner
+     * @return - true if the signature covers the attested commitment, shot and hit
+     */
+    pub fn verify(&self, public_key: PublicKey) -> bool {
+        let msg = shot_message(
+            self.outputs.commitment,
+            self.outputs.shot,
+            self.outputs.hit.is_hit(),
+        );
+        verify(msg, self.signature, public_key)
+    }
+
+    /**
+     * Check the attestation is signed by the board owner and attests the outputs of a shot proof
+     *
+     * @param proof - shot proof the attestation is claimed to summarize
+     * @param public_key - secp256k1 public key of the board owner
+     * @return - true if the signature is valid and the attested outputs equal the proof's outputs
+     */
+    pub fn verify_against(
+        &self,
+        proof: ProofWithPublicInputs<F, C, D>,
+        public_key: PublicKey,
+    ) -> Result<bool> {
+        let outputs = ShotCircuit::decode_public(proof)?;
+        Ok(outputs == self.outputs && self.verify(public_key))
+    }
+}
+
 pub struct ShotCircuit {
     pub data: CircuitData<F, C, D>,
     pub board_t: [Target; 4],
     pub shot_t: [Target; 2],
+    pub verify_after_prove: bool, // natively verify each proof after generating it (default true)
+}
+
+// Public outputs of a dual shot proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DualShotOutputs {
+    pub turn: bool, // false = shot against the host board, true = shot against the guest board
+    pub shot: u8,
+    pub hit: ShotResult,
+    pub host: [u64; 4],  // host board commitment
+    pub guest: [u64; 4], // guest board commitment
+}
+
+// Shot circuit over both boards of a channel, for referees checking the two players' states at once
+pub struct DualShotCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub host_t: [Target; 4],
+    pub guest_t: [Target; 4],
+    pub shot_t: [Target; 2],
+    pub turn_t: BoolTarget,
 }
 
 impl ShotCircuit {
+    /**
+     * Return the number of public inputs exported by proofs of this circuit
+     * @notice SHOT_PUBLIC_INPUTS: kind tag, serialized shot, hit and board commitment
+     *
+     * @return - length of the public input vector to allocate when verifying
+     */
+    pub fn num_public_inputs(&self) -> usize {
+        self.data.common.num_public_inputs
+    }
+
     /**
      * Generate a circuit config capable of handling 128 bit random access gates
      *
      * @return - circuit config
      */
     pub fn config_inner() -> Result<CircuitConfig> {
-        let mut config = CircuitConfig::standard_recursion_config();
-        // set wires for random access gate
-        config.num_wires = 137;
-        config.num_routed_wires = 130;
-        Ok(config)
+        Ok(CircuitConfigBuilder::new().with_random_access().build())
     }
 
     /**
@@ -57,10 +201,7 @@ impl ShotCircuit {
      * @return - circuit config
      */
     pub fn config_outer() -> Result<CircuitConfig> {
-        let mut config = CircuitConfig::standard_recursion_config();
-        // toggle zero knowledge blinding
-        config.zero_knowledge = true;
-        Ok(config)
+        Ok(CircuitConfigBuilder::new().with_zk(true).build())
     }
 
     /**
@@ -78,15 +219,48 @@ impl ShotCircuit {
         shot_t: [Target; 2],
         board_t: [Target; 4],
     ) -> Result<PartialWitness<F>> {
-        // marshall board into canonical form
-        let board_canonical = board.canonical();
+        ShotCircuit::partial_witness_inner_bits(shot, board.bits(), shot_t, board_t)
+    }
+
+    /**
+     * Generate the witness for the shot circuit inner proof inputs from a raw board bitmap
+     *
+     * @param shot - the shot coordinate (x, y)
+     * @param bits - the board as 100 LE bits (index = 10y + x)
+     * @param shot_t - the shot coordinate targets (x, y)
+     * @param board_t - the board targets, a u128 serialized in LE by 4 u32s
+     * @return - inner proof witness
+     */
+    pub fn partial_witness_inner_bits(
+        shot: [u8; 2],
+        bits: [bool; 100],
+        shot_t: [Target; 2],
+        board_t: [Target; 4],
+    ) -> Result<PartialWitness<F>> {
+        // marshall board bits into canonical form
+        let preimage = Board::pack_bits(&bits).map(F::from_canonical_u32);
+        ShotCircuit::partial_witness_inner_preimage(shot, preimage, shot_t, board_t)
+    }
 
+    /**
+     * Generate the witness for the shot circuit inner proof inputs from the board commitment preimage
+     * @dev a non-canonical preimage (bits set past the 100th cell) fails the hash_board constraint when proving
+     *
+     * @param shot - the shot coordinate (x, y)
+     * @param preimage - the board as the poseidon preimage of its commitment (4 LE u32 limbs)
+     * @param shot_t - the shot coordinate targets (x, y)
+     * @param board_t - the board targets, a u128 serialized in LE by 4 u32s
+     * @return - inner proof witness
+     */
+    pub fn partial_witness_inner_preimage(
+        shot: [u8; 2],
+        preimage: [F; 4],
+        shot_t: [Target; 2],
+        board_t: [Target; 4],
+    ) -> Result<PartialWitness<F>> {
         // witness board state
         let mut pw = PartialWitness::new();
-        pw.set_target(board_t[0], F::from_canonical_u32(board_canonical[0]));
-        pw.set_target(board_t[1], F::from_canonical_u32(board_canonical[1]));
-        pw.set_target(board_t[2], F::from_canonical_u32(board_canonical[2]));
-        pw.set_target(board_t[3], F::from_canonical_u32(board_canonical[3]));
+        pw.set_target_arr(board_t, preimage);
 
         // witness shot coordinate
         pw.set_target(shot_t[0], F::from_canonical_u8(shot[0]));
@@ -125,6 +299,84 @@ impl ShotCircuit {
      * @return - circuit data and board/ shot targets
      */
     pub fn build(config: &CircuitConfig) -> Result<ShotCircuit> {
+        ShotCircuit::synthesize(config, None)
+    }
+
+    /**
+     * Layout the shot circuit padded to a common circuit shape
+     * @notice padding public inputs are zeros appended after the commitment
+     *
+     * @param config - circuit config
+     * @param shape - common shape to pad the circuit to
+     * @return - circuit data and board/ shot targets
+     */
+    pub fn build_padded(config: &CircuitConfig, shape: &CircuitShape) -> Result<ShotCircuit> {
+        ShotCircuit::synthesize(config, Some(shape))
+    }
+
+    /**
+     * Layout the circuit proving hit/ miss of a shot against one of two committed boards, selected by a turn bit
+     * @notice mirrors the channel: turn = false targets the host board, turn = true targets the guest board
+     * @dev both boards are decomposed and multiplexed bit by bit, so the hit lookup reads only the targeted board
+     *
+     * @param config - circuit config (must support 128 bit random access gates)
+     * @return - circuit data and host/ guest/ shot/ turn targets
+     */
+    pub fn build_dual(config: &CircuitConfig) -> Result<DualShotCircuit> {
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // TARGETS //
+        let host_t = builder.add_virtual_target_arr::<4>();
+        let guest_t = builder.add_virtual_target_arr::<4>();
+        let shot_t = builder.add_virtual_target_arr::<2>();
+        let turn_t = builder.add_virtual_bool_target_safe();
+
+        // SYNTHESIZE //
+        let serialized_t = serialize_shot(shot_t[0], shot_t[1], &mut builder)?;
+        // select the targeted board bit by bit and look up the shot in it
+        let host_bits = decompose_board(host_t, &mut builder)?;
+        let guest_bits = decompose_board(guest_t, &mut builder)?;
+        let targeted = host_bits
+            .iter()
+            .zip(guest_bits.iter())
+            .map(|(host, guest)| builder.select(turn_t, *guest, *host))
+            .collect::<Vec<Target>>();
+        let hit = builder.random_access(serialized_t, targeted);
+        // commit to both boards regardless of which one was shot
+        let host_hash_t = hash_board(host_t, &mut builder)?;
+        let guest_hash_t = hash_board(guest_t, &mut builder)?;
+
+        // PUBLIC INPUTS //
+        //  - [0] = circuit kind tag (dual shot)
+        //  - [1] = turn
+        //  - [2] = serialized shot
+        //  - [3] = hit/ miss
+        //  - [4..8] = host board commitment
+        //  - [8..12] = guest board commitment
+        CircuitKind::DualShot.register(&mut builder);
+        builder.register_public_input(turn_t.target);
+        builder.register_public_input(serialized_t);
+        builder.register_public_input(hit);
+        builder.register_public_inputs(&host_hash_t.elements);
+        builder.register_public_inputs(&guest_hash_t.elements);
+
+        Ok(DualShotCircuit {
+            data: builder.build::<C>(),
+            host_t,
+            guest_t,
+            shot_t,
+            turn_t,
+        })
+    }
+
+    /**
+     * Layout the shot circuit
+     *
+     * @param config - circuit config
+     * @param shape - optional common shape to pad the circuit to
+     * @return - circuit data and board/ shot targets
+     */
+    fn synthesize(config: &CircuitConfig, shape: Option<&CircuitShape>) -> Result<ShotCircuit> {
         // define circuit builder
         let mut builder = CircuitBuilder::<F, D>::new(config.clone());
 
@@ -135,6 +387,9 @@ impl ShotCircuit {
         // serialize shot coordinate
         let serialized_t = serialize_shot(shot_t[0], shot_t[1], &mut builder).unwrap();
 
+        // export circuit kind tag
+        CircuitKind::Shot.register(&mut builder);
+
         // export serialized shot value
         builder.register_public_input(serialized_t);
 
@@ -151,12 +406,18 @@ impl ShotCircuit {
         // @dev todo: making commitment blinding as well (alternatively hide behind ecdsa signature)
         builder.register_public_inputs(&board_hash_t.elements);
 
+        // optionally pad to a common circuit shape
+        if let Some(shape) = shape {
+            shape.pad(&mut builder)?;
+        }
+
         // return circuit data and input targets
         let data = builder.build::<C>();
         Ok(Self {
             data,
             board_t,
             shot_t,
+            verify_after_prove: true,
         })
     }
 
@@ -167,30 +428,199 @@ impl ShotCircuit {
      * @return - proof tuple of everything needed to verify the proof natively or recursively
      */
     pub fn prove_inner(board: Board, shot: [u8; 2]) -> Result<ProofTuple<F, C, D>> {
+        ShotCircuit::prove_inner_bits(board.bits(), shot)
+    }
+
+    /**
+     * Given a raw board bitmap, generate a proof of hit/ miss for a shot against the committed board
+     *
+     * @param bits - the board as 100 LE bits (index = 10y + x)
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_inner_bits(bits: [bool; 100], shot: [u8; 2]) -> Result<ProofTuple<F, C, D>> {
+        Ok(ShotCircuit::prove_inner_bits_timed(bits, shot)?.0)
+    }
+
+    /**
+     * Given the board commitment preimage, generate a proof of hit/ miss for a shot against the committed board
+     * @notice for integrations holding the preimage rather than a Board; no ship placements are needed
+     *
+     * @param preimage - the board as the poseidon preimage of its commitment (4 LE u32 limbs)
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_inner_preimage(preimage: [F; 4], shot: [u8; 2]) -> Result<ProofTuple<F, C, D>> {
+        let circuit = ShotCircuit::build(&ShotCircuit::config_inner()?)?;
+        circuit.prove_cached_preimage(preimage, shot)
+    }
+
+    /**
+     * Prove hit/ miss of a shot against a board and return wall clock timings alongside the proof
+     *
+     * @param board - board configuration
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple and build/ prove/ verify timings
+     */
+    pub fn prove_inner_timed(
+        board: Board,
+        shot: [u8; 2],
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
+        ShotCircuit::prove_inner_bits_timed(board.bits(), shot)
+    }
+
+    /**
+     * Prove hit/ miss of a shot against a raw board bitmap, returning timings alongside the proof
+     *
+     * @param bits - the board as 100 LE bits (index = 10y + x)
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple and build/ prove/ verify timings
+     */
+    pub fn prove_inner_bits_timed(
+        bits: [bool; 100],
+        shot: [u8; 2],
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
+        let start = Instant::now();
+
         // generate circuit config
         let config = ShotCircuit::config_inner()?;
 
         // build inner proof circuit
         let circuit = ShotCircuit::build(&config)?;
+        let build = start.elapsed();
+
+        // prove against the freshly built circuit
+        let (proof, mut timings) = circuit.prove_cached_bits_timed(bits, shot)?;
+        timings.build = build;
+        Ok((proof, timings))
+    }
 
+    /**
+     * Prove hit/ miss of a shot against an already built shot circuit
+     * @dev lets callers build the circuit once (see GameCircuits) and prove every shot of a game with it
+     *
+     * @param board - board configuration
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_cached(&self, board: Board, shot: [u8; 2]) -> Result<ProofTuple<F, C, D>> {
+        Ok(self.prove_cached_bits_timed(board.bits(), shot)?.0)
+    }
+
+    /**
+     * Prove hit/ miss of a shot against an already built shot circuit from the board commitment preimage
+     *
+     * @param preimage - the board as the poseidon preimage of its commitment (4 LE u32 limbs)
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_cached_preimage(
+        &self,
+        preimage: [F; 4],
+        shot: [u8; 2],
+    ) -> Result<ProofTuple<F, C, D>> {
+        let pw =
+            ShotCircuit::partial_witness_inner_preimage(shot, preimage, self.shot_t, self.board_t)?;
+        Ok(self.prove_witnessed_timed(pw)?.0)
+    }
+
+    /**
+     * Prove and verify hit/ miss of a shot against an already built shot circuit, recording timings
+     *
+     * @param bits - the board as 100 LE bits (index = 10y + x)
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple and prove/ verify timings (build is left at zero)
+     */
+    fn prove_cached_bits_timed(
+        &self,
+        bits: [bool; 100],
+        shot: [u8; 2],
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
         // witness board and shot
-        let pw = ShotCircuit::partial_witness_inner(shot, board, circuit.shot_t, circuit.board_t)?;
+        let pw = ShotCircuit::partial_witness_inner_bits(shot, bits, self.shot_t, self.board_t)?;
+        self.prove_witnessed_timed(pw)
+    }
 
-        // generate proof
+    /**
+     * Prove and verify a witnessed shot against an already built shot circuit, recording timings
+     *
+     * @param pw - witness of the board and shot targets
+     * @return - proof tuple and prove/ verify timings (build is left at zero)
+     */
+    fn prove_witnessed_timed(
+        &self,
+        pw: PartialWitness<F>,
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
         let mut timing = TimingTree::new("prove", Level::Debug);
-        let proof = prove(
-            &circuit.data.prover_only,
-            &circuit.data.common,
-            pw,
-            &mut timing,
-        )?;
+        let proof = self.prove_witnessed_in(pw, &mut timing)?;
         timing.print();
+        Ok(proof)
+    }
 
-        // verify the proof was generated correctly
-        circuit.data.verify(proof.clone())?;
+    /**
+     * Prove a witnessed shot against an already built shot circuit under a "prove shot" span of a parent tree
+     *
+     * @param pw - witness of the board and shot targets
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - proof tuple and prove/ verify timings (build is left at zero)
+     */
+    fn prove_witnessed_in(
+        &self,
+        pw: PartialWitness<F>,
+        timing: &mut TimingTree,
+    ) -> Result<(ProofTuple<F, C, D>, ProofTiming)> {
+        let mut timings = ProofTiming::default();
+
+        // generate proof
+        let start = Instant::now();
+        timing.push("prove shot", Level::Debug);
+        let proof = prove(&self.data.prover_only, &self.data.common, pw, timing);
+        timing.pop();
+        let proof = proof?;
+        timings.prove = start.elapsed();
+
+        // verify the proof was generated correctly (skipped by trusted batch provers)
+        if self.verify_after_prove {
+            let start = Instant::now();
+            self.data.verify(proof.clone())?;
+            timings.verify = start.elapsed();
+        }
 
         // PROVE //
-        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+        Ok(((proof, self.data.verifier_only.clone(), self.data.common.clone()), timings))
+    }
+
+    /**
+     * Prove hit/ miss of a shot, recording the proof in a parent timing tree instead of printing its own
+     *
+     * @param board - board configuration
+     * @param shot - the shot coordinate (x, y)
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_inner_with_timing(
+        board: Board,
+        shot: [u8; 2],
+        timing: &mut TimingTree,
+    ) -> Result<ProofTuple<F, C, D>> {
+        let circuit = ShotCircuit::build(&ShotCircuit::config_inner()?)?;
+        let pw =
+            ShotCircuit::partial_witness_inner_bits(shot, board.bits(), circuit.shot_t, circuit.board_t)?;
+        Ok(circuit.prove_witnessed_in(pw, timing)?.0)
+    }
+
+    /**
+     * Recursive outer proof retried on recoverable (e.g. zero knowledge blinding) proving failures
+     *
+     * @param inner - the proof tuple from the execution of the inner ShotCircuit proof
+     * @param retries - number of additional attempts after the first failure
+     * @return - outer proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_outer_with_retries(
+        inner: ProofTuple<F, C, D>,
+        retries: usize,
+    ) -> Result<ProofTuple<F, C, D>> {
+        prove_with_retries(retries, || ShotCircuit::prove_outer(inner.clone()))
     }
 
     /**
@@ -200,40 +630,43 @@ impl ShotCircuit {
      * @return - outer proof tuple of everything needed to verify the proof natively or recursively
      */
     pub fn prove_outer(inner: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = ShotCircuit::prove_outer_with_timing(inner, &mut timing)?;
+        timing.print();
+        Ok(proof)
+    }
+
+    /**
+     * Recursive outer proof recorded as a "prove shot outer" span of a parent timing tree
+     *
+     * @param inner - the proof tuple from the execution of the inner ShotCircuit proof
+     * @param timing - parent timing tree the proof is recorded in
+     * @return - outer proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_outer_with_timing(
+        inner: ProofTuple<F, C, D>,
+        timing: &mut TimingTree,
+    ) -> Result<ProofTuple<F, C, D>> {
         // generate circuit config
         let config = ShotCircuit::config_outer()?;
 
-        // define targets
-        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
-        let pt = builder.add_virtual_proof_with_pis(&inner.2);
-        let inner_data = builder.add_virtual_verifier_data(inner.2.config.fri_config.cap_height);
-        let outer_targets = RecursiveTargets {
-            proof: pt.clone(),
-            verifier: inner_data.clone(),
-        };
-
-        // synthesize outer proof
-        builder.verify_proof::<C>(&pt, &inner_data, &inner.2);
+        // decode inner commitment before the inner proof is consumed by the fold
+        let inner_commitment = ShotCircuit::decode_public(inner.0.clone())?.commitment;
 
-        // pipe commitment to outer proof public inputs
-        builder.register_public_inputs(&pt.public_inputs);
-
-        // construct circuit data
-        let data = builder.build::<C>();
+        // verify the inner proof and pipe its public inputs to the outer proof
+        let proof = fold_with_timing(inner, &config, "prove shot outer", timing, |builder, pt, _| {
+            builder.register_public_inputs(&pt.public_inputs);
+            Ok(())
+        })?;
 
-        // compute partial witness
-        let pw = ShotCircuit::partial_witness_outer(inner, outer_targets)?;
-
-        // prove outer proof provides valid shielding of a board validity circuit
-        let mut timing = TimingTree::new("prove", Level::Debug);
-        let proof = prove(&data.prover_only, &data.common, pw, &mut timing)?;
-        timing.print();
-
-        // verify the outer proof's integrity
-        data.verify(proof.clone())?;
+        // defensively check the commitment survived the inner -> outer transition
+        let outer_commitment = ShotCircuit::decode_public(proof.0.clone())?.commitment;
+        if inner_commitment != outer_commitment {
+            return Err(CircuitError::CommitmentMismatch.into());
+        }
 
         // return outer proof artifacts
-        Ok((proof, data.verifier_only, data.common))
+        Ok(proof)
     }
 
     /**
@@ -243,10 +676,13 @@ impl ShotCircuit {
      * @return - formatted outputs from shot ciruit
      */
     pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<ShotCircuitOutputs> {
+        // ensure proof matches the shot circuit layout
+        check_public_inputs(&proof, SHOT_PUBLIC_INPUTS)?;
+
         let public_inputs = proof.clone().public_inputs;
-        let shot = public_inputs[0].to_canonical_u64() as u8;
-        let hit = public_inputs[1].to_canonical_u64() != 0;
-        let commitment: [u64; 4] = public_inputs[2..6]
+        let shot = public_inputs[1].to_canonical_u64() as u8;
+        let hit = ShotResult::from(public_inputs[2].to_canonical_u64() != 0);
+        let commitment: [u64; 4] = public_inputs[3..7]
             .iter()
             .map(|x| x.to_canonical_u64())
             .collect::<Vec<u64>>()
@@ -260,11 +696,78 @@ impl ShotCircuit {
     }
 }
 
+impl DualShotCircuit {
+    /**
+     * Prove hit/ miss of a shot against the board selected by the turn, committing to both boards
+     *
+     * @param host - host board configuration
+     * @param guest - guest board configuration
+     * @param turn - false to shoot the host board, true to shoot the guest board
+     * @param shot - the shot coordinate (x, y)
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove(
+        host: Board,
+        guest: Board,
+        turn: bool,
+        shot: [u8; 2],
+    ) -> Result<ProofTuple<F, C, D>> {
+        let circuit = ShotCircuit::build_dual(&ShotCircuit::config_inner()?)?;
+
+        // WITNESS //
+        let mut pw = PartialWitness::new();
+        pw.set_target_arr(circuit.host_t, host.canonical().map(F::from_canonical_u32));
+        pw.set_target_arr(circuit.guest_t, guest.canonical().map(F::from_canonical_u32));
+        pw.set_target_arr(circuit.shot_t, shot.map(F::from_canonical_u8));
+        pw.set_bool_target(circuit.turn_t, turn);
+
+        // PROVE //
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&circuit.data.prover_only, &circuit.data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        circuit.data.verify(proof.clone())?;
+        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+    }
+
+    /**
+     * Decode the output of a dual shot proof
+     *
+     * @param proof - proof from dual shot circuit
+     * @return - formatted outputs from dual shot circuit
+     */
+    pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<DualShotOutputs> {
+        // ensure proof matches the dual shot circuit layout
+        check_public_inputs(&proof, DUAL_SHOT_PUBLIC_INPUTS)?;
+
+        let public_inputs = proof.public_inputs;
+        let commitment = |offset: usize| -> [u64; 4] {
+            public_inputs[offset..offset + 4]
+                .iter()
+                .map(|x| x.to_canonical_u64())
+                .collect::<Vec<u64>>()
+                .try_into()
+                .unwrap()
+        };
+        Ok(DualShotOutputs {
+            turn: public_inputs[1].to_canonical_u64() != 0,
+            shot: public_inputs[2].to_canonical_u64() as u8,
+            hit: ShotResult::from(public_inputs[3].to_canonical_u64() != 0),
+            host: commitment(4),
+            guest: commitment(8),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::utils::{board::Board, ship::Ship};
+    use crate::{
+        circuits::{test_utils::dummy_proof, CircuitError},
+        utils::{board::Board, ecdsa::keypair},
+    };
 
     // Carrier: 3, 4, false
     // Battleship: 9, 6, true
@@ -288,18 +791,13 @@ mod tests {
     #[test]
     fn test_shot_hit() {
         // define inputs
-        let board = Board::new(
-            Ship::new(3, 4, false),
-            Ship::new(9, 6, true),
-            Ship::new(0, 0, false),
-            Ship::new(0, 6, false),
-            Ship::new(6, 1, true),
-        );
+        let board = Board::standard_host();
         let shot = [0u8, 0];
 
         // prove inner proof
         let inner = ShotCircuit::prove_inner(board.clone(), shot.clone()).unwrap();
         println!("Inner proof successful");
+        let inner_commitment = ShotCircuit::decode_public(inner.0.clone()).unwrap().commitment;
 
         // prove outer proof
         let outer = ShotCircuit::prove_outer(inner).unwrap();
@@ -308,23 +806,20 @@ mod tests {
         // verify integrity of public exports
         let output = ShotCircuit::decode_public(outer.0.clone()).unwrap();
         let expected_shot = 0u8;
-        let expected_hit = true;
+        let expected_hit = ShotResult::Hit;
         let expected_commitment = board.hash();
         assert_eq!(output.shot, expected_shot);
         assert_eq!(output.hit, expected_hit);
         assert_eq!(output.commitment, expected_commitment);
+
+        // commitment survives the inner -> outer transition unchanged
+        assert_eq!(output.commitment, inner_commitment);
     }
 
     #[test]
     fn test_shot_miss() {
         // define inputs
-        let board = Board::new(
-            Ship::new(3, 4, false),
-            Ship::new(9, 6, true),
-            Ship::new(0, 0, false),
-            Ship::new(0, 6, false),
-            Ship::new(6, 1, true),
-        );
+        let board = Board::standard_host();
         let shot = [0u8, 1];
 
         // prove inner proof
@@ -338,11 +833,127 @@ mod tests {
         // verify integrity of public exports
         let output = ShotCircuit::decode_public(outer.0.clone()).unwrap();
         let expected_shot = 10u8;
-        let expected_hit = false;
+        let expected_hit = ShotResult::Miss;
         let expected_commitment = board.hash();
         assert_eq!(output.shot, expected_shot);
         assert_eq!(output.hit, expected_hit);
         assert_eq!(output.commitment, expected_commitment);
     }
-    // }
+
+    #[test]
+    fn test_shot_from_bits() {
+        // define inputs
+        let board = Board::standard_host();
+        let shot = [9u8, 7];
+
+        // build raw bitmap without going through Board
+        let mut bits = [false; 100];
+        for coordinate in [0, 1, 2, 16, 26, 43, 44, 45, 46, 47, 60, 61, 62, 69, 79, 89, 99] {
+            bits[coordinate] = true;
+        }
+        assert_eq!(bits, board.bits());
+
+        // prove shot against bitmap and against board
+        let from_bits = ShotCircuit::prove_inner_bits(bits, shot).unwrap();
+        let from_board = ShotCircuit::prove_inner(board.clone(), shot).unwrap();
+
+        // outputs must match
+        let output_bits = ShotCircuit::decode_public(from_bits.0).unwrap();
+        let output_board = ShotCircuit::decode_public(from_board.0).unwrap();
+        assert_eq!(output_bits.shot, output_board.shot);
+        assert_eq!(output_bits.hit, output_board.hit);
+        assert_eq!(output_bits.commitment, output_board.commitment);
+        assert_eq!(output_bits.hit, ShotResult::Hit);
+        assert_eq!(output_bits.commitment, board.hash());
+    }
+
+    #[test]
+    fn test_decode_public_shape() {
+        // truncated public inputs
+        let err = ShotCircuit::decode_public(dummy_proof(SHOT_PUBLIC_INPUTS - 1)).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::PublicInputShape));
+
+        // over-long public inputs
+        let err = ShotCircuit::decode_public(dummy_proof(SHOT_PUBLIC_INPUTS + 1)).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::PublicInputShape));
+
+        // well formed public inputs
+        assert!(ShotCircuit::decode_public(dummy_proof(SHOT_PUBLIC_INPUTS)).is_ok());
+    }
+
+    #[test]
+    fn test_timed_shot() {
+        // prove a shot and collect timings
+        let (proof, timing) =
+            ShotCircuit::prove_inner_timed(Board::standard_host(), [0, 0]).unwrap();
+        assert!(ShotCircuit::decode_public(proof.0).unwrap().hit.is_hit());
+
+        // every stage recorded a duration
+        assert!(timing.prove > std::time::Duration::ZERO);
+        assert!(timing.verify > std::time::Duration::ZERO);
+        assert!(timing.total() > std::time::Duration::ZERO);
+    }
+
+    #[test]
+    fn test_shot_attestation() {
+        // guest attests a hit on its carrier
+        let (secret_key, public_key) = keypair();
+        let proof = ShotCircuit::prove_inner(Board::standard_guest(), [3, 3]).unwrap();
+        let outputs = ShotCircuit::decode_public(proof.0.clone()).unwrap();
+        assert_eq!(outputs.hit, ShotResult::Hit);
+        let attestation = ShotAttestation::sign(outputs, secret_key);
+        assert!(attestation.verify(public_key));
+        assert!(attestation.verify_against(proof.0.clone(), public_key).unwrap());
+
+        // another key does not verify
+        let (_, other_key) = keypair();
+        assert!(!attestation.verify(other_key));
+
+        // forged hit bit invalidates the signature and no longer matches the proof
+        let forged = ShotAttestation {
+            outputs: ShotCircuitOutputs {
+                hit: ShotResult::Miss,
+                ..attestation.outputs
+            },
+            signature: attestation.signature,
+        };
+        assert!(!forged.verify(public_key));
+        assert!(!forged.verify_against(proof.0, public_key).unwrap());
+    }
+
+    #[test]
+    fn test_prove_from_preimage() {
+        let board = Board::standard_host();
+        let preimage = board.canonical().map(F::from_canonical_u32);
+
+        // preimage proof exports the same outputs as the Board-derived proof
+        let from_preimage = ShotCircuit::prove_inner_preimage(preimage, [4, 4]).unwrap();
+        let from_board = ShotCircuit::prove_inner(board.clone(), [4, 4]).unwrap();
+        assert_eq!(from_preimage.0.public_inputs, from_board.0.public_inputs);
+        let outputs = ShotCircuit::decode_public(from_preimage.0).unwrap();
+        assert_eq!(outputs.hit, ShotResult::Hit);
+        assert_eq!(outputs.commitment, board.hash());
+    }
+
+    #[test]
+    fn test_dual_shot() {
+        // (0, 0) is the host's cruiser but open water on the guest board
+        let host = Board::standard_host();
+        let guest = Board::standard_guest();
+
+        let proof = DualShotCircuit::prove(host.clone(), guest.clone(), false, [0, 0]).unwrap();
+        let outputs = DualShotCircuit::decode_public(proof.0).unwrap();
+        assert!(!outputs.turn);
+        assert_eq!(outputs.shot, 0);
+        assert_eq!(outputs.hit, ShotResult::Hit);
+        assert_eq!(outputs.host, host.hash());
+        assert_eq!(outputs.guest, guest.hash());
+
+        // the turn bit switches the lookup to the guest board under the same commitments
+        let proof = DualShotCircuit::prove(host.clone(), guest.clone(), true, [0, 0]).unwrap();
+        let outputs = DualShotCircuit::decode_public(proof.0).unwrap();
+        assert!(outputs.turn);
+        assert_eq!(outputs.hit, ShotResult::Miss);
+        assert_eq!((outputs.host, outputs.guest), (host.hash(), guest.hash()));
+    }
 }
@@ -0,0 +1,313 @@
+use {
+    super::super::{check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, C, D, F},
+    crate::{
+        gadgets::board::{
+            count_sunk_ships, decompose_board, hash_board, witness_standard_fleet, FleetShipTarget,
+            STANDARD_FLEET,
+        },
+        utils::{board::Board, shot::serialize_shot},
+    },
+    anyhow::Result,
+    log::Level,
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        iop::{
+            target::Target,
+            witness::{PartialWitness, WitnessWrite},
+        },
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitConfig, CircuitData},
+            proof::ProofWithPublicInputs,
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// number of public inputs exported by the sunk ships circuit (kind tag, hits, sunk count, commitment)
+pub const SUNK_SHIPS_PUBLIC_INPUTS: usize = 10;
+
+pub struct SunkShipsCircuitOutputs {
+    pub hits: [u32; 4], // shot coordinates as a u128 serialized in LE by 4 u32s
+    pub sunk: u8,
+    pub commitment: [u64; 4],
+}
+
+pub struct SunkShipsCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub board_t: [Target; 4],
+    pub hits_t: [Target; 4],
+    pub ships: Vec<FleetShipTarget>, // placements of the standard fleet, in fleet order
+}
+
+// Argument of knowledge proving how many ships of a committed board are sunk by a public set of shots
+// @dev used for spectator modes that show remaining ships without revealing their positions
+impl SunkShipsCircuit {
+    /**
+     * Generate a circuit config for the sunk ships circuit
+     *
+     * @return - circuit config
+     */
+    pub fn config_inner() -> Result<CircuitConfig> {
+        Ok(CircuitConfigBuilder::new().with_random_access().build())
+    }
+
+    /**
+     * Generate a circuit config that uses zero knowledge blinding
+     *
+     * @return - circuit config
+     */
+    pub fn config_outer() -> Result<CircuitConfig> {
+        Ok(CircuitConfigBuilder::new().with_zk(true).build())
+    }
+
+    /**
+     * Serialize a list of shot coordinates into a hit bitmap
+     * @notice off-board shots cannot hit a ship and are skipped
+     *
+     * @param shots - shot coordinates (x, y)
+     * @return - bitmap of shot coordinates (index = 10y + x)
+     */
+    pub fn hit_bits(shots: &[[u8; 2]]) -> [bool; 100] {
+        let mut bits = [false; 100];
+        for shot in shots {
+            if let Some(index) = serialize_shot(shot[0], shot[1]) {
+                bits[index as usize] = true;
+            }
+        }
+        bits
+    }
+
+    /**
+     * Generate the witness for the sunk ships circuit inner proof inputs
+     *
+     * @param board - the board configuration object
+     * @param hits - bitmap of shot coordinates (index = 10y + x)
+     * @param board_t - the board targets, a u128 serialized in LE by 4 u32s
+     * @param hits_t - the hit targets, a u128 serialized in LE by 4 u32s
+     * @param ships - the fleet placement targets
+     * @return - inner proof witness
+     */
+    pub fn partial_witness_inner(
+        board: Board,
+        hits: [bool; 100],
+        board_t: [Target; 4],
+        hits_t: [Target; 4],
+        ships: &[FleetShipTarget],
+    ) -> Result<PartialWitness<F>> {
+        // marshall board and hits into canonical form
+        let board_canonical = board.canonical();
+        let hits_canonical = Board::pack_bits(&hits);
+
+        // witness board state and hits
+        let mut pw = PartialWitness::new();
+        for i in 0..board_t.len() {
+            pw.set_target(board_t[i], F::from_canonical_u32(board_canonical[i]));
+            pw.set_target(hits_t[i], F::from_canonical_u32(hits_canonical[i]));
+        }
+
+        // witness fleet placements
+        witness_standard_fleet(&mut pw, ships, &board);
+
+        // return witnessed input variables
+        Ok(pw)
+    }
+
+    /**
+     * Layout the circuit for counting the ships of a committed board sunk by a set of shots
+     *
+     * @param config - circuit config
+     * @return - circuit data and board/ hit targets
+     */
+    pub fn build(config: &CircuitConfig) -> Result<SunkShipsCircuit> {
+        // define circuit builder
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // input targets
+        let board_t: [Target; 4] = builder.add_virtual_targets(4).try_into().unwrap();
+        let hits_t: [Target; 4] = builder.add_virtual_targets(4).try_into().unwrap();
+
+        // count ships whose every cell has been hit
+        // @notice: constrains the board to the standard fleet shape
+        let board_bits = decompose_board(board_t, &mut builder)?;
+        let hit_bits = decompose_board(hits_t, &mut builder)?;
+        let (sunk_t, ships) =
+            count_sunk_ships(board_bits, hit_bits, &STANDARD_FLEET, &mut builder)?;
+
+        // export circuit kind tag
+        CircuitKind::SunkShips.register(&mut builder);
+
+        // export hits and sunk ship count publicly
+        builder.register_public_inputs(&hits_t);
+        builder.register_public_input(sunk_t);
+
+        // export binding commitment to board publicly
+        let board_hash_t = hash_board(board_t, &mut builder)?;
+        builder.register_public_inputs(&board_hash_t.elements);
+
+        // return circuit data and input targets
+        let data = builder.build::<C>();
+        Ok(Self {
+            data,
+            board_t,
+            hits_t,
+            ships,
+        })
+    }
+
+    /**
+     * Given a board configuration, prove the number of ships sunk by a set of shots
+     *
+     * @param board - board configuration
+     * @param shots - shot coordinates (x, y) made against the board
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_inner(board: Board, shots: &[[u8; 2]]) -> Result<ProofTuple<F, C, D>> {
+        // generate circuit config
+        let config = SunkShipsCircuit::config_inner()?;
+
+        // build inner proof circuit
+        let circuit = SunkShipsCircuit::build(&config)?;
+
+        // witness board and hits
+        let pw = SunkShipsCircuit::partial_witness_inner(
+            board,
+            SunkShipsCircuit::hit_bits(shots),
+            circuit.board_t,
+            circuit.hits_t,
+            &circuit.ships,
+        )?;
+
+        // generate proof
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(
+            &circuit.data.prover_only,
+            &circuit.data.common,
+            pw,
+            &mut timing,
+        )?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        circuit.data.verify(proof.clone())?;
+
+        // PROVE //
+        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+    }
+
+    /**
+     * Recursive outer proof that obfuscates information of inner proof
+     *
+     * @param inner - the proof tuple from the execution of the inner SunkShipsCircuit proof
+     * @return - outer proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove_outer(inner: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
+        // generate circuit config
+        let config = SunkShipsCircuit::config_outer()?;
+
+        // define targets
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let pt = builder.add_virtual_proof_with_pis(&inner.2);
+        let inner_data = builder.add_virtual_verifier_data(inner.2.config.fri_config.cap_height);
+
+        // synthesize outer proof
+        builder.verify_proof::<C>(&pt, &inner_data, &inner.2);
+
+        // pipe hits, sunk count and commitment to outer proof public inputs
+        builder.register_public_inputs(&pt.public_inputs);
+
+        // construct circuit data
+        let data = builder.build::<C>();
+
+        // witness inner proof
+        let mut pw = PartialWitness::new();
+        pw.set_proof_with_pis_target(&pt, &inner.0);
+        pw.set_verifier_data_target(&inner_data, &inner.1);
+
+        // prove outer proof provides valid shielding of a sunk ships circuit
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&data.prover_only, &data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the outer proof's integrity
+        data.verify(proof.clone())?;
+
+        // return outer proof artifacts
+        Ok((proof, data.verifier_only, data.common))
+    }
+
+    /**
+     * Decode the output of a sunk ships proof
+     *
+     * @param proof - proof from sunk ships circuit
+     * @return - formatted outputs from sunk ships circuit
+     */
+    pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<SunkShipsCircuitOutputs> {
+        // ensure proof matches the sunk ships circuit layout
+        check_public_inputs(&proof, SUNK_SHIPS_PUBLIC_INPUTS)?;
+
+        let public_inputs = proof.public_inputs;
+        let hits: [u32; 4] = public_inputs[1..5]
+            .iter()
+            .map(|x| x.to_canonical_u64() as u32)
+            .collect::<Vec<u32>>()
+            .try_into()
+            .unwrap();
+        let sunk = public_inputs[5].to_canonical_u64() as u8;
+        let commitment: [u64; 4] = public_inputs[6..10]
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap();
+        Ok(SunkShipsCircuitOutputs {
+            hits,
+            sunk,
+            commitment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Carrier: 3, 4, false
+    // Battleship: 9, 6, true
+    // Cruiser: 0, 0, false
+    // Submarine: 0, 6, false
+    // Destroyer: 6, 1, true
+
+    #[test]
+    fn test_sunk_carrier() {
+        // hit every cell of the carrier, and all but one cell of the battleship
+        let board = Board::standard_host();
+        let shots = [[3u8, 4], [4, 4], [5, 4], [6, 4], [7, 4], [9, 6], [9, 7], [9, 8], [0, 9]];
+
+        // prove the number of sunk ships
+        let inner = SunkShipsCircuit::prove_inner(board.clone(), &shots).unwrap();
+        let outer = SunkShipsCircuit::prove_outer(inner).unwrap();
+
+        // only the carrier is reported sunk
+        let output = SunkShipsCircuit::decode_public(outer.0).unwrap();
+        assert_eq!(output.sunk, 1);
+        assert_eq!(output.hits, Board::pack_bits(&SunkShipsCircuit::hit_bits(&shots)));
+        assert_eq!(output.commitment, board.hash());
+    }
+
+    #[test]
+    fn test_sunk_fleet() {
+        // hit every occupied coordinate
+        let board = Board::standard_host();
+        let shots = board
+            .occupied_coordinates()
+            .iter()
+            .map(|coordinate| [coordinate % 10, coordinate / 10])
+            .collect::<Vec<[u8; 2]>>();
+
+        // every ship is reported sunk
+        let inner = SunkShipsCircuit::prove_inner(board, &shots).unwrap();
+        assert_eq!(SunkShipsCircuit::decode_public(inner.0).unwrap().sunk, 5);
+    }
+}
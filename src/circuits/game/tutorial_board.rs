@@ -0,0 +1,268 @@
+use {
+    super::super::{
+        check_public_inputs, CircuitConfigBuilder, CircuitKind, ProofTuple, C, D, F,
+    },
+    crate::{
+        gadgets::board::{
+            decompose_board, hash_board, place_typed_ship, recompose_board, TypedShipTarget,
+        },
+        utils::board::Board,
+    },
+    anyhow::Result,
+    log::Level,
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        iop::{
+            target::{BoolTarget, Target},
+            witness::{PartialWitness, WitnessWrite},
+        },
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            circuit_data::{CircuitConfig, CircuitData},
+            proof::ProofWithPublicInputs,
+            prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+};
+
+// number of public inputs exported by the tutorial board circuit (kind tag, mode, public board, commitment)
+pub const TUTORIAL_BOARD_PUBLIC_INPUTS: usize = 10;
+
+pub struct TutorialBoardOutputs {
+    pub tutorial: bool,         // true = commitment is to the public board
+    pub public_board: [u32; 4], // public board limbs (zero outside tutorial mode)
+    pub commitment: [u64; 4],
+}
+
+pub struct TutorialBoardCircuit {
+    pub data: CircuitData<F, C, D>,
+    pub carrier: TypedShipTarget<5>,
+    pub battleship: TypedShipTarget<4>,
+    pub cruiser: TypedShipTarget<3>,
+    pub submarine: TypedShipTarget<3>,
+    pub destroyer: TypedShipTarget<2>,
+    pub tutorial: BoolTarget,
+    pub public_board: [Target; 4],
+}
+
+// Argument of knowledge proving a board commitment is either the hash of a valid private board (normal mode) or the
+// hash of a board given in the public inputs (tutorial mode)
+// @dev one verifier handles both modes; the mode flag is exported so verifiers can reject tutorial boards in real games
+// @dev placement constraints are unconditional and the public board must equal the placed fleet in tutorial mode, so
+//      tutorial proofs still commit to a valid fleet placement
+impl TutorialBoardCircuit {
+    /**
+     * Generate a circuit config capable of handling 128 bit random access gates
+     *
+     * @return - circuit config
+     */
+    pub fn config_inner() -> Result<CircuitConfig> {
+        Ok(CircuitConfigBuilder::new().with_random_access().build())
+    }
+
+    /**
+     * Layout the circuit committing to the placed fleet and optionally revealing it as the public board
+     * @notice the public board is constrained to tutorial * placed: the placed fleet in tutorial mode, zero otherwise
+     *
+     * @param config - circuit config
+     * @return - circuit data and ship/ mode/ public board targets
+     */
+    pub fn build(config: &CircuitConfig) -> Result<TutorialBoardCircuit> {
+        let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+        // TARGETS //
+        let carrier = TypedShipTarget::<5>::new(&mut builder);
+        let battleship = TypedShipTarget::<4>::new(&mut builder);
+        let cruiser = TypedShipTarget::<3>::new(&mut builder);
+        let submarine = TypedShipTarget::<3>::new(&mut builder);
+        let destroyer = TypedShipTarget::<2>::new(&mut builder);
+        let tutorial = builder.add_virtual_bool_target_safe();
+        let public_board = builder.add_virtual_target_arr::<4>();
+
+        // SYNTHESIZE //
+        // place the private fleet on a blank board
+        let board_blank: [Target; 4] = builder
+            .constants(&[F::ZERO; 4])
+            .try_into()
+            .unwrap();
+        let board_initial = decompose_board(board_blank, &mut builder)?;
+        let board_0 = place_typed_ship(carrier, board_initial, &mut builder)?;
+        let board_1 = place_typed_ship(battleship, board_0, &mut builder)?;
+        let board_2 = place_typed_ship(cruiser, board_1, &mut builder)?;
+        let board_3 = place_typed_ship(submarine, board_2, &mut builder)?;
+        let board_4 = place_typed_ship(destroyer, board_3, &mut builder)?;
+        let placed = recompose_board(board_4, &mut builder)?;
+
+        // reveal the placed fleet as the public board in tutorial mode, otherwise leave the public board zeroed
+        for i in 0..4 {
+            let revealed = builder.mul(tutorial.target, placed[i]);
+            builder.connect(public_board[i], revealed);
+        }
+        let commitment = hash_board(placed, &mut builder)?;
+
+        // PUBLIC INPUTS //
+        //  - [0] = circuit kind tag (tutorial board)
+        //  - [1] = tutorial mode flag
+        //  - [2..6] = public board limbs
+        //  - [6..10] = board commitment
+        CircuitKind::TutorialBoard.register(&mut builder);
+        builder.register_public_input(tutorial.target);
+        builder.register_public_inputs(&public_board);
+        builder.register_public_inputs(&commitment.elements);
+
+        let data = builder.build::<C>();
+        Ok(Self {
+            data,
+            carrier,
+            battleship,
+            cruiser,
+            submarine,
+            destroyer,
+            tutorial,
+            public_board,
+        })
+    }
+
+    /**
+     * Generate the witness for the tutorial board circuit
+     * @notice in tutorial mode the board is public: its limbs are exported and its own placement is witnessed
+     *
+     * @param board - board configuration
+     * @param tutorial - whether to commit to the board as a public tutorial board
+     * @return - witness of the ship, mode and public board targets
+     */
+    pub fn partial_witness(&self, board: &Board, tutorial: bool) -> Result<PartialWitness<F>> {
+        let mut pw = PartialWitness::new();
+        self.carrier.witness(&mut pw, &board.carrier);
+        self.battleship.witness(&mut pw, &board.battleship);
+        self.cruiser.witness(&mut pw, &board.cruiser);
+        self.submarine.witness(&mut pw, &board.submarine);
+        self.destroyer.witness(&mut pw, &board.destroyer);
+        pw.set_bool_target(self.tutorial, tutorial);
+        let limbs = match tutorial {
+            true => board.canonical(),
+            false => [0; 4],
+        };
+        pw.set_target_arr(self.public_board, limbs.map(F::from_canonical_u32));
+        Ok(pw)
+    }
+
+    /**
+     * Prove a board commitment in normal or tutorial mode
+     *
+     * @param board - board configuration
+     * @param tutorial - whether to commit to the board as a public tutorial board
+     * @return - proof tuple of everything needed to verify the proof natively or recursively
+     */
+    pub fn prove(board: Board, tutorial: bool) -> Result<ProofTuple<F, C, D>> {
+        let circuit = TutorialBoardCircuit::build(&TutorialBoardCircuit::config_inner()?)?;
+        let pw = circuit.partial_witness(&board, tutorial)?;
+
+        // generate proof
+        let mut timing = TimingTree::new("prove", Level::Debug);
+        let proof = prove(&circuit.data.prover_only, &circuit.data.common, pw, &mut timing)?;
+        timing.print();
+
+        // verify the proof was generated correctly
+        circuit.data.verify(proof.clone())?;
+        Ok((proof, circuit.data.verifier_only, circuit.data.common))
+    }
+
+    /**
+     * Decode the mode, public board and commitment exported by a tutorial board proof
+     *
+     * @param proof - proof from tutorial board circuit
+     * @return - formatted outputs from tutorial board circuit
+     */
+    pub fn decode_public(proof: ProofWithPublicInputs<F, C, D>) -> Result<TutorialBoardOutputs> {
+        // ensure proof matches the tutorial board circuit layout
+        check_public_inputs(&proof, TUTORIAL_BOARD_PUBLIC_INPUTS)?;
+
+        let public_inputs = proof.public_inputs;
+        let tutorial = public_inputs[1].to_canonical_u64() != 0;
+        let public_board: [u32; 4] = public_inputs[2..6]
+            .iter()
+            .map(|x| x.to_canonical_u64() as u32)
+            .collect::<Vec<u32>>()
+            .try_into()
+            .unwrap();
+        let commitment: [u64; 4] = public_inputs[6..10]
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap();
+        Ok(TutorialBoardOutputs {
+            tutorial,
+            public_board,
+            commitment,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::{decode_any, DecodedPublic};
+
+    #[test]
+    fn test_tutorial_board_modes() {
+        let board = Board::standard_guest();
+
+        // normal mode: private board, nothing public beyond the commitment
+        let private = TutorialBoardCircuit::prove(board.clone(), false).unwrap();
+        let outputs = TutorialBoardCircuit::decode_public(private.0.clone()).unwrap();
+        assert!(!outputs.tutorial);
+        assert_eq!(outputs.public_board, [0; 4]);
+        assert_eq!(outputs.commitment, board.hash());
+
+        // tutorial mode: board limbs exported, same commitment
+        let public = TutorialBoardCircuit::prove(board.clone(), true).unwrap();
+        let outputs = match decode_any(public.0.clone()).unwrap() {
+            DecodedPublic::TutorialBoard(outputs) => outputs,
+            _ => panic!("expected tutorial board proof"),
+        };
+        assert!(outputs.tutorial);
+        assert_eq!(outputs.public_board, board.canonical());
+        assert_eq!(outputs.commitment, board.hash());
+
+        // one verifier handles both modes
+        assert_eq!(private.1, public.1);
+        assert_eq!(private.2, public.2);
+    }
+
+    /**
+     * Prove a tutorial board proof placing one fleet while claiming another as the public board
+     *
+     * @param placed - board whose fleet is witnessed
+     * @param public_board - limbs claimed as the public board
+     * @param tutorial - mode flag to witness
+     */
+    fn prove_claimed(placed: &Board, public_board: [u32; 4], tutorial: bool) {
+        let circuit = TutorialBoardCircuit::build(&TutorialBoardCircuit::config_inner().unwrap()).unwrap();
+        let mut pw = PartialWitness::new();
+        circuit.carrier.witness(&mut pw, &placed.carrier);
+        circuit.battleship.witness(&mut pw, &placed.battleship);
+        circuit.cruiser.witness(&mut pw, &placed.cruiser);
+        circuit.submarine.witness(&mut pw, &placed.submarine);
+        circuit.destroyer.witness(&mut pw, &placed.destroyer);
+        pw.set_bool_target(circuit.tutorial, tutorial);
+        pw.set_target_arr(circuit.public_board, public_board.map(F::from_canonical_u32));
+        circuit.data.prove(pw).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tutorial_board_differs_from_placed() {
+        // guest fleet placed, host board claimed as the public tutorial board
+        prove_claimed(&Board::standard_guest(), Board::standard_host().canonical(), true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_private_board_leaks_public_board() {
+        // normal mode must not export any public board
+        prove_claimed(&Board::standard_guest(), Board::standard_guest().canonical(), false);
+    }
+}
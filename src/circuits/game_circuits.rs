@@ -0,0 +1,404 @@
+use {
+    super::{
+        channel::{
+            close_channel::{can_close, ChannelCloseCircuit},
+            increment_channel::StateIncrementCircuit,
+            open_channel::ChannelOpenCircuit,
+            win_proof::WinProofCircuit,
+        },
+        game::{board::BoardCircuit, shot::ShotCircuit},
+        prove_classified, ProofTuple, C, D, F,
+    },
+    crate::utils::board::Board,
+    anyhow::{anyhow, ensure, Result},
+    plonky2::plonk::circuit_data::CommonCircuitData,
+    std::{ops::Deref, sync::Arc},
+};
+
+// BattleZips Game Circuits: every circuit needed to prove a game, built once and reused for every proof
+//  - board and shot circuits are fixed, so a single build serves every board and every shot
+//  - channel open is built against the board circuit and the first state increment against channel open
+//  - later state increments verify the previous increment, whose common data can change until the recursion
+//    reaches a fixed point; setup builds increments until one verifies proofs of its own shape
+//  - channel close is built against the fixed point state increment
+
+// maximum number of state increment circuits built while searching for the recursion fixed point
+pub const MAX_INCREMENT_CIRCUITS: usize = 4;
+
+// Ceilings on the degree (log2 of gate rows) of each game circuit: prove time roughly doubles with each degree,
+// so a change that crosses a ceiling must be reviewed and the ceiling raised deliberately
+pub const MAX_BOARD_DEGREE_BITS: usize = 11;
+pub const MAX_SHOT_DEGREE_BITS: usize = 6;
+pub const MAX_OPEN_DEGREE_BITS: usize = 14;
+pub const MAX_INCREMENT_DEGREE_BITS: usize = 14;
+pub const MAX_CLOSE_DEGREE_BITS: usize = 14;
+
+pub struct GameCircuits {
+    pub board: BoardCircuit,
+    pub shot: ShotCircuit,
+    pub open: ChannelOpenCircuit,
+    // (common data of the previous state proof, increment circuit verifying it)
+    pub increments: Vec<(CommonCircuitData<F, D>, StateIncrementCircuit)>,
+    pub close: ChannelCloseCircuit,
+}
+
+impl GameCircuits {
+    /**
+     * Build and cache every circuit needed to prove a game
+     * @notice expensive: call once at startup and share the result across games
+     *
+     * @return - cached game circuits
+     */
+    pub fn setup() -> Result<GameCircuits> {
+        // board and shot circuits
+        let board = BoardCircuit::build(&BoardCircuit::config_inner()?)?;
+        let shot = ShotCircuit::build(&ShotCircuit::config_inner()?)?;
+
+        // channel open against the board circuit
+        let open = ChannelOpenCircuit::build(&board.data.common, &board.data.common)?;
+
+        // state increments until the increment circuit verifies proofs of its own shape
+        let mut increments = Vec::<(CommonCircuitData<F, D>, StateIncrementCircuit)>::new();
+        let mut prev = open.data.common.clone();
+        loop {
+            ensure!(
+                increments.len() < MAX_INCREMENT_CIRCUITS,
+                "state increment recursion did not reach a fixed point"
+            );
+            let circuit = StateIncrementCircuit::build(&prev, &shot.data.common)?;
+            let next = circuit.data.common.clone();
+            let fixed_point = next == prev;
+            increments.push((prev, circuit));
+            if fixed_point {
+                break;
+            }
+            prev = next;
+        }
+
+        // channel close against the fixed point state increment
+        let close = ChannelCloseCircuit::build(&prev)?;
+
+        Ok(Self {
+            board,
+            shot,
+            open,
+            increments,
+            close,
+        })
+    }
+
+    /**
+     * Prove board validity with the cached board circuit
+     *
+     * @param board - board configuration
+     * @return - board validity proof
+     */
+    pub fn prove_board(&self, board: Board) -> Result<ProofTuple<F, C, D>> {
+        self.board.prove_cached(board)
+    }
+
+    /**
+     * Prove board validity bound to a game nonce with the cached board circuit
+     * @notice only bound board proofs can open a channel
+     *
+     * @param board - board configuration
+     * @param nonce - game/ session id to bind the proof to
+     * @return - board validity proof
+     */
+    pub fn prove_board_with_nonce(&self, board: Board, nonce: u64) -> Result<ProofTuple<F, C, D>> {
+        self.board.prove_cached_with_nonce(board, nonce)
+    }
+
+    /**
+     * Prove hit/ miss of a shot with the cached shot circuit
+     *
+     * @param board - board configuration being shot
+     * @param shot - the shot coordinate (x, y)
+     * @return - shot proof
+     */
+    pub fn prove_shot(&self, board: Board, shot: [u8; 2]) -> Result<ProofTuple<F, C, D>> {
+        self.shot.prove_cached(board, shot)
+    }
+
+    /**
+     * Open a channel with the cached channel open circuit
+     *
+     * @param host - board proof from prove_board_with_nonce made by host
+     * @param guest - board proof from prove_board_with_nonce made by guest, bound to the same nonce
+     * @param shot - opening shot to be made by host
+     * @return - channel open proof
+     */
+    pub fn prove_open(
+        &self,
+        host: ProofTuple<F, C, D>,
+        guest: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+    ) -> Result<ProofTuple<F, C, D>> {
+        self.open.prove_cached(host, guest, shot)
+    }
+
+    /**
+     * Increment channel state with the cached state increment circuit matching the previous state proof
+     *
+     * @param prev_p - channel open or state increment proof produced by this cache
+     * @param shot_p - shot proof from prove_shot
+     * @param shot - shot coordinate to be verified in next state increment
+     * @return - state increment proof
+     */
+    pub fn prove_increment(
+        &self,
+        prev_p: ProofTuple<F, C, D>,
+        shot_p: ProofTuple<F, C, D>,
+        shot: [u8; 2],
+    ) -> Result<ProofTuple<F, C, D>> {
+        let circuit = self
+            .increments
+            .iter()
+            .find(|(prev, _)| *prev == prev_p.2)
+            .map(|(_, circuit)| circuit)
+            .ok_or_else(|| anyhow!("no cached state increment circuit for the previous state proof"))?;
+        circuit.prove_cached(prev_p, shot_p, shot)
+    }
+
+    /**
+     * Close a channel with the cached channel close circuit
+     *
+     * @param state_p - final state increment proof produced by this cache
+     * @return - channel close proof exporting the winner and loser board commitments
+     */
+    pub fn prove_close(&self, state_p: ProofTuple<F, C, D>) -> Result<ProofTuple<F, C, D>> {
+        ensure!(
+            state_p.2 == self.increments.last().unwrap().1.data.common,
+            "no cached channel close circuit for the state proof"
+        );
+        self.close.prove_cached(state_p)
+    }
+
+    /**
+     * Prove a clean win with a win proof circuit pinned to the fixed point state increment circuit
+     * @notice the win circuit is not cached: it is only needed by the player claiming the win
+     *
+     * @param state_p - final state increment proof produced by this cache
+     * @param winner - claimed winner (true = guest, false = host)
+     * @return - win proof exporting the winner and loser board commitments
+     */
+    pub fn prove_win(
+        &self,
+        state_p: ProofTuple<F, C, D>,
+        winner: bool,
+    ) -> Result<ProofTuple<F, C, D>> {
+        let state = &self.increments.last().unwrap().1.data;
+        ensure!(state_p.2 == state.common, "no state increment circuit for the win proof to trust");
+        WinProofCircuit::build(&state.verifier_only, &state.common)?.prove_cached(state_p, winner)
+    }
+
+    /**
+     * Prove a full ZK State Channel from a shot log using only cached circuits
+     * @notice shot log follows the convention of prove_channel_with_progress
+     * @dev failures of proofs consuming earlier proofs downcast to a VerifyError (see prove_classified)
+     *
+     * @param host - host board configuration
+     * @param guest - guest board configuration
+     * @param nonce - game/ session id both board proofs are bound to (must not be 0)
+     * @param shots - ordered shot log alternating guest board, host board (starting with the host's opening shot)
+     * @return - proof that the channel closed with a winner
+     */
+    pub fn prove_channel(
+        &self,
+        host: Board,
+        guest: Board,
+        nonce: u64,
+        shots: &[[u8; 2]],
+    ) -> Result<ProofTuple<F, C, D>> {
+        ensure!(shots.len() >= 2, "shot log must contain an opening shot and a next shot");
+
+        // CHANNEL OPEN //
+        let host_p = self.prove_board_with_nonce(host.clone(), nonce)?;
+        let guest_p = self.prove_board_with_nonce(guest.clone(), nonce)?;
+        let mut state_p = prove_classified(&[&host_p, &guest_p], || {
+            self.prove_open(host_p.clone(), guest_p.clone(), shots[0])
+        })?;
+
+        // STATE INCREMENTS //
+        for k in 1..shots.len() {
+            // shot the board selected by the turn of the previous state
+            let turn = StateIncrementCircuit::decode_public(state_p.0.clone())?.turn;
+            let board = if turn { guest.clone() } else { host.clone() };
+            let shot_p = self.prove_shot(board, shots[k - 1])?;
+            state_p = prove_classified(&[&state_p, &shot_p], || {
+                self.prove_increment(state_p.clone(), shot_p.clone(), shots[k])
+            })?;
+        }
+
+        // CHANNEL CLOSE //
+        ensure!(can_close(&state_p.0), "shot log does not finish the game");
+        Ok(prove_classified(&[&state_p], || self.prove_close(state_p.clone()))?)
+    }
+}
+
+// Game circuits shared by many concurrent games (e.g. across server worker threads)
+// @dev proving only reads the cached circuit data (each call owns its witness), so the cache is shared without locks
+#[derive(Clone)]
+pub struct SharedGameCircuits(Arc<GameCircuits>);
+
+// cached circuits must stay shareable across threads: fails to compile if a circuit gains non Send/ Sync state
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<GameCircuits>();
+};
+
+impl SharedGameCircuits {
+    /**
+     * Build every game circuit once and wrap the cache for sharing across threads
+     *
+     * @return - shared game circuits; clone to hand a reference to another thread
+     */
+    pub fn setup() -> Result<SharedGameCircuits> {
+        Ok(SharedGameCircuits(Arc::new(GameCircuits::setup()?)))
+    }
+}
+
+impl From<GameCircuits> for SharedGameCircuits {
+    fn from(circuits: GameCircuits) -> Self {
+        SharedGameCircuits(Arc::new(circuits))
+    }
+}
+
+impl Deref for SharedGameCircuits {
+    type Target = GameCircuits;
+
+    fn deref(&self) -> &GameCircuits {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::{
+        channel::{
+            close_channel::{decode_public, CLOSE_PUBLIC_INPUTS},
+            GameState, GAME_STATE_PUBLIC_INPUTS,
+        },
+        game::{board::BOARD_PUBLIC_INPUTS, shot::SHOT_PUBLIC_INPUTS},
+        test_utils::{mock_state_proof, GAME_NONCE},
+    };
+
+    #[test]
+    fn test_setup_reuses_cached_circuits() {
+        let circuits = GameCircuits::setup().unwrap();
+
+        // last increment circuit verifies proofs of its own shape
+        let (prev, last) = circuits.increments.last().unwrap();
+        assert_eq!(*prev, last.data.common);
+
+        // cached proofs are made by the cached circuits
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let board_p = circuits.prove_board(host_board.clone()).unwrap();
+        assert_eq!(board_p.1.circuit_digest, circuits.board.data.verifier_only.circuit_digest);
+        let shot_p = circuits.prove_shot(guest_board.clone(), [0, 0]).unwrap();
+        assert_eq!(shot_p.1.circuit_digest, circuits.shot.data.verifier_only.circuit_digest);
+
+        // full game (every guest coordinate hit, host wins) proven by the cached close circuit
+        let mut shots = Vec::<[u8; 2]>::new();
+        for coordinate in guest_board.occupied_coordinates() {
+            shots.push([coordinate % 10, coordinate / 10]);
+            shots.push([5, 5]);
+        }
+        let close_p = circuits
+            .prove_channel(host_board.clone(), guest_board.clone(), GAME_NONCE, &shots)
+            .unwrap();
+        assert_eq!(close_p.1.circuit_digest, circuits.close.data.verifier_only.circuit_digest);
+
+        // close proof names the host as the winner
+        let outputs = decode_public(close_p.0).unwrap();
+        assert_eq!(outputs.winner, host_board.hash());
+        assert_eq!(outputs.loser, guest_board.hash());
+        assert_eq!(outputs.nonce, GAME_NONCE);
+    }
+
+    #[test]
+    fn test_win_rejects_untrusted_state() {
+        // a final state proven outside of the cache cannot back a win proof
+        let circuits = GameCircuits::setup().unwrap();
+        let state_p = mock_state_proof(&GameState {
+            host: Board::standard_host().hash(),
+            guest: Board::standard_guest().hash(),
+            host_damage: 17,
+            guest_damage: 0,
+            turn: true,
+            shot: 0,
+            moves: 34,
+            resolved_shot: 0,
+            resolved_hit: true,
+            nonce: GAME_NONCE,
+            match_index: 0,
+        });
+        assert!(circuits.prove_win(state_p, true).is_err());
+    }
+
+    #[test]
+    fn test_degree_bits_ceilings() {
+        let circuits = GameCircuits::setup().unwrap();
+        let degrees = [
+            ("board", circuits.board.data.common.degree_bits(), MAX_BOARD_DEGREE_BITS),
+            ("shot", circuits.shot.data.common.degree_bits(), MAX_SHOT_DEGREE_BITS),
+            ("open", circuits.open.data.common.degree_bits(), MAX_OPEN_DEGREE_BITS),
+            ("close", circuits.close.data.common.degree_bits(), MAX_CLOSE_DEGREE_BITS),
+        ];
+        for (name, degree_bits, max) in degrees {
+            assert!(degree_bits <= max, "{} circuit degree {} exceeds ceiling {}", name, degree_bits, max);
+        }
+        for (_, increment) in circuits.increments.iter() {
+            let degree_bits = increment.data.common.degree_bits();
+            assert!(
+                degree_bits <= MAX_INCREMENT_DEGREE_BITS,
+                "increment circuit degree {} exceeds ceiling {}",
+                degree_bits,
+                MAX_INCREMENT_DEGREE_BITS
+            );
+        }
+    }
+
+    #[test]
+    fn test_num_public_inputs() {
+        let circuits = GameCircuits::setup().unwrap();
+        assert_eq!(circuits.board.num_public_inputs(), BOARD_PUBLIC_INPUTS);
+        assert_eq!(circuits.shot.num_public_inputs(), SHOT_PUBLIC_INPUTS);
+        assert_eq!(circuits.open.num_public_inputs(), GAME_STATE_PUBLIC_INPUTS);
+        for (_, increment) in circuits.increments.iter() {
+            assert_eq!(increment.num_public_inputs(), GAME_STATE_PUBLIC_INPUTS);
+        }
+        assert_eq!(circuits.close.num_public_inputs(), CLOSE_PUBLIC_INPUTS);
+        // documented counts
+        assert_eq!(
+            [BOARD_PUBLIC_INPUTS, SHOT_PUBLIC_INPUTS, GAME_STATE_PUBLIC_INPUTS, CLOSE_PUBLIC_INPUTS],
+            [6, 7, 18, 12]
+        );
+    }
+
+    #[test]
+    fn test_shared_circuits_across_threads() {
+        let circuits = SharedGameCircuits::setup().unwrap();
+        let boards = (0..4u8)
+            .map(|symmetry| Board::standard_host().transform(symmetry))
+            .collect::<Vec<Board>>();
+
+        // every thread proves a board against the same cached circuit
+        let handles = boards
+            .iter()
+            .map(|board| {
+                let circuits = circuits.clone();
+                let board = board.clone();
+                std::thread::spawn(move || circuits.prove_board(board))
+            })
+            .collect::<Vec<_>>();
+        for (handle, board) in handles.into_iter().zip(boards.iter()) {
+            let proof = handle.join().unwrap().unwrap();
+            assert_eq!(proof.1.circuit_digest, circuits.board.data.verifier_only.circuit_digest);
+            circuits.board.data.verify(proof.0.clone()).unwrap();
+            assert_eq!(BoardCircuit::decode_public(proof.0).unwrap().commitment, board.hash());
+        }
+    }
+}
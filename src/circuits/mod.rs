@@ -1,16 +1,58 @@
-use plonky2::plonk::{
-    config::{GenericConfig, PoseidonGoldilocksConfig},
-    circuit_data::{CommonCircuitData, VerifierOnlyCircuitData, VerifierCircuitTarget},
-    proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget}
+use {
+    anyhow::Result,
+    channel::{
+        close_channel::{self, CloseOutputs, TimeoutOutputs},
+        increment_channel::StateIncrementCircuit,
+        player_keys::{PlayerKeysCircuit, PlayerKeysOutputs},
+        win_proof::{WinOutputs, WinProofCircuit},
+        GameState,
+    },
+    game::{
+        board::{BoardCircuit, BoardCircuitOutputs},
+        consistency::{ConsistencyCircuit, ConsistencyCircuitOutputs},
+        contains_ship::{ContainsShipCircuit, ContainsShipCircuitOutputs},
+        merkle_shot::{MerkleShotCircuit, MerkleShotCircuitOutputs},
+        remaining_ships::{RemainingShipsCircuit, RemainingShipsOutputs},
+        shot::{DualShotCircuit, DualShotOutputs, ShotCircuit, ShotCircuitOutputs},
+        sunk_ships::{SunkShipsCircuit, SunkShipsCircuitOutputs},
+        tutorial_board::{TutorialBoardCircuit, TutorialBoardOutputs},
+    },
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        plonk::{
+            circuit_builder::CircuitBuilder,
+            config::{GenericConfig, KeccakGoldilocksConfig, PoseidonGoldilocksConfig},
+            circuit_data::{
+                CircuitConfig, CircuitData, CommonCircuitData, VerifierCircuitData,
+                VerifierCircuitTarget, VerifierOnlyCircuitData,
+            },
+            proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget},
+        },
+    },
+    std::{
+        collections::HashMap,
+        fmt,
+        panic::{catch_unwind, AssertUnwindSafe},
+        sync::{Arc, Mutex, MutexGuard, PoisonError},
+        time::Duration,
+    },
 };
 
 pub mod game;
 pub mod channel;
+pub mod shape;
+pub mod recursion;
+pub mod game_circuits;
 // pub mod recursion_ex;
 
 pub const D: usize = 2;
 pub type C = PoseidonGoldilocksConfig;
 pub type F = <C as GenericConfig<D>>::F;
+// hasher used for commitments in gadgets and natively (follows the generic config)
+pub type H = <C as GenericConfig<D>>::Hasher;
+// alternate config for the final wrap of a proof (keccak merkle caps are cheaper to check outside the field)
+// @dev plonky2 only provides goldilocks configs, so the wrap changes the hasher rather than the field
+pub type WrapC = KeccakGoldilocksConfig;
 
 pub type ProofTuple<F, C, const D: usize> = (
     ProofWithPublicInputs<F, C, D>,
@@ -18,7 +60,819 @@ pub type ProofTuple<F, C, const D: usize> = (
     CommonCircuitData<F, D>,
 );
 
+#[derive(Clone)]
 pub struct RecursiveTargets {
     pub proof: ProofWithPublicInputsTarget<D>,
     pub verifier: VerifierCircuitTarget,
-}
\ No newline at end of file
+}
+
+// Builder for the circuit configs used by BattleZips circuits
+// @dev zero knowledge is opt-in: inner proofs default to no blinding and are shielded by zk outer proofs, but any
+//      circuit can be blinded directly by building its config with_zk(true)
+#[derive(Debug, Clone)]
+pub struct CircuitConfigBuilder {
+    config: CircuitConfig,
+}
+
+impl Default for CircuitConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CircuitConfigBuilder {
+    /**
+     * Start from the standard recursion config (no zero knowledge blinding)
+     *
+     * @return - circuit config builder
+     */
+    pub fn new() -> CircuitConfigBuilder {
+        CircuitConfigBuilder {
+            config: CircuitConfig::standard_recursion_config(),
+        }
+    }
+
+    /**
+     * Widen the config to handle 128 bit random access gates (board lookups)
+     *
+     * @return - circuit config builder
+     */
+    pub fn with_random_access(mut self) -> CircuitConfigBuilder {
+        // set wires for random access gate
+        self.config.num_wires = 137;
+        self.config.num_routed_wires = 130;
+        self
+    }
+
+    /**
+     * Toggle zero knowledge blinding
+     *
+     * @param zk - whether proofs generated with the config are zero knowledge
+     * @return - circuit config builder
+     */
+    pub fn with_zk(mut self, zk: bool) -> CircuitConfigBuilder {
+        self.config.zero_knowledge = zk;
+        self
+    }
+
+    /**
+     * Finish building the circuit config
+     *
+     * @return - circuit config
+     */
+    pub fn build(self) -> CircuitConfig {
+        self.config
+    }
+}
+
+// Wall clock timings of a proof, returned by the _timed provers so callers can log or aggregate them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProofTiming {
+    pub build: Duration,  // time spent laying out and building the circuit
+    pub prove: Duration,  // time spent generating the proof
+    pub verify: Duration, // time spent natively verifying the generated proof
+}
+
+impl ProofTiming {
+    /**
+     * Total time spent building, proving and verifying
+     *
+     * @return - sum of all recorded durations
+     */
+    pub fn total(&self) -> Duration {
+        self.build + self.prove + self.verify
+    }
+}
+
+// Minimal set of artifacts a verifier must store to check a proof natively:
+//  - proof with public inputs (the statement and its proof)
+//  - verifier only circuit data (constants/ sigmas cap and circuit digest)
+//  - common circuit data (gates, config and fri parameters shared by prover and verifier)
+// @dev prover only circuit data is never needed, so a verifier never has to build the circuit
+pub struct VerifierBundle(pub ProofTuple<F, C, D>);
+
+impl VerifierBundle {
+    /**
+     * Natively verify the bundled proof against its bundled verifier data
+     *
+     * @return - error if the proof does not verify
+     */
+    pub fn verify(&self) -> Result<()> {
+        let (proof, verifier_only, common) = &self.0;
+        let verifier = VerifierCircuitData {
+            verifier_only: verifier_only.clone(),
+            common: common.clone(),
+        };
+        verifier.verify(proof.clone())
+    }
+}
+
+// Conversion of a proof tuple into the verifier bundle
+pub trait IntoVerifierBundle {
+    fn into_verifier_bundle(self) -> VerifierBundle;
+}
+
+impl IntoVerifierBundle for ProofTuple<F, C, D> {
+    /**
+     * Package a proof tuple as a verifier bundle
+     * @notice drops nothing: every element of the proof tuple is required to verify
+     *
+     * @return - verifier bundle
+     */
+    fn into_verifier_bundle(self) -> VerifierBundle {
+        VerifierBundle(self)
+    }
+}
+
+/**
+ * Compare two proof tuples for logical equality: the same statement proven by the same circuit
+ * @notice proof bytes are ignored since zero knowledge blinding makes two proofs of one statement differ
+ *
+ * @param a - first proof tuple
+ * @param b - second proof tuple
+ * @return - true if the public inputs and verifier data of both proofs are equal
+ */
+pub fn proof_tuples_equal(a: &ProofTuple<F, C, D>, b: &ProofTuple<F, C, D>) -> bool {
+    a.0.public_inputs == b.0.public_inputs && a.1 == b.1 && a.2 == b.2
+}
+
+// Verifier circuit data prepared once per trusted circuit and reused for every proof of that circuit
+// @dev keyed by circuit digest and filled only from circuit data the verifier built itself: the verifier data
+//      bundled with a proof is compared against the cached data, never inserted
+// @dev the map is behind a mutex so one cache can be shared by verifying threads
+#[derive(Default)]
+pub struct VerifierCache {
+    verifiers: Mutex<HashMap<[u64; 4], Arc<VerifierCircuitData<F, C, D>>>>,
+}
+
+impl VerifierCache {
+    /**
+     * Instantiate an empty verifier cache
+     *
+     * @return - verifier cache without any prepared verifier data
+     */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Return the number of circuits with prepared verifier data
+     *
+     * @return - number of cached verifiers
+     */
+    pub fn prepared(&self) -> usize {
+        self.verifiers().len()
+    }
+
+    /**
+     * Prepare the verifier data of a circuit built by the verifier
+     *
+     * @param data - circuit data of a trusted circuit
+     */
+    pub fn trust(&self, data: &CircuitData<F, C, D>) {
+        let digest = VerifierCache::digest(&data.verifier_only);
+        self.verifiers().insert(digest, Arc::new(data.verifier_data()));
+    }
+
+    /**
+     * Natively verify a bundled proof against the prepared verifier data of its circuit
+     *
+     * @param bundle - proof and verifier data to verify
+     * @return - UntrustedCircuit if the digest is not trusted or the bundled verifier data differs from the
+     *           trusted data, otherwise error if the proof does not verify
+     */
+    pub fn verify(&self, bundle: &VerifierBundle) -> Result<()> {
+        let (proof, verifier_only, common) = &bundle.0;
+        let verifier = self
+            .verifiers()
+            .get(&VerifierCache::digest(verifier_only))
+            .cloned()
+            .ok_or(CircuitError::UntrustedCircuit)?;
+        if verifier.verifier_only != *verifier_only || verifier.common != *common {
+            return Err(CircuitError::UntrustedCircuit.into());
+        }
+        verifier.verify(proof.clone())
+    }
+
+    /**
+     * Lock the prepared verifier data
+     * @dev a thread panicking while holding the lock cannot leave the map half updated (single insert/ lookup),
+     *      so a poisoned lock is recovered rather than failing every later verification
+     *
+     * @return - guard over the prepared verifier data
+     */
+    fn verifiers(&self) -> MutexGuard<'_, HashMap<[u64; 4], Arc<VerifierCircuitData<F, C, D>>>> {
+        self.verifiers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /**
+     * Key a circuit by its digest
+     *
+     * @param verifier_only - verifier data of the circuit
+     * @return - circuit digest as canonical u64s
+     */
+    fn digest(verifier_only: &VerifierOnlyCircuitData<C, D>) -> [u64; 4] {
+        verifier_only.circuit_digest.elements.map(|x| x.to_canonical_u64())
+    }
+}
+
+// Tag registered as the first public input of every circuit so proofs of unknown kind can be dispatched
+// @dev outer (shielding) proofs pipe the inner public inputs through and therefore carry the inner tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitKind {
+    Board = 0,
+    Shot = 1,
+    ChannelOpen = 2,
+    StateIncrement = 3,
+    ChannelClose = 4,
+    ContainsShip = 5,
+    ChannelTimeout = 6,
+    Win = 7,
+    SunkShips = 8,
+    MerkleShot = 9,
+    EmptyBoard = 10,
+    TutorialBoard = 11,
+    Consistency = 12,
+    PlayerKeys = 13,
+    RemainingShips = 14,
+    DualShot = 15,
+}
+
+impl CircuitKind {
+    /**
+     * Parse a circuit kind from its public input tag
+     *
+     * @param tag - first public input of a proof
+     * @return - circuit kind, or None if the tag is unknown
+     */
+    pub fn from_tag(tag: u64) -> Option<CircuitKind> {
+        match tag {
+            0 => Some(CircuitKind::Board),
+            1 => Some(CircuitKind::Shot),
+            2 => Some(CircuitKind::ChannelOpen),
+            3 => Some(CircuitKind::StateIncrement),
+            4 => Some(CircuitKind::ChannelClose),
+            5 => Some(CircuitKind::ContainsShip),
+            6 => Some(CircuitKind::ChannelTimeout),
+            7 => Some(CircuitKind::Win),
+            8 => Some(CircuitKind::SunkShips),
+            9 => Some(CircuitKind::MerkleShot),
+            10 => Some(CircuitKind::EmptyBoard),
+            11 => Some(CircuitKind::TutorialBoard),
+            12 => Some(CircuitKind::Consistency),
+            13 => Some(CircuitKind::PlayerKeys),
+            14 => Some(CircuitKind::RemainingShips),
+            15 => Some(CircuitKind::DualShot),
+            _ => None,
+        }
+    }
+
+    /**
+     * Register the circuit kind tag as the next public input
+     * @notice must be called before any other public input is registered
+     *
+     * @param builder - circuit builder
+     */
+    pub fn register(self, builder: &mut CircuitBuilder<F, D>) {
+        let tag_t = builder.constant(F::from_canonical_u64(self as u64));
+        builder.register_public_input(tag_t);
+    }
+}
+
+// Public outputs of any BattleZips proof, decoded according to its circuit kind tag
+pub enum DecodedPublic {
+    Board(BoardCircuitOutputs),
+    Shot(ShotCircuitOutputs),
+    ChannelOpen(GameState),
+    StateIncrement(GameState),
+    ChannelClose(CloseOutputs),
+    ContainsShip(ContainsShipCircuitOutputs),
+    ChannelTimeout(TimeoutOutputs), // draw: (host commitment, guest commitment, move cap)
+    Win(WinOutputs),
+    SunkShips(SunkShipsCircuitOutputs),
+    MerkleShot(MerkleShotCircuitOutputs),
+    EmptyBoard(BoardCircuitOutputs), // commitment to the all-zero board
+    TutorialBoard(TutorialBoardOutputs),
+    Consistency(ConsistencyCircuitOutputs),
+    PlayerKeys(PlayerKeysOutputs),
+    RemainingShips(RemainingShipsOutputs),
+    DualShot(DualShotOutputs),
+}
+
+// Errors surfaced when handling proofs produced by BattleZips circuits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitError {
+    PublicInputShape,   // proof does not expose the public input layout of the circuit being decoded
+    UnknownKind,        // proof's circuit kind tag does not match any BattleZips circuit
+    CommitmentMismatch, // outer (shielding) proof does not export the commitment of its inner proof
+    WrongBoard,         // shot proof was not made against the board of the player being shot this turn
+    Timeout,            // proving did not finish within its time budget
+    UntrustedCircuit,   // proof was generated by a circuit other than the one the verifier trusts
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::PublicInputShape => {
+                write!(f, "proof public inputs do not match the expected circuit layout")
+            }
+            CircuitError::UnknownKind => write!(f, "proof has an unknown circuit kind tag"),
+            CircuitError::CommitmentMismatch => {
+                write!(f, "outer proof commitment does not match inner proof commitment")
+            }
+            CircuitError::WrongBoard => {
+                write!(f, "shot proof commitment does not match the board of the player being shot")
+            }
+            CircuitError::Timeout => write!(f, "proving did not finish within its time budget"),
+            CircuitError::UntrustedCircuit => {
+                write!(f, "proof circuit digest does not match the trusted circuit")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+// Classified failure of a proof consumed by a BattleZips circuit, so clients can respond to each case
+// @dev corrupt proofs can be re-requested from the peer; constraint failures mean the peer's move is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyError {
+    Corrupt,           // proof does not parse or verify against its verifier data (damaged or tampered with)
+    ConstraintFailure, // proofs verify on their own, but the consuming circuit cannot be satisfied by them
+    ShapeMismatch,     // proof does not expose the public input layout of a BattleZips circuit
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::Corrupt => write!(f, "proof is corrupt and does not verify"),
+            VerifyError::ConstraintFailure => {
+                write!(f, "proofs verify but do not satisfy the consuming circuit")
+            }
+            VerifyError::ShapeMismatch => {
+                write!(f, "proof public inputs do not match a known circuit layout")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/**
+ * Verify a proof tuple, classifying why it fails
+ * @notice checked in order: public input layout (ShapeMismatch), then the proof itself (Corrupt)
+ *
+ * @param proof - proof tuple to verify
+ * @return - error classifying the failure, if any
+ */
+pub fn verify_classified(proof: &ProofTuple<F, C, D>) -> std::result::Result<(), VerifyError> {
+    let public_inputs = &proof.0.public_inputs;
+    let known_kind = public_inputs
+        .first()
+        .and_then(|tag| CircuitKind::from_tag(tag.to_canonical_u64()))
+        .is_some();
+    if public_inputs.len() != proof.2.num_public_inputs || !known_kind {
+        return Err(VerifyError::ShapeMismatch);
+    }
+    proof
+        .clone()
+        .into_verifier_bundle()
+        .verify()
+        .map_err(|_| VerifyError::Corrupt)
+}
+
+/**
+ * Deserialize and verify a proof received as bytes, classifying why it fails
+ *
+ * @param bytes - serialized proof with public inputs
+ * @param verifier_only - verifier data of the circuit the proof claims to come from
+ * @param common - common data of the circuit the proof claims to come from
+ * @return - deserialized proof, or error classifying the failure
+ */
+pub fn verify_bytes_classified(
+    bytes: &[u8],
+    verifier_only: &VerifierOnlyCircuitData<C, D>,
+    common: &CommonCircuitData<F, D>,
+) -> std::result::Result<ProofWithPublicInputs<F, C, D>, VerifyError> {
+    let proof = ProofWithPublicInputs::<F, C, D>::from_bytes(bytes.to_vec(), common)
+        .map_err(|_| VerifyError::Corrupt)?;
+    verify_classified(&(proof.clone(), verifier_only.clone(), common.clone()))?;
+    Ok(proof)
+}
+
+/**
+ * Run a proving call that consumes previous proofs, classifying why it fails
+ * @notice inputs are verified first, so a failure of the proving call itself is a ConstraintFailure unless it is a
+ *         public input layout error
+ * @dev plonky2 panics rather than erroring on a conflicting witness, so the proving call is run under catch_unwind
+ *
+ * @param inputs - proofs consumed by the proving call
+ * @param prove - proving call to attempt
+ * @return - result of the proving call, or error classifying the failure
+ */
+pub fn prove_classified<T>(
+    inputs: &[&ProofTuple<F, C, D>],
+    prove: impl FnOnce() -> Result<T>,
+) -> std::result::Result<T, VerifyError> {
+    for input in inputs.iter() {
+        verify_classified(input)?;
+    }
+    match catch_unwind(AssertUnwindSafe(prove)) {
+        Ok(result) => result.map_err(|e| match e.downcast_ref::<CircuitError>() {
+            Some(CircuitError::PublicInputShape) | Some(CircuitError::UnknownKind) => {
+                VerifyError::ShapeMismatch
+            }
+            _ => VerifyError::ConstraintFailure,
+        }),
+        // witness generation panicked on a conflicting witness
+        Err(_) => Err(VerifyError::ConstraintFailure),
+    }
+}
+
+/**
+ * Ensure a proof exposes exactly the number of public inputs a circuit layout expects
+ * @dev guards decoders against proofs from a different circuit instead of panicking on slice access
+ *
+ * @param proof - proof whose public inputs are about to be decoded
+ * @param expected - number of public inputs registered by the circuit
+ * @return - error if the public inputs do not match the expected layout
+ */
+pub fn check_public_inputs(proof: &ProofWithPublicInputs<F, C, D>, expected: usize) -> Result<()> {
+    if proof.public_inputs.len() != expected {
+        return Err(CircuitError::PublicInputShape.into());
+    }
+    Ok(())
+}
+
+/**
+ * Decode the public outputs of a proof of unknown kind by dispatching on its circuit kind tag
+ *
+ * @param proof - proof from any BattleZips circuit
+ * @return - public outputs formatted according to the circuit kind
+ */
+pub fn decode_any(proof: ProofWithPublicInputs<F, C, D>) -> Result<DecodedPublic> {
+    // read circuit kind tag
+    let tag = match proof.public_inputs.first() {
+        Some(tag) => tag.to_canonical_u64(),
+        None => return Err(CircuitError::PublicInputShape.into()),
+    };
+    let kind = CircuitKind::from_tag(tag).ok_or(CircuitError::UnknownKind)?;
+
+    // dispatch to the decoder for the circuit kind
+    Ok(match kind {
+        CircuitKind::Board => DecodedPublic::Board(BoardCircuit::decode_public(proof)?),
+        CircuitKind::Shot => DecodedPublic::Shot(ShotCircuit::decode_public(proof)?),
+        CircuitKind::ChannelOpen => {
+            DecodedPublic::ChannelOpen(StateIncrementCircuit::decode_public(proof)?)
+        }
+        CircuitKind::StateIncrement => {
+            DecodedPublic::StateIncrement(StateIncrementCircuit::decode_public(proof)?)
+        }
+        CircuitKind::ChannelClose => DecodedPublic::ChannelClose(close_channel::decode_public(proof)?),
+        CircuitKind::ContainsShip => {
+            DecodedPublic::ContainsShip(ContainsShipCircuit::decode_public(proof)?)
+        }
+        CircuitKind::ChannelTimeout => {
+            DecodedPublic::ChannelTimeout(close_channel::decode_timeout(proof)?)
+        }
+        CircuitKind::Win => DecodedPublic::Win(WinProofCircuit::decode_public(proof)?),
+        CircuitKind::SunkShips => {
+            DecodedPublic::SunkShips(SunkShipsCircuit::decode_public(proof)?)
+        }
+        CircuitKind::MerkleShot => {
+            DecodedPublic::MerkleShot(MerkleShotCircuit::decode_public(proof)?)
+        }
+        CircuitKind::EmptyBoard => DecodedPublic::EmptyBoard(BoardCircuit::decode_public(proof)?),
+        CircuitKind::TutorialBoard => {
+            DecodedPublic::TutorialBoard(TutorialBoardCircuit::decode_public(proof)?)
+        }
+        CircuitKind::Consistency => {
+            DecodedPublic::Consistency(ConsistencyCircuit::decode_public(proof)?)
+        }
+        CircuitKind::PlayerKeys => {
+            DecodedPublic::PlayerKeys(PlayerKeysCircuit::decode_public(proof)?)
+        }
+        CircuitKind::RemainingShips => {
+            DecodedPublic::RemainingShips(RemainingShipsCircuit::decode_public(proof)?)
+        }
+        CircuitKind::DualShot => DecodedPublic::DualShot(DualShotCircuit::decode_public(proof)?),
+    })
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use {
+        super::{
+            channel::{GameState, GAME_STATE_PUBLIC_INPUTS},
+            CircuitConfigBuilder, CircuitKind, ProofTuple, C, D, F,
+        },
+        plonky2::{
+            field::types::Field,
+            iop::witness::{PartialWitness, WitnessWrite},
+            plonk::{
+                circuit_builder::CircuitBuilder,
+                circuit_data::{CircuitConfig, CommonCircuitData},
+                proof::ProofWithPublicInputs,
+            },
+        },
+    };
+
+    // game nonce both board proofs of a test channel are bound to (channel open rejects unbound boards)
+    pub const GAME_NONCE: u64 = 1;
+
+    /**
+     * Assert two circuits share the common data a recursive verifier is built against
+     * @dev compares field by field so a failure names the field that drifted (e.g. gate ordering)
+     *
+     * @param a - common circuit data of the first circuit
+     * @param b - common circuit data of the second circuit
+     */
+    pub fn assert_common_data_eq(a: &CommonCircuitData<F, D>, b: &CommonCircuitData<F, D>) {
+        assert_eq!(a.config, b.config, "config differs");
+        assert_eq!(a.fri_params, b.fri_params, "fri params differ");
+        assert_eq!(a.degree_bits(), b.degree_bits(), "degree bits differ");
+        assert_eq!(a.gates, b.gates, "gates differ");
+        assert_eq!(a.selectors_info, b.selectors_info, "selectors differ");
+        assert_eq!(a.quotient_degree_factor, b.quotient_degree_factor, "quotient degree factor differs");
+        assert_eq!(a.num_gate_constraints, b.num_gate_constraints, "gate constraint count differs");
+        assert_eq!(a.num_constants, b.num_constants, "constant count differs");
+        assert_eq!(a.num_public_inputs, b.num_public_inputs, "public input count differs");
+        assert_eq!(a.k_is, b.k_is, "coset shifts differ");
+        assert_eq!(a.num_partial_products, b.num_partial_products, "partial product count differs");
+        assert!(a == b, "common data differs");
+    }
+
+    /**
+     * Generate a trivial proof carrying an arbitrary number of (zeroed) public inputs
+     * @dev used to exercise decoders without synthesizing the full game circuits
+     *
+     * @param num_public_inputs - length of the public input vector to attach to the proof
+     * @return - proof with the requested public input vector
+     */
+    pub fn dummy_proof(num_public_inputs: usize) -> ProofWithPublicInputs<F, C, D> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let zero = builder.zero();
+        builder.register_public_input(zero);
+        let data = builder.build::<C>();
+        let mut proof = data.prove(PartialWitness::new()).unwrap();
+        proof.public_inputs = vec![F::ZERO; num_public_inputs];
+        proof
+    }
+
+    /**
+     * Prove a trivial circuit exporting a given game state in the state increment layout
+     * @dev stands in for a final state increment proof so close and win proofs can be exercised without proving a full game
+     *
+     * @param state - game state to export
+     * @return - proof tuple carrying the game state as public inputs
+     */
+    pub fn mock_state_proof(state: &GameState) -> ProofTuple<F, C, D> {
+        mock_tagged_state_proof(state, CircuitKind::StateIncrement)
+    }
+
+    /**
+     * Prove the mock state circuit under an arbitrary circuit kind tag
+     * @dev same circuit as mock_state_proof, so a consumer pinned to it can only reject the proof by its tag
+     *
+     * @param state - game state to export
+     * @param kind - circuit kind tag to export as [0]
+     * @return - proof tuple carrying the tag and game state as public inputs
+     */
+    pub fn mock_tagged_state_proof(state: &GameState, kind: CircuitKind) -> ProofTuple<F, C, D> {
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfigBuilder::new().build());
+        let state_t = builder.add_virtual_targets(GAME_STATE_PUBLIC_INPUTS);
+        builder.register_public_inputs(&state_t);
+        let data = builder.build::<C>();
+
+        let mut values = vec![F::from_canonical_u64(kind as u64)];
+        values.extend(state.host.iter().chain(state.guest.iter()).map(|x| F::from_canonical_u64(*x)));
+        values.push(F::from_canonical_u8(state.host_damage));
+        values.push(F::from_canonical_u8(state.guest_damage));
+        values.push(F::from_bool(state.turn));
+        values.push(F::from_canonical_u8(state.shot));
+        values.push(F::from_canonical_u32(state.moves));
+        values.push(F::from_canonical_u8(state.resolved_shot));
+        values.push(F::from_bool(state.resolved_hit));
+        values.push(F::from_canonical_u64(state.nonce));
+        values.push(F::from_canonical_u64(state.match_index));
+        let mut pw = PartialWitness::new();
+        for (target, value) in state_t.iter().zip(values) {
+            pw.set_target(*target, value);
+        }
+        (data.prove(pw).unwrap(), data.verifier_only, data.common)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuits::{channel::open_channel::prove_channel_open, test_utils::GAME_NONCE},
+        utils::{board::Board, ship::Ship},
+    };
+
+    #[test]
+    fn test_verifier_bundle() {
+        // board proof verifies through the bundle
+        let board_p = BoardCircuit::prove_inner(Board::standard_host()).unwrap();
+        let bundle = board_p.into_verifier_bundle();
+        bundle.verify().unwrap();
+
+        // tampered public inputs do not verify
+        let mut tampered = bundle;
+        tampered.0 .0.public_inputs[1] = F::ONE;
+        assert!(tampered.verify().is_err());
+    }
+
+    #[test]
+    fn test_proof_tuples_equal() {
+        let circuit = BoardCircuit::build(&BoardCircuit::config_inner().unwrap()).unwrap();
+        let board_p = circuit.prove_cached(Board::standard_host()).unwrap();
+
+        // serialize -> deserialize round trip proves the same statement
+        let bytes = board_p.0.to_bytes();
+        let decoded = ProofWithPublicInputs::<F, C, D>::from_bytes(bytes, &board_p.2).unwrap();
+        let round_trip = (decoded, board_p.1.clone(), board_p.2.clone());
+        assert!(proof_tuples_equal(&board_p, &round_trip));
+
+        // another board is another statement of the same circuit
+        let other_p = circuit.prove_cached(Board::standard_guest()).unwrap();
+        assert!(!proof_tuples_equal(&board_p, &other_p));
+
+        // the same board under the outer circuit is verified by other verifier data
+        let outer_p = BoardCircuit::prove_outer(board_p.clone()).unwrap();
+        assert_eq!(outer_p.0.public_inputs, board_p.0.public_inputs);
+        assert!(!proof_tuples_equal(&board_p, &outer_p));
+    }
+
+    #[test]
+    fn test_verifier_cache() {
+        let cache = VerifierCache::new();
+        let circuit = BoardCircuit::build(&BoardCircuit::config_inner().unwrap()).unwrap();
+        let host_p = circuit.prove_cached(Board::standard_host()).unwrap();
+        let guest_p = circuit.prove_cached(Board::standard_guest()).unwrap();
+
+        // proofs of a circuit that was never trusted are rejected
+        let err = cache.verify(&host_p.clone().into_verifier_bundle()).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::UntrustedCircuit));
+        assert_eq!(cache.prepared(), 0);
+
+        // repeated verifications of proofs from the same circuit reuse the verifier data prepared once
+        cache.trust(&circuit.data);
+        for _ in 0..3 {
+            cache.verify(&host_p.clone().into_verifier_bundle()).unwrap();
+            cache.verify(&guest_p.clone().into_verifier_bundle()).unwrap();
+        }
+        assert_eq!(cache.prepared(), 1);
+
+        // proof of another trusted circuit uses its own verifier data
+        let shot = ShotCircuit::build(&ShotCircuit::config_inner().unwrap()).unwrap();
+        cache.trust(&shot.data);
+        let shot_p = shot.prove_cached(Board::standard_guest(), [3, 4]).unwrap();
+        cache.verify(&shot_p.clone().into_verifier_bundle()).unwrap();
+        assert_eq!(cache.prepared(), 2);
+
+        // trusted digest bundled with the common data of another circuit is rejected
+        let mismatched = (host_p.0.clone(), host_p.1.clone(), shot_p.2);
+        let err = cache.verify(&mismatched.into_verifier_bundle()).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::UntrustedCircuit));
+
+        // cached verifier data still rejects tampered proofs
+        let mut tampered = host_p.into_verifier_bundle();
+        tampered.0 .0.public_inputs[1] = F::ONE;
+        assert!(cache.verify(&tampered).is_err());
+        assert_eq!(cache.prepared(), 2);
+    }
+
+    #[test]
+    fn test_verifier_cache_shared() {
+        let cache = VerifierCache::new();
+        let circuit = BoardCircuit::build(&BoardCircuit::config_inner().unwrap()).unwrap();
+        cache.trust(&circuit.data);
+        let host_p = circuit.prove_cached(Board::standard_host()).unwrap();
+
+        // a thread panicking while holding the lock poisons it
+        _ = std::thread::scope(|s| {
+            s.spawn(|| {
+                let _guard = cache.verifiers.lock().unwrap();
+                panic!("poison the verifier cache");
+            })
+            .join()
+        });
+        assert!(cache.verifiers.is_poisoned());
+
+        // verifying threads share the poisoned cache without failing
+        std::thread::scope(|s| {
+            for _ in 0..2 {
+                s.spawn(|| cache.verify(&host_p.clone().into_verifier_bundle()).unwrap());
+            }
+        });
+        assert_eq!(cache.prepared(), 1);
+    }
+
+    #[test]
+    fn test_decode_any() {
+        // INPUTS
+        let host_board = Board::standard_host();
+        let guest_board = Board::standard_guest();
+        let shot = [3u8, 4];
+
+        // board proof
+        let host_p = BoardCircuit::prove_inner_with_nonce(host_board.clone(), GAME_NONCE).unwrap();
+        match decode_any(host_p.0.clone()).unwrap() {
+            DecodedPublic::Board(output) => assert_eq!(output.commitment, host_board.hash()),
+            _ => panic!("expected board proof"),
+        }
+
+        // shot proof
+        let shot_p = ShotCircuit::prove_inner(guest_board.clone(), shot).unwrap();
+        match decode_any(shot_p.0.clone()).unwrap() {
+            DecodedPublic::Shot(output) => {
+                assert_eq!(output.shot, 43);
+                assert_eq!(output.commitment, guest_board.hash());
+            }
+            _ => panic!("expected shot proof"),
+        }
+
+        // contains ship proof
+        let contains_p =
+            ContainsShipCircuit::prove_inner(host_board.clone(), Ship::<5>::new(3, 4, false))
+                .unwrap();
+        match decode_any(contains_p.0).unwrap() {
+            DecodedPublic::ContainsShip(output) => assert_eq!(output.length, 5),
+            _ => panic!("expected contains ship proof"),
+        }
+
+        // channel open proof
+        let guest_p = BoardCircuit::prove_inner_with_nonce(guest_board.clone(), GAME_NONCE).unwrap();
+        let open_p = prove_channel_open(host_p, guest_p, shot).unwrap();
+        match decode_any(open_p.0.clone()).unwrap() {
+            DecodedPublic::ChannelOpen(state) => {
+                assert_eq!(state.host, host_board.hash());
+                assert_eq!(state.guest, guest_board.hash());
+                assert_eq!(state.shot, 43);
+                assert_eq!(state.nonce, GAME_NONCE);
+            }
+            _ => panic!("expected channel open proof"),
+        }
+
+        // state increment proof
+        let increment_p = StateIncrementCircuit::prove(open_p, shot_p, [0, 0]).unwrap();
+        match decode_any(increment_p.0).unwrap() {
+            DecodedPublic::StateIncrement(state) => assert_eq!(state.guest_damage, 1),
+            _ => panic!("expected state increment proof"),
+        }
+    }
+
+    #[test]
+    fn test_verify_classified() {
+        let proof = BoardCircuit::prove_inner(Board::standard_host()).unwrap();
+        assert_eq!(verify_classified(&proof), Ok(()));
+
+        // bit flipped in the proof body (public inputs are serialized last)
+        let mut bytes = proof.0.to_bytes();
+        bytes[0] ^= 1;
+        let err = verify_bytes_classified(&bytes, &proof.1, &proof.2).err().unwrap();
+        assert_eq!(err, VerifyError::Corrupt);
+
+        // public inputs truncated
+        let mut truncated = proof.clone();
+        truncated.0.public_inputs.pop();
+        assert_eq!(verify_classified(&truncated), Err(VerifyError::ShapeMismatch));
+
+        // valid final state that cannot satisfy a win proof (neither board sunk): plonky2 panics on the witness
+        let state_p = test_utils::mock_state_proof(&GameState {
+            host: Board::standard_host().hash(),
+            guest: Board::standard_guest().hash(),
+            host_damage: 16,
+            guest_damage: 3,
+            turn: true,
+            shot: 0,
+            moves: 20,
+            resolved_shot: 0,
+            resolved_hit: true,
+            nonce: GAME_NONCE,
+            match_index: 0,
+        });
+        let win = WinProofCircuit::build(&state_p.1, &state_p.2).unwrap();
+        let err = prove_classified(&[&state_p], || win.prove_cached(state_p.clone(), true))
+            .err()
+            .unwrap();
+        assert_eq!(err, VerifyError::ConstraintFailure);
+
+        // corrupt input is reported before proving is attempted
+        let mut corrupt = proof.clone();
+        corrupt.0.public_inputs[1] += F::ONE;
+        let err = prove_classified(&[&proof, &corrupt], || Ok(())).err().unwrap();
+        assert_eq!(err, VerifyError::Corrupt);
+    }
+
+    #[test]
+    fn test_decode_any_unknown_kind() {
+        let mut proof = test_utils::dummy_proof(1);
+        proof.public_inputs[0] = F::from_canonical_u64(42);
+        let err = decode_any(proof).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::UnknownKind));
+    }
+}
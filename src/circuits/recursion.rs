@@ -0,0 +1,318 @@
+use {
+    super::{CircuitConfigBuilder, CircuitError, ProofTuple, RecursiveTargets, C, D, F},
+    anyhow::Result,
+    log::Level,
+    plonky2::{
+        iop::witness::{PartialWitness, WitnessWrite},
+        plonk::{
+            circuit_builder::CircuitBuilder, circuit_data::CircuitConfig, config::GenericConfig,
+            proof::ProofWithPublicInputsTarget, prover::prove,
+        },
+        util::timing::TimingTree,
+    },
+    std::{sync::mpsc, thread, time::Duration},
+};
+
+// BattleZips Recursion: fold a previous proof into a new proof that verifies it and applies one step
+//  - the previous proof is verified in circuit and witnessed by fold
+//  - the step closure adds the per-step constraints and public inputs, reading the previous public inputs
+//    through the proof target for continuity, and witnesses any private targets it introduces
+// @dev shared by outer (shielding) proofs and channel proofs that extend a single previous proof
+
+/**
+ * Fold a previous proof into a new proof under the standard (non zk) recursion config
+ *
+ * @param prev - proof tuple to verify in the new proof
+ * @param step - adds per-step constraints/ public inputs given the previous proof target, and witnesses its targets
+ * @return - proof tuple of the folded step
+ */
+pub fn fold(
+    prev: ProofTuple<F, C, D>,
+    step: impl FnOnce(
+        &mut CircuitBuilder<F, D>,
+        &ProofWithPublicInputsTarget<D>,
+        &mut PartialWitness<F>,
+    ) -> Result<()>,
+) -> Result<ProofTuple<F, C, D>> {
+    fold_with_config(prev, &CircuitConfigBuilder::new().build(), step)
+}
+
+/**
+ * Fold a previous proof into a new proof under a caller supplied circuit config
+ * @notice e.g. a zero knowledge config for shielding outer proofs
+ *
+ * @param prev - proof tuple to verify in the new proof
+ * @param config - circuit config of the new proof
+ * @param step - adds per-step constraints/ public inputs given the previous proof target, and witnesses its targets
+ * @return - proof tuple of the folded step
+ */
+pub fn fold_with_config(
+    prev: ProofTuple<F, C, D>,
+    config: &CircuitConfig,
+    step: impl FnOnce(
+        &mut CircuitBuilder<F, D>,
+        &ProofWithPublicInputsTarget<D>,
+        &mut PartialWitness<F>,
+    ) -> Result<()>,
+) -> Result<ProofTuple<F, C, D>> {
+    let mut timing = TimingTree::new("prove", Level::Debug);
+    let proof = fold_with_timing(prev, config, "fold", &mut timing, step)?;
+    timing.print();
+    Ok(proof)
+}
+
+/**
+ * Fold a previous proof into a new proof, recording proving time as a named span of a parent timing tree
+ * @notice lets a whole game's proofs accumulate into one tree that the caller prints once
+ *
+ * @param prev - proof tuple to verify in the new proof
+ * @param config - circuit config of the new proof
+ * @param name - name of the span the fold is recorded under
+ * @param timing - parent timing tree
+ * @param step - adds per-step constraints/ public inputs given the previous proof target, and witnesses its targets
+ * @return - proof tuple of the folded step
+ */
+pub fn fold_with_timing(
+    prev: ProofTuple<F, C, D>,
+    config: &CircuitConfig,
+    name: &str,
+    timing: &mut TimingTree,
+    step: impl FnOnce(
+        &mut CircuitBuilder<F, D>,
+        &ProofWithPublicInputsTarget<D>,
+        &mut PartialWitness<F>,
+    ) -> Result<()>,
+) -> Result<ProofTuple<F, C, D>> {
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+    // TARGETS //
+    let prev_t = RecursiveTargets {
+        proof: builder.add_virtual_proof_with_pis(&prev.2),
+        verifier: builder.add_virtual_verifier_data(prev.2.config.fri_config.cap_height),
+    };
+
+    // SYNTHESIZE //
+    // verify previous proof
+    builder.verify_proof::<C>(&prev_t.proof, &prev_t.verifier, &prev.2);
+
+    // WITNESS //
+    let mut pw = PartialWitness::new();
+    pw.set_proof_with_pis_target(&prev_t.proof, &prev.0);
+    pw.set_verifier_data_target(&prev_t.verifier, &prev.1);
+
+    // apply the step constraints, public inputs and witness
+    step(&mut builder, &prev_t.proof, &mut pw)?;
+
+    // PROVE //
+    // construct circuit data
+    let data = builder.build::<C>();
+    // generate proof under its own span of the parent tree
+    timing.push(name, Level::Debug);
+    let proof = prove(&data.prover_only, &data.common, pw, timing);
+    timing.pop();
+    let proof = proof?;
+
+    // verify the proof was generated correctly
+    data.verify(proof.clone())?;
+
+    Ok((proof, data.verifier_only, data.common))
+}
+
+/**
+ * Wrap a proof in a final proof generated under a different generic config
+ * @notice the wrapped proof re-exports the public inputs of prev unchanged, so existing layouts still decode
+ * @dev prev must use the (algebraic) poseidon config to be verifiable in circuit; the wrap itself can use any
+ *      goldilocks config, but cannot be recursed into further unless OC also uses an algebraic hasher
+ *
+ * @param prev - proof tuple to wrap
+ * @return - proof tuple of the wrapper under config OC
+ */
+pub fn wrap<OC: GenericConfig<D, F = F>>(prev: ProofTuple<F, C, D>) -> Result<ProofTuple<F, OC, D>> {
+    let mut builder = CircuitBuilder::<F, D>::new(CircuitConfigBuilder::new().build());
+
+    // TARGETS //
+    let prev_t = RecursiveTargets {
+        proof: builder.add_virtual_proof_with_pis(&prev.2),
+        verifier: builder.add_virtual_verifier_data(prev.2.config.fri_config.cap_height),
+    };
+
+    // SYNTHESIZE //
+    // verify previous proof under the inner config
+    builder.verify_proof::<C>(&prev_t.proof, &prev_t.verifier, &prev.2);
+
+    // PUBLIC INPUTS //
+    builder.register_public_inputs(&prev_t.proof.public_inputs);
+
+    // WITNESS //
+    let mut pw = PartialWitness::new();
+    pw.set_proof_with_pis_target(&prev_t.proof, &prev.0);
+    pw.set_verifier_data_target(&prev_t.verifier, &prev.1);
+
+    // PROVE //
+    // construct circuit data under the outer config
+    let data = builder.build::<OC>();
+    let mut timing = TimingTree::new("prove", Level::Debug);
+    let proof = prove(&data.prover_only, &data.common, pw, &mut timing)?;
+    timing.print();
+
+    // verify the proof was generated correctly
+    data.verify(proof.clone())?;
+
+    Ok((proof, data.verifier_only, data.common))
+}
+
+/**
+ * Retry a proving call on recoverable failures
+ * @notice zero knowledge proving draws random blinding, so a failed attempt may succeed when repeated
+ * @dev CircuitError failures are deterministic (malformed or mismatched inputs) and are returned immediately
+ *
+ * @param retries - number of additional attempts after the first failure
+ * @param prove - proving call to attempt
+ * @return - result of the first successful attempt, or the error of the last attempt
+ */
+pub fn prove_with_retries<T>(retries: usize, mut prove: impl FnMut() -> Result<T>) -> Result<T> {
+    let mut attempt = 0;
+    loop {
+        match prove() {
+            Ok(proof) => return Ok(proof),
+            Err(e) if e.downcast_ref::<CircuitError>().is_some() || attempt >= retries => {
+                return Err(e)
+            }
+            Err(e) => {
+                attempt += 1;
+                log::warn!("proving attempt {} failed, retrying: {}", attempt, e);
+            }
+        }
+    }
+}
+
+/**
+ * Run a proving call on a worker thread and give up once it exceeds a time budget
+ * @notice the worker cannot be cancelled: on timeout it is abandoned and its result discarded when it finishes
+ *
+ * @param timeout - time budget for the proving call
+ * @param prove - proving call to run
+ * @return - result of the proving call, or CircuitError::Timeout if it did not finish within the budget
+ */
+pub fn prove_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    prove: impl FnOnce() -> Result<T> + Send + 'static,
+) -> Result<T> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // receiver is dropped if the budget was exceeded, so a failed send is expected
+        let _ = sender.send(prove());
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(CircuitError::Timeout.into()),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(anyhow::anyhow!("proving thread panicked")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{circuits::game::board::BoardCircuit, utils::board::Board},
+        plonky2::field::types::{Field, PrimeField64},
+    };
+
+    #[test]
+    fn test_fold_counter() {
+        // base proof exporting a count of 0
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfigBuilder::new().build());
+        let count_t = builder.add_virtual_target();
+        builder.register_public_input(count_t);
+        let data = builder.build::<C>();
+        let mut pw = PartialWitness::new();
+        pw.set_target(count_t, F::ZERO);
+        let mut proof = (data.prove(pw).unwrap(), data.verifier_only, data.common);
+
+        // each fold increments the count carried by the previous proof
+        let steps = 3;
+        for _ in 0..steps {
+            proof = fold(proof, |builder, prev, _| {
+                let next_t = builder.add_const(prev.public_inputs[0], F::ONE);
+                builder.register_public_input(next_t);
+                Ok(())
+            })
+            .unwrap();
+        }
+        assert_eq!(proof.0.public_inputs[0].to_canonical_u64(), steps);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fold_step_constraint() {
+        // base proof exporting a count of 1
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfigBuilder::new().build());
+        let count_t = builder.add_virtual_target();
+        builder.register_public_input(count_t);
+        let data = builder.build::<C>();
+        let mut pw = PartialWitness::new();
+        pw.set_target(count_t, F::ONE);
+        let proof = (data.prove(pw).unwrap(), data.verifier_only, data.common);
+
+        // step witnesses a private count that must equal the previous count, but lies
+        fold(proof, |builder, prev, pw| {
+            let claimed_t = builder.add_virtual_target();
+            builder.connect(claimed_t, prev.public_inputs[0]);
+            pw.set_target(claimed_t, F::ZERO);
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_prove_with_retries() {
+        // injected failure on the first two attempts
+        let mut attempts = 0;
+        let proof = prove_with_retries(2, || {
+            attempts += 1;
+            match attempts {
+                1 | 2 => Err(anyhow::anyhow!("injected proving failure")),
+                _ => Ok(attempts),
+            }
+        })
+        .unwrap();
+        assert_eq!(proof, 3);
+
+        // gives up once the retries are exhausted
+        let mut attempts = 0;
+        let result = prove_with_retries(2, || -> Result<()> {
+            attempts += 1;
+            Err(anyhow::anyhow!("injected proving failure"))
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+
+        // deterministic circuit errors are not retried
+        let mut attempts = 0;
+        let result = prove_with_retries(2, || -> Result<()> {
+            attempts += 1;
+            Err(CircuitError::CommitmentMismatch.into())
+        });
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_prove_with_timeout() {
+        let board = Board::standard_host();
+
+        // unrealistically short budget times out
+        let inner = board.clone();
+        let err = prove_with_timeout(Duration::from_nanos(1), move || BoardCircuit::prove_inner(inner))
+            .err()
+            .unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::Timeout));
+
+        // generous budget returns the proof
+        let inner = board.clone();
+        let proof = prove_with_timeout(Duration::from_secs(600), move || BoardCircuit::prove_inner(inner))
+            .unwrap();
+        let commitment = BoardCircuit::decode_public(proof.0).unwrap().commitment;
+        assert_eq!(commitment, board.hash());
+    }
+}
@@ -0,0 +1,158 @@
+use {
+    super::{D, F},
+    anyhow::{ensure, Result},
+    plonky2::{
+        gates::{gate::GateRef, noop::NoopGate},
+        plonk::{circuit_builder::CircuitBuilder, circuit_data::CommonCircuitData},
+    },
+};
+
+// Common circuit shape: circuits padded to the same shape share CommonCircuitData
+// @dev a single recursive verifier built from the shared common data verifies a proof of any padded circuit
+//      (only the verifier only data is swapped in as witness), at the cost of proving the padding
+
+pub struct CircuitShape {
+    pub degree_bits: usize,        // log2 of the number of gate rows
+    pub num_public_inputs: usize,  // public inputs (unused public inputs are zero)
+    pub gates: Vec<GateRef<F, D>>, // gate set (every gate type any padded circuit uses)
+}
+
+impl CircuitShape {
+    /**
+     * Compute the smallest shape that fits every given circuit
+     * @notice circuits must be built with the same circuit config
+     *
+     * @param commons - common circuit data of each (unpadded) circuit
+     * @return - shape covering all circuits
+     */
+    pub fn covering(commons: &[&CommonCircuitData<F, D>]) -> CircuitShape {
+        // union of gate sets
+        let mut gates = Vec::<GateRef<F, D>>::new();
+        for common in commons {
+            for gate in common.gates.iter() {
+                if !gates.contains(gate) {
+                    gates.push(gate.clone());
+                }
+            }
+        }
+        CircuitShape {
+            degree_bits: commons.iter().map(|c| c.degree_bits()).max().unwrap_or(0),
+            num_public_inputs: commons.iter().map(|c| c.num_public_inputs).max().unwrap_or(0),
+            gates,
+        }
+    }
+
+    /**
+     * Pad a circuit to the shape
+     * @notice must be called once the circuit is fully laid out, immediately before building
+     *
+     * @param builder - circuit builder
+     * @return - error if the shape is empty (no degree) or the circuit exports more public inputs than it allows
+     */
+    pub fn pad(&self, builder: &mut CircuitBuilder<F, D>) -> Result<()> {
+        // a shape covering no circuits has no degree to pad to
+        ensure!(self.degree_bits > 0, "shape has degree 0, covering was given no circuits");
+        ensure!(
+            builder.num_public_inputs() <= self.num_public_inputs,
+            "circuit exports {} public inputs, shape allows {}",
+            builder.num_public_inputs(),
+            self.num_public_inputs
+        );
+
+        // pad public inputs with zeros
+        let zero = builder.zero();
+        while builder.num_public_inputs() < self.num_public_inputs {
+            builder.register_public_input(zero);
+        }
+
+        // register every gate of the shape so selectors/ constants match
+        for gate in self.gates.iter() {
+            builder.add_gate_to_gate_set(gate.clone());
+        }
+
+        // pad rows so the circuit rounds up to the shape degree when built
+        while builder.num_gates() < 1 << (self.degree_bits - 1) {
+            builder.add_gate(NoopGate, vec![]);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuits::{
+            game::{board::BoardCircuit, shot::ShotCircuit},
+            ProofTuple, C,
+        },
+        utils::board::Board,
+    };
+    use plonky2::{
+        field::types::Field,
+        iop::witness::{PartialWitness, WitnessWrite},
+        plonk::circuit_data::CircuitConfig,
+    };
+
+    #[test]
+    fn test_pad_empty_shape() {
+        let shape = CircuitShape::covering(&[]);
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+        assert!(shape.pad(&mut builder).is_err());
+    }
+
+    #[test]
+    fn test_padded_board_and_shot_share_verifier() {
+        // shape covering the board and shot inner circuits
+        let config = BoardCircuit::config_inner().unwrap();
+        let board_circuit = BoardCircuit::build(&config).unwrap();
+        let shot_circuit = ShotCircuit::build(&config).unwrap();
+        assert_ne!(board_circuit.data.common, shot_circuit.data.common);
+        let shape =
+            CircuitShape::covering(&[&board_circuit.data.common, &shot_circuit.data.common]);
+
+        // padded circuits share common circuit data
+        let board_circuit = BoardCircuit::build_padded(&config, &shape).unwrap();
+        let shot_circuit = ShotCircuit::build_padded(&config, &shape).unwrap();
+        assert_eq!(board_circuit.data.common, shot_circuit.data.common);
+
+        // prove a board and a shot
+        let board = Board::standard_host();
+        let mut pw =
+            BoardCircuit::partial_witness_inner(board_circuit.ships, board.clone()).unwrap();
+        pw.set_target(board_circuit.nonce, F::ZERO);
+        let board_p: ProofTuple<F, C, D> = (
+            board_circuit.data.prove(pw).unwrap(),
+            board_circuit.data.verifier_only,
+            board_circuit.data.common,
+        );
+        let pw = ShotCircuit::partial_witness_inner(
+            [0, 0],
+            board,
+            shot_circuit.shot_t,
+            shot_circuit.board_t,
+        )
+        .unwrap();
+        let shot_p: ProofTuple<F, C, D> = (
+            shot_circuit.data.prove(pw).unwrap(),
+            shot_circuit.data.verifier_only,
+            shot_circuit.data.common,
+        );
+
+        // build a single recursive verifier from the shared common data
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+        let pt = builder.add_virtual_proof_with_pis(&board_p.2);
+        let inner_data = builder.add_virtual_verifier_data(board_p.2.config.fri_config.cap_height);
+        builder.verify_proof::<C>(&pt, &inner_data, &board_p.2);
+        let verifier = builder.build::<C>();
+
+        // both proofs verify under the one verifier
+        for (proof, verifier_only, _) in [board_p, shot_p] {
+            let mut pw = PartialWitness::new();
+            pw.set_proof_with_pis_target(&pt, &proof);
+            pw.set_verifier_data_target(&inner_data, &verifier_only);
+            let outer = verifier.prove(pw).unwrap();
+            verifier.verify(outer).unwrap();
+        }
+    }
+}
@@ -1,10 +1,19 @@
 use {
-    super::range::less_than_10,
-    crate::circuits::{D, F},
+    super::{
+        range::less_than_10,
+        shot::{assert_distinct, serialize_shot},
+    },
+    crate::{
+        circuits::{D, F, H},
+        utils::{board::Board, merkle_board::CELL_TREE_DEPTH, ship::Ship},
+    },
     plonky2::{
         field::types::Field,
-        hash::{hash_types::HashOutTarget, poseidon::PoseidonHash},
-        iop::target::{BoolTarget, Target},
+        hash::{hash_types::HashOutTarget, merkle_proofs::MerkleProofTarget},
+        iop::{
+            target::{BoolTarget, Target},
+            witness::{PartialWitness, WitnessWrite},
+        },
         plonk::circuit_builder::CircuitBuilder,
     },
     anyhow::Result
@@ -55,17 +64,99 @@ pub fn recompose_board(
     ])
 }
 
+/**
+ * Constrain a serialized board to its canonical representation (no bits set past the 100th cell)
+ * @notice bits 100..128 live in the high 28 bits of the final u32 limb
+ * @dev copy constraint will fail if board[3] >= 2^4; lower limbs are range checked wherever the board is decomposed
+ *
+ * @param board - u128 target representing private board state in LE
+ * @param builder - circuit builder
+ */
+pub fn assert_canonical_board(board: [Target; 4], builder: &mut CircuitBuilder<F, D>) -> Result<()> {
+    builder.range_check(board[3], 100 - 96);
+    Ok(())
+}
+
 /**
  * Given the canonical representation of board state, return the hash of the board state
- * @todo: add private salt to hash
+ * @notice rejects non-canonical boards so one placement cannot be committed to under several hashes
  *
  * @param board - u128 target representing private board state in LE
  * @param builder - circuit builder
  * @return - target of constrained computation of board hash
  */
 pub fn hash_board(board: [Target; 4], builder: &mut CircuitBuilder<F, D>) -> Result<HashOutTarget> {
-    let hash = builder.hash_n_to_hash_no_pad::<PoseidonHash>(board.try_into().unwrap());
-    Ok(hash)
+    hash_board_salted(board, &[], builder)
+}
+
+/**
+ * Hash a canonical board with salt limbs appended to the preimage
+ * @notice circuit counterpart of Board::hash_subset; an empty salt yields the hash_board commitment
+ *
+ * @param board - u128 target representing private board state in LE
+ * @param salt - salt targets appended after the board limbs
+ * @param builder - circuit builder
+ * @return - target of constrained computation of the salted board hash
+ */
+pub fn hash_board_salted(
+    board: [Target; 4],
+    salt: &[Target],
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<HashOutTarget> {
+    assert_canonical_board(board, builder)?;
+    let mut preimage = board.to_vec();
+    preimage.extend_from_slice(salt);
+    Ok(builder.hash_n_to_hash_no_pad::<H>(preimage))
+}
+
+/**
+ * Constrain a board to hash to a commitment already present in a larger circuit
+ * @notice lets composite circuits reuse a commitment target (e.g. one read from a verified proof) instead of
+ *         registering the board hash as new public inputs
+ *
+ * @param board - u128 target representing private board state in LE
+ * @param expected - commitment the board must open
+ * @param builder - circuit builder
+ * @return - success if the copy constraints on the commitment are added
+ */
+pub fn constrain_commitment_matches(
+    board: [Target; 4],
+    expected: HashOutTarget,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<()> {
+    let commitment = hash_board(board, builder)?;
+    builder.connect_hashes(commitment, expected);
+    Ok(())
+}
+
+/**
+ * Constrain the opening of a single cell of a cell-committed board (see utils::merkle_board::CellCommittedBoard)
+ * @notice reveals one cell's occupied bit without revealing the rest of the board
+ *
+ * @param root - Merkle root of the cell commitments
+ * @param index - serialized cell index (10y + x)
+ * @param builder - circuit builder
+ * @return - (occupied bit, cell salt, Merkle proof targets) to witness with the opening
+ */
+pub fn open_cell(
+    root: HashOutTarget,
+    index: Target,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<(BoolTarget, [Target; 4], MerkleProofTarget)> {
+    // opened cell and its salt
+    let occupied = builder.add_virtual_bool_target_safe();
+    let salt = builder.add_virtual_target_arr::<4>();
+    // sibling commitments from the leaf up to the root
+    let proof = MerkleProofTarget {
+        siblings: builder.add_virtual_hashes(CELL_TREE_DEPTH),
+    };
+    // recompute the cell commitment and open the root at the cell index
+    let mut inputs = vec![occupied.target];
+    inputs.extend_from_slice(&salt);
+    let leaf = builder.hash_n_to_hash_no_pad::<H>(inputs);
+    let index_bits = builder.split_le(index, CELL_TREE_DEPTH);
+    builder.verify_merkle_proof::<H>(leaf.elements.to_vec(), &index_bits, root, &proof);
+    Ok((occupied, salt, proof))
 }
 
 /**
@@ -103,6 +194,31 @@ pub fn generate_coordiante(
     Ok(builder.add(x_t, y_serialized_t))
 }
 
+/**
+ * Constrain a ship head to leave room for a ship of length L in its orientation
+ * @notice horizontal (z = false) requires x + L <= 10, vertical (z = true) requires y + L <= 10
+ * @dev the per-offset range checks in generate_coordiante imply this, but fail on whichever offset leaves the board
+ *
+ * @param x - x coordinate of ship head
+ * @param y - y coordinate of ship head
+ * @param z - orientation of ship head
+ * @param builder - circuit builder
+ * @return - copy constraint fails if the ship does not fit on the board from its head
+ */
+pub fn assert_head_fits<const L: usize>(
+    x: Target,
+    y: Target,
+    z: BoolTarget,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<()> {
+    // select the coordinate the ship extends along
+    let head_t = builder.select(z, y, x);
+    // last cell occupied by the ship must be on the board (head + L - 1 < 10)
+    let tail_offset_t = builder.constant(F::from_canonical_usize(L - 1));
+    let tail_t = builder.add(head_t, tail_offset_t);
+    less_than_10(tail_t, builder)
+}
+
 /**
  * Given a ship as (x, y, z) with a constant ship length, compute the occupied coordinates
  *
@@ -118,6 +234,8 @@ pub fn ship_to_coordinates<const L: usize>(
     // range check ship head
     less_than_10(x, builder)?;
     less_than_10(y, builder)?;
+    // check ship fits on the board from its head
+    assert_head_fits::<L>(x, y, z, builder)?;
     // build ship placement coordinate array
     let coordinates = builder.add_virtual_target_arr::<L>();
     for i in 0..L {
@@ -128,6 +246,48 @@ pub fn ship_to_coordinates<const L: usize>(
     Ok(coordinates)
 }
 
+/**
+ * Constrain the cells just past each end of a ship of length L to be empty or off the board
+ * @notice without this a claim over part of a longer ship (or ships laid end to end) would be accepted
+ * @dev off-board ends are looked up at cell 0 and masked, since head - 1/ tail + 1 wrap rows or leave the board
+ *
+ * @param ship - ship instantiation coordinates (range checked by ship_to_coordinates)
+ * @param bits - decomposed board bits
+ * @param builder - circuit builder
+ * @return - copy constraint fails if a cell adjacent to either end is occupied
+ */
+pub fn assert_ship_ends_empty<const L: usize>(
+    ship: (Target, Target, BoolTarget),
+    bits: Vec<Target>,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<()> {
+    let (x, y, z) = ship;
+    let zero_t = builder.zero();
+    // step between consecutive cells: 1 along a row (horizontal), 10 along a column (vertical)
+    let one_t = builder.one();
+    let ten_t = builder.constant(F::from_canonical_u8(10));
+    let step_t = builder.select(z, ten_t, one_t);
+    let head_t = builder.mul_add(y, ten_t, x);
+    let along_t = builder.select(z, y, x);
+    // cell before the head is off the board if the head is on the first row/ column
+    let first_t = builder.is_equal(along_t, zero_t);
+    let before_t = builder.sub(head_t, step_t);
+    let before_t = builder.select(first_t, zero_t, before_t);
+    let before_occupied_t = builder.random_access(before_t, bits.clone());
+    let before_occupied_t = builder.select(first_t, zero_t, before_occupied_t);
+    builder.connect(before_occupied_t, zero_t);
+    // cell after the tail is off the board if the tail is on the last row/ column
+    let end_t = builder.constant(F::from_canonical_usize(10 - L));
+    let last_t = builder.is_equal(along_t, end_t);
+    let length_t = builder.constant(F::from_canonical_usize(L));
+    let after_t = builder.mul_add(step_t, length_t, head_t);
+    let after_t = builder.select(last_t, zero_t, after_t);
+    let after_occupied_t = builder.random_access(after_t, bits);
+    let after_occupied_t = builder.select(last_t, zero_t, after_occupied_t);
+    builder.connect(after_occupied_t, zero_t);
+    Ok(())
+}
+
 /**
  * Constructs an equation where the output will only be 1 if the input is one of the values in coordinates
  *
@@ -158,6 +318,80 @@ pub fn interpolate_bitflip_bool<const L: usize>(
     Ok(builder.is_equal(exp_t, zero_t))
 }
 
+/**
+ * Ship placement targets (x, y, z) tagged with the length L of the ship they place
+ * @dev the placement length is read from the type, so a ship can only be placed and witnessed as the length
+ *      it was declared with (a Ship<4> cannot witness a TypedShipTarget<5>, or be placed as 3 coordinates)
+ *
+ * ```compile_fail
+ * use battlezips_plonky2::{gadgets::board::TypedShipTarget, utils::ship::Ship};
+ * use plonky2::iop::witness::PartialWitness;
+ * fn witness(carrier: &TypedShipTarget<5>, battleship: &Ship<4>) {
+ *     carrier.witness(&mut PartialWitness::new(), battleship);
+ * }
+ * ```
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct TypedShipTarget<const L: usize> {
+    pub x: Target,
+    pub y: Target,
+    pub z: BoolTarget,
+}
+
+impl<const L: usize> TypedShipTarget<L> {
+    /**
+     * Add virtual targets for a ship of length L
+     *
+     * @param builder - circuit builder
+     * @return - unwitnessed ship targets
+     */
+    pub fn new(builder: &mut CircuitBuilder<F, D>) -> Self {
+        Self {
+            x: builder.add_virtual_target(),
+            y: builder.add_virtual_target(),
+            z: builder.add_virtual_bool_target_safe(),
+        }
+    }
+
+    /**
+     * Return the untyped (x, y, z) targets of the ship
+     *
+     * @return - ship targets as a tuple
+     */
+    pub fn tuple(&self) -> (Target, Target, BoolTarget) {
+        (self.x, self.y, self.z)
+    }
+
+    /**
+     * Witness a ship of the same length into the targets
+     *
+     * @param pw - partial witness to write ship into
+     * @param ship - ship of length L
+     */
+    pub fn witness(&self, pw: &mut PartialWitness<F>, ship: &Ship<L>) {
+        let (x, y, z) = ship.canonical();
+        pw.set_target(self.x, F::from_canonical_u8(x));
+        pw.set_target(self.y, F::from_canonical_u8(y));
+        pw.set_bool_target(self.z, z);
+    }
+}
+
+/**
+ * Constrain the placement of a typed ship on the board, placing as many coordinates as the ship's length
+ *
+ * @param ship - ship targets tagged with the ship length
+ * @param board - board state as a 100 bit vector
+ * @param builder - circuit builder
+ * @return - new board state as 100 bit vector with ship coordinates bitflipped
+ */
+pub fn place_typed_ship<const L: usize>(
+    ship: TypedShipTarget<L>,
+    board: Vec<Target>,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<Vec<Target>> {
+    place_ship::<L>(ship.tuple(), board, builder)
+}
+
 /**
  * Given a ship and board, constrain the placement of the ship
  * @dev prevent overlapping ships
@@ -182,6 +416,51 @@ pub fn place_ship<const L: usize>(
     // @notice: range checks placement
     let ship_coordinates = ship_to_coordinates::<L>(ship, builder)?;
 
+    // place the ship's coordinates on the board
+    place_coordinates::<L>(ship_coordinates, board, builder)
+}
+
+/**
+ * Place a piece given as an explicit list of (x, y) cells, for rulesets where pieces are not axis-aligned lines
+ * @notice only constrains the cells to be on the board, pairwise distinct and empty; the shape of the piece (e.g.
+ *         connectedness) is left to the caller
+ *
+ * @param cells - (x, y) targets of every cell occupied by the piece
+ * @param board - board state as a 100 bit vector
+ * @param builder - circuit builder
+ * @return - new board state as 100 bit vector with the piece's cells bitflipped
+ */
+pub fn place_cells<const L: usize>(
+    cells: [[Target; 2]; L],
+    board: Vec<Target>,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<Vec<Target>> {
+    // serialize (and range check) each cell
+    let mut coordinates = [builder.zero(); L];
+    for i in 0..L {
+        coordinates[i] = serialize_shot(cells[i][0], cells[i][1], builder)?;
+    }
+    // a repeated cell would be placed once but counted twice
+    assert_distinct(&coordinates, builder)?;
+
+    // place the cells on the board
+    place_coordinates::<L>(coordinates, board, builder)
+}
+
+/**
+ * Flip a set of empty serialized coordinates on the board
+ * @dev coordinates must already be range checked (see ship_to_coordinates, place_cells)
+ *
+ * @param ship_coordinates - serialized coordinates (10y + x) to occupy
+ * @param board - board state as a 100 bit vector
+ * @param builder - circuit builder
+ * @return - new board state as 100 bit vector with the coordinates bitflipped
+ */
+fn place_coordinates<const L: usize>(
+    ship_coordinates: [Target; L],
+    board: Vec<Target>,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<Vec<Target>> {
     // check that coordinates occupied by new ship are available
     let zero_t = builder.constant(F::ZERO);
     for i in 0..L {
@@ -217,3 +496,554 @@ pub fn place_ship<const L: usize>(
     Ok(board_out)
 }
 
+/**
+ * Constrain two interchangeable ships (same length) to be provided in canonical order
+ * @notice canonical order = strictly ascending serialized head coordinate (10y + x)
+ * @dev ships of equal length can swap slots without changing the board, so ordering maps a board to one ship input vector
+ * @dev copy constraint will fail if ships are not in canonical order
+ *
+ * @param first - ship expected to have the lower head coordinate
+ * @param second - ship expected to have the higher head coordinate
+ * @param builder - circuit builder
+ */
+pub fn constrain_ship_order(
+    first: (Target, Target, BoolTarget),
+    second: (Target, Target, BoolTarget),
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<()> {
+    // serialize ship heads into board indexes
+    let ten_t = builder.constant(F::from_canonical_u8(10));
+    let first_t = builder.mul_add(first.1, ten_t, first.0);
+    let second_t = builder.mul_add(second.1, ten_t, second.0);
+    // offset difference so it is always positive for heads in range (second - first + 127 in [28, 226])
+    let offset_t = builder.constant(F::from_canonical_u8(127));
+    let difference_t = builder.sub(second_t, first_t);
+    let shifted_t = builder.add(difference_t, offset_t);
+    // high bit is only set if second - first >= 1
+    let bits = builder.split_le(shifted_t, 8);
+    let one_t = builder.constant(F::ONE);
+    builder.connect(bits[7].target, one_t);
+    Ok(())
+}
+
+// witnessed placement of a fleet ship: ship targets (x, y, z) and the serialized coordinates (10y + x) it covers
+pub type FleetShipTarget = ((Target, Target, BoolTarget), Vec<Target>);
+
+// ship lengths of the standard battleship fleet (carrier, battleship, cruiser, submarine, destroyer)
+pub const STANDARD_FLEET: [usize; 5] = [5, 4, 3, 3, 2];
+
+/**
+ * Constrain the occupied cells of a board bitmap to be exactly the cells of a fleet placed by witnessed ships
+ * @notice each ship of the fleet is witnessed as (x, y, z) with the length given by the fleet, so the bitmap is only
+ *         accepted if a placement of the fleet covers exactly its occupied cells
+ * @dev ships may touch; only ships overlapping (a cell claimed twice) and cells or ships off the board are rejected
+ * @dev copy constraint will fail if the board does not match the fleet
+ *
+ * @param board - board state as a 128 bit vector (first 100 bits used, index = 10y + x)
+ * @param fleet - length of each ship in the fleet
+ * @param builder - circuit builder
+ * @return - targets to witness each ship of the fleet with and the coordinates each ship covers
+ */
+pub fn assert_fleet_shape(
+    board: Vec<Target>,
+    fleet: &[usize],
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<Vec<FleetShipTarget>> {
+    let zero_t = builder.zero();
+    let one_t = builder.one();
+
+    // place each ship of the fleet over occupied cells
+    let mut ships = Vec::<FleetShipTarget>::new();
+    let mut coordinates = Vec::<Target>::new();
+    for length in fleet {
+        let ship = (
+            builder.add_virtual_target(),
+            builder.add_virtual_target(),
+            builder.add_virtual_bool_target_safe(),
+        );
+        // range check ship head; each coordinate range checks the cell it extends to
+        less_than_10(ship.0, builder)?;
+        less_than_10(ship.1, builder)?;
+        let mut cells = Vec::<Target>::new();
+        for offset in 0..*length {
+            let cell = generate_coordiante(ship.0, ship.1, ship.2, offset, builder)?;
+            let occupied = builder.random_access(cell, board.clone());
+            builder.connect(occupied, one_t);
+            cells.push(cell);
+        }
+        coordinates.extend_from_slice(&cells);
+        ships.push((ship, cells));
+    }
+
+    // no cell is claimed by two ships
+    assert_distinct(&coordinates, builder)?;
+
+    // no cell is occupied outside the fleet: as many occupied cells as fleet tonnage and none past the grid
+    let occupied_t = builder.add_many(&board[0..100]);
+    let tonnage_t = builder.constant(F::from_canonical_usize(coordinates.len()));
+    builder.connect(occupied_t, tonnage_t);
+    for bit in board[100..].iter() {
+        builder.connect(*bit, zero_t);
+    }
+    Ok(ships)
+}
+
+/**
+ * Witness the ships of the standard fleet from the placements of a board
+ *
+ * @param pw - partial witness to write the placements to
+ * @param ships - fleet ship targets returned for STANDARD_FLEET (carrier, battleship, cruiser, submarine, destroyer)
+ * @param board - board whose placements are witnessed
+ */
+pub fn witness_standard_fleet(pw: &mut PartialWitness<F>, ships: &[FleetShipTarget], board: &Board) {
+    let placements = [
+        board.carrier.canonical(),
+        board.battleship.canonical(),
+        board.cruiser.canonical(),
+        board.submarine.canonical(),
+        board.destroyer.canonical(),
+    ];
+    for (((x_t, y_t, z_t), _), (x, y, z)) in ships.iter().zip(placements) {
+        pw.set_target(*x_t, F::from_canonical_u8(x));
+        pw.set_target(*y_t, F::from_canonical_u8(y));
+        pw.set_bool_target(*z_t, z);
+    }
+}
+
+/**
+ * Count the ships of a fleet-shaped board whose every cell has been hit
+ * @notice constrains the board to the fleet (see assert_fleet_shape) so each witnessed ship is exactly one ship
+ * @dev touching ships can leave a bitmap with more than one placement of the fleet; ships are counted as witnessed
+ *
+ * @param board - board state as a 128 bit vector (first 100 bits used, index = 10y + x)
+ * @param hits - coordinates that have been shot as a 128 bit vector (first 100 bits used, index = 10y + x)
+ * @param fleet - length of each ship in the fleet
+ * @param builder - circuit builder
+ * @return - number of fully sunk ships and the fleet ship targets to witness
+ */
+pub fn count_sunk_ships(
+    board: Vec<Target>,
+    hits: Vec<Target>,
+    fleet: &[usize],
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<(Target, Vec<FleetShipTarget>)> {
+    // locate every ship on the board
+    let ships = assert_fleet_shape(board, fleet, builder)?;
+
+    // a ship is sunk if every cell it covers was shot
+    let mut sunk_t = builder.zero();
+    for (_, cells) in ships.iter() {
+        let mut hit_t = builder.one();
+        for cell in cells {
+            let shot_t = builder.random_access(*cell, hits.clone());
+            hit_t = builder.mul(hit_t, shot_t);
+        }
+        sunk_t = builder.add(sunk_t, hit_t);
+    }
+    Ok((sunk_t, ships))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            circuits::{CircuitConfigBuilder, C},
+            gadgets::shot::check_hit,
+            utils::{board::Board, merkle_board::CellCommittedBoard},
+        },
+        plonky2::{
+            field::types::PrimeField64,
+            plonk::{circuit_data::CircuitConfig, config::Hasher},
+        },
+    };
+
+    /**
+     * Prove the fleet shape of a board given as 100 LE bits
+     *
+     * @param bits - board bitmap
+     * @param placements - board whose ship placements are witnessed
+     * @return - error if proving fails
+     */
+    fn prove_fleet_shape(bits: [bool; 100], placements: &Board) -> Result<()> {
+        let config = CircuitConfigBuilder::new().with_random_access().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let board_t = builder.add_virtual_target_arr::<4>();
+        let board_bits = decompose_board(board_t, &mut builder)?;
+        let ships = assert_fleet_shape(board_bits, &STANDARD_FLEET, &mut builder)?;
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        witness_standard_fleet(&mut pw, &ships, placements);
+        let canonical = Board::pack_bits(&bits);
+        for i in 0..board_t.len() {
+            pw.set_target(board_t[i], F::from_canonical_u32(canonical[i]));
+        }
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_hash_board_matches_native() {
+        // commit to the board in circuit
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let board_t = builder.add_virtual_target_arr::<4>();
+        let commitment_t = hash_board(board_t, &mut builder).unwrap();
+        builder.register_public_inputs(&commitment_t.elements);
+        let data = builder.build::<C>();
+
+        // witness the canonical board
+        let board = Board::standard_host();
+        let canonical = board
+            .canonical()
+            .iter()
+            .map(|limb| F::from_canonical_u32(*limb))
+            .collect::<Vec<F>>();
+        let mut pw = PartialWitness::new();
+        for i in 0..board_t.len() {
+            pw.set_target(board_t[i], canonical[i]);
+        }
+        let proof = data.prove(pw).unwrap();
+
+        // gadget commitment equals the config hasher computed natively
+        let native = H::hash_no_pad(&canonical);
+        assert_eq!(proof.public_inputs, native.elements.to_vec());
+        assert_eq!(
+            proof.public_inputs.iter().map(|x| x.to_canonical_u64()).collect::<Vec<u64>>(),
+            board.hash().to_vec()
+        );
+    }
+
+    /**
+     * Prove a commitment to a board given as raw u32 limbs
+     *
+     * @param limbs - serialized board limbs (LE)
+     * @return - error if proving fails
+     */
+    fn prove_hash_limbs(limbs: [u32; 4]) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let board_t = builder.add_virtual_target_arr::<4>();
+        hash_board(board_t, &mut builder)?;
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for i in 0..board_t.len() {
+            pw.set_target(board_t[i], F::from_canonical_u32(limbs[i]));
+        }
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_hash_subset_matches_native() {
+        // commit to a subset of the fleet with salt in circuit
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let board_t = builder.add_virtual_target_arr::<4>();
+        let salt_t = builder.add_virtual_target_arr::<4>();
+        let commitment_t = hash_board_salted(board_t, &salt_t, &mut builder).unwrap();
+        builder.register_public_inputs(&commitment_t.elements);
+        let data = builder.build::<C>();
+
+        // witness the carrier and destroyer only
+        let board = Board::standard_guest();
+        let salt = [3u64, 1, 4, 1];
+        let subset = Board::pack_bits(&board.subset_bits(&[0, 4]).unwrap());
+        let mut pw = PartialWitness::new();
+        pw.set_target_arr(board_t, subset.map(F::from_canonical_u32));
+        pw.set_target_arr(salt_t, salt.map(F::from_canonical_u64));
+        let proof = data.prove(pw).unwrap();
+
+        assert_eq!(
+            proof.public_inputs.iter().map(|x| x.to_canonical_u64()).collect::<Vec<u64>>(),
+            board.hash_subset(&[0, 4], Some(salt)).unwrap().to_vec()
+        );
+    }
+
+    /**
+     * Prove a shot lookup against a board opened to a commitment supplied as a hash target
+     *
+     * @param board - board opened in circuit
+     * @param commitment - board whose commitment is witnessed as the expected hash
+     * @return - error if proving fails
+     */
+    fn prove_opened_lookup(board: Board, commitment: Board) -> Result<()> {
+        let config = CircuitConfigBuilder::new().with_random_access().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let board_t = builder.add_virtual_target_arr::<4>();
+        let expected_t = builder.add_virtual_hash();
+        constrain_commitment_matches(board_t, expected_t, &mut builder)?;
+        // compose with a lookup on the opened board
+        let shot_t = builder.constant(F::ZERO);
+        let hit_t = check_hit(board_t, shot_t, &mut builder)?;
+        builder.register_public_input(hit_t);
+        let data = builder.build::<C>();
+
+        let expected = H::hash_no_pad(&commitment.canonical().map(F::from_canonical_u32));
+        let mut pw = PartialWitness::new();
+        pw.set_target_arr(board_t, board.canonical().map(F::from_canonical_u32));
+        pw.set_hash_target(expected_t, expected);
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_commitment_matches() {
+        prove_opened_lookup(Board::standard_host(), Board::standard_host()).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commitment_mismatch() {
+        prove_opened_lookup(Board::standard_host(), Board::standard_guest()).unwrap();
+    }
+
+    #[test]
+    fn test_place_cells_l_shape() {
+        // L-shaped piece covering (0, 0), (1, 0) and (0, 1) on an otherwise blank board
+        let config = CircuitConfigBuilder::new().with_random_access().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let cells_t = [(); 3].map(|_| builder.add_virtual_target_arr::<2>());
+        let blank: [Target; 4] = builder.constants(&[F::ZERO; 4]).try_into().unwrap();
+        let board_bits = decompose_board(blank, &mut builder).unwrap();
+        let placed = place_cells::<3>(cells_t, board_bits, &mut builder).unwrap();
+        let board_t = recompose_board(placed, &mut builder).unwrap();
+        let commitment_t = hash_board(board_t, &mut builder).unwrap();
+        builder.register_public_inputs(&commitment_t.elements);
+        let data = builder.build::<C>();
+
+        let cells = [[0u8, 0], [1, 0], [0, 1]];
+        let mut pw = PartialWitness::new();
+        for (cell_t, cell) in cells_t.iter().zip(cells) {
+            pw.set_target_arr(*cell_t, cell.map(F::from_canonical_u8));
+        }
+        let proof = data.prove(pw).unwrap();
+
+        // commitment is the hash of a board with exactly the three cells set
+        let mut bits = [false; 100];
+        for [x, y] in cells {
+            bits[(y * 10 + x) as usize] = true;
+        }
+        let expected = H::hash_no_pad(&Board::pack_bits(&bits).map(F::from_canonical_u32));
+        assert_eq!(proof.public_inputs, expected.elements.to_vec());
+    }
+
+    #[test]
+    fn test_canonical_board() {
+        // bit 99 is the last cell on the board
+        let mut bits = [false; 100];
+        bits[99] = true;
+        prove_hash_limbs(Board::pack_bits(&bits)).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_non_canonical_board() {
+        // bit 100 is past the last cell, in the high bits of the final limb
+        let mut limbs = Board::standard_host().canonical();
+        limbs[3] |= 1 << (100 - 96);
+        prove_hash_limbs(limbs).unwrap();
+    }
+
+    /**
+     * Prove that a carrier fits on the board from a head coordinate
+     *
+     * @param x - x coordinate of ship head
+     * @param y - y coordinate of ship head
+     * @param z - orientation of ship head
+     * @return - error if proving fails
+     */
+    fn prove_carrier_head(x: u8, y: u8, z: bool) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let x_t = builder.add_virtual_target();
+        let y_t = builder.add_virtual_target();
+        let z_t = builder.add_virtual_bool_target_safe();
+        assert_head_fits::<5>(x_t, y_t, z_t, &mut builder)?;
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(x_t, F::from_canonical_u8(x));
+        pw.set_target(y_t, F::from_canonical_u8(y));
+        pw.set_bool_target(z_t, z);
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_head_fits() {
+        // horizontal carrier at x = 5 occupies x = 5..=9
+        prove_carrier_head(5, 9, false).unwrap();
+        // vertical carrier only checks y, so x = 6 fits
+        prove_carrier_head(6, 5, true).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_head_fits_overflow() {
+        // horizontal carrier at x = 6 would need x = 6..=10
+        prove_carrier_head(6, 0, false).unwrap();
+    }
+
+    #[test]
+    fn test_place_typed_ship_length() {
+        // place a typed cruiser on a blank board and export the number of occupied cells
+        let config = CircuitConfigBuilder::new().with_random_access().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let cruiser_t = TypedShipTarget::<3>::new(&mut builder);
+        let blank_t: [Target; 4] = builder.constants(&[F::ZERO; 4]).try_into().unwrap();
+        let board_t = decompose_board(blank_t, &mut builder).unwrap();
+        let placed_t = place_typed_ship(cruiser_t, board_t, &mut builder).unwrap();
+        let occupied_t = builder.add_many(&placed_t[0..100]);
+        builder.register_public_input(occupied_t);
+        let data = builder.build::<C>();
+
+        // witness a ship of the same length (the only length the target accepts)
+        let mut pw = PartialWitness::new();
+        cruiser_t.witness(&mut pw, &Ship::<3>::new(2, 4, true));
+        let proof = data.prove(pw).unwrap();
+        assert_eq!(proof.public_inputs[0].to_canonical_u64(), 3);
+    }
+
+    /**
+     * Prove an opening of a cell of the standard host board committed cell by cell
+     *
+     * @param index - serialized cell index (10y + x)
+     * @param occupied - occupied bit claimed by the opening
+     * @return - error if proving fails
+     */
+    fn prove_cell_opening(index: usize, occupied: bool) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let root_t = builder.add_virtual_hash();
+        let index_t = builder.add_virtual_target();
+        let (occupied_t, salt_t, proof_t) = open_cell(root_t, index_t, &mut builder)?;
+        let data = builder.build::<C>();
+
+        let committed = CellCommittedBoard::new(Board::standard_host().bits(), [1, 2, 3, 4]);
+        let opening = committed.open(index)?;
+        let mut pw = PartialWitness::new();
+        pw.set_target_arr(root_t.elements, committed.root().map(F::from_canonical_u64));
+        pw.set_target(index_t, F::from_canonical_usize(index));
+        pw.set_bool_target(occupied_t, occupied);
+        pw.set_target_arr(salt_t, opening.salt.map(F::from_canonical_u64));
+        for (sibling_t, sibling) in proof_t.siblings.iter().zip(opening.proof.siblings) {
+            pw.set_hash_target(*sibling_t, sibling);
+        }
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_open_cell() {
+        // carrier head (3, 4) is occupied, (5, 5) is empty
+        prove_cell_opening(43, true).unwrap();
+        prove_cell_opening(55, false).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_open_cell_tampered() {
+        // claim the occupied carrier head is empty
+        prove_cell_opening(43, false).unwrap();
+    }
+
+    #[test]
+    fn test_orientation_matches_native() {
+        // place a carrier on a blank board and export the 100 placed bits
+        let config = CircuitConfigBuilder::new().with_random_access().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let carrier_t = TypedShipTarget::<5>::new(&mut builder);
+        let blank_t: [Target; 4] = builder.constants(&[F::ZERO; 4]).try_into().unwrap();
+        let board_t = decompose_board(blank_t, &mut builder).unwrap();
+        let placed_t = place_typed_ship(carrier_t, board_t, &mut builder).unwrap();
+        builder.register_public_inputs(&placed_t[0..100]);
+        let data = builder.build::<C>();
+
+        // circuit placement agrees with Board::add_ship for both orientations (z = true is vertical)
+        for z in [false, true] {
+            let carrier = Ship::<5>::new(2, 3, z);
+            let mut pw = PartialWitness::new();
+            carrier_t.witness(&mut pw, &carrier);
+            let proof = data.prove(pw).unwrap();
+            let placed = proof
+                .public_inputs
+                .iter()
+                .map(|bit| bit.to_canonical_u64() == 1)
+                .collect::<Vec<bool>>();
+
+            let mut native = [false; 100];
+            Board::add_ship(&carrier, &mut native);
+            assert_eq!(placed, native.to_vec());
+            let expected: [u8; 5] = if z { [32, 42, 52, 62, 72] } else { [32, 33, 34, 35, 36] };
+            assert_eq!(carrier.coordinates(), expected);
+        }
+    }
+
+    #[test]
+    fn test_fleet_shape() {
+        let board = Board::standard_host();
+        assert_eq!(board.bit_count(), 17);
+        prove_fleet_shape(board.bits(), &board).unwrap();
+    }
+
+    #[test]
+    fn test_fleet_shape_touching() {
+        // carrier and battleship stacked on adjacent rows, cruiser laid end to end with the carrier
+        let board = Board::new(
+            Ship::new(0, 0, false),
+            Ship::new(0, 1, false),
+            Ship::new(5, 0, false),
+            Ship::new(0, 5, true),
+            Ship::new(9, 8, true),
+        );
+        board.validate().unwrap();
+        prove_fleet_shape(board.bits(), &board).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fleet_shape_overlap() {
+        // submarine witnessed over the cruiser: 14 cells occupied, the shared cells are claimed twice
+        let board = Board::new(
+            Ship::new(3, 4, false),
+            Ship::new(9, 6, true),
+            Ship::new(0, 0, false),
+            Ship::new(0, 0, false),
+            Ship::new(6, 1, true),
+        );
+        prove_fleet_shape(board.bits(), &board).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fleet_shape_stray_cell() {
+        // standard host fleet plus one occupied cell no ship covers
+        let board = Board::standard_host();
+        let mut bits = board.bits();
+        bits[50] = true;
+        prove_fleet_shape(bits, &board).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fleet_shape_scattered() {
+        // 17 isolated cells on alternating coordinates of the first rows
+        let mut bits = [false; 100];
+        for i in 0..17 {
+            let index = i * 2 + (i * 2 / 10) % 2;
+            bits[index] = true;
+        }
+        assert_eq!(bits.iter().filter(|b| **b).count(), 17);
+        prove_fleet_shape(bits, &Board::standard_host()).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fleet_shape_empty() {
+        // all-false bitmap (no ships) has no cell for any ship of the fleet (see Board::validate_bits natively)
+        prove_fleet_shape([false; 100], &Board::standard_host()).unwrap();
+    }
+}
@@ -0,0 +1,151 @@
+use {
+    super::{
+        board::{decompose_board, place_ship},
+        shot::{check_hit, serialize_shot},
+    },
+    crate::{
+        circuits::{CircuitConfigBuilder, C, D, F},
+        utils::{board::Board, ship::Ship},
+    },
+    anyhow::{anyhow, Result},
+    plonky2::{
+        field::types::Field,
+        iop::witness::{PartialWitness, WitnessWrite},
+        plonk::circuit_builder::CircuitBuilder,
+    },
+    std::panic::{catch_unwind, AssertUnwindSafe},
+};
+
+// Developer tooling: rebuild the smallest circuit around a single gadget to find which one rejects a failing proof
+
+// Gadget to isolate in a minimal reproduction circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugGadget {
+    PlaceShip,              // place each ship of the board in fleet order, one circuit per ship
+    SerializeShot([u8; 2]), // serialize (and range check) a shot coordinate
+    CheckHit([u8; 2]),      // look up a shot coordinate on the board
+}
+
+/**
+ * Prove a board input against the smallest circuit exercising only the given gadget
+ * @notice failures name the gadget (and for placements the ship) that rejected the input
+ * @dev unsatisfied copy constraints panic during witness generation; the panic is caught and returned as the error
+ *
+ * @param board - board configuration the failing proof was made over
+ * @param gadget - gadget to reproduce the failure with
+ * @return - success if the gadget accepts the input, or an error naming the gadget that rejected it
+ */
+pub fn reproduce(board: &Board, gadget: DebugGadget) -> Result<()> {
+    match gadget {
+        DebugGadget::PlaceShip => {
+            // each ship is placed over the cells of the ships placed before it
+            reproduce_placement(&board.subset_bits(&[])?, &board.carrier, "carrier")?;
+            reproduce_placement(&board.subset_bits(&[0])?, &board.battleship, "battleship")?;
+            reproduce_placement(&board.subset_bits(&[0, 1])?, &board.cruiser, "cruiser")?;
+            reproduce_placement(&board.subset_bits(&[0, 1, 2])?, &board.submarine, "submarine")?;
+            let prior = board.subset_bits(&[0, 1, 2, 3])?;
+            reproduce_placement(&prior, &board.destroyer, "destroyer")
+        }
+        DebugGadget::SerializeShot(shot) => {
+            let mut builder = CircuitBuilder::<F, D>::new(CircuitConfigBuilder::new().build());
+            let shot_t = builder.add_virtual_target_arr::<2>();
+            serialize_shot(shot_t[0], shot_t[1], &mut builder)?;
+
+            let mut pw = PartialWitness::new();
+            pw.set_target_arr(shot_t, shot.map(F::from_canonical_u8));
+            prove_isolated(builder, pw, &format!("serialize_shot ({}, {})", shot[0], shot[1]))
+        }
+        DebugGadget::CheckHit(shot) => {
+            let config = CircuitConfigBuilder::new().with_random_access().build();
+            let mut builder = CircuitBuilder::<F, D>::new(config);
+            let board_t = builder.add_virtual_target_arr::<4>();
+            let shot_t = builder.add_virtual_target();
+            check_hit(board_t, shot_t, &mut builder)?;
+
+            // the shot is witnessed pre-serialized so only the lookup is exercised
+            let mut pw = PartialWitness::new();
+            pw.set_target_arr(board_t, board.canonical().map(F::from_canonical_u32));
+            let serialized = shot[1] as u64 * 10 + shot[0] as u64;
+            pw.set_target(shot_t, F::from_canonical_u64(serialized));
+            prove_isolated(builder, pw, &format!("check_hit ({}, {})", shot[0], shot[1]))
+        }
+    }
+}
+
+/**
+ * Reproduce the placement of a single ship on a board already holding the ships placed before it
+ *
+ * @param prior - board bits before the ship is placed
+ * @param ship - ship being placed
+ * @param name - name of the ship reported on failure
+ * @return - success if place_ship accepts the ship, or an error naming the ship
+ */
+fn reproduce_placement<const L: usize>(
+    prior: &[bool; 100],
+    ship: &Ship<L>,
+    name: &str,
+) -> Result<()> {
+    let config = CircuitConfigBuilder::new().with_random_access().build();
+    let mut builder = CircuitBuilder::<F, D>::new(config);
+    let board_t = builder.add_virtual_target_arr::<4>();
+    let x_t = builder.add_virtual_target();
+    let y_t = builder.add_virtual_target();
+    let z_t = builder.add_virtual_bool_target_safe();
+    let bits = decompose_board(board_t, &mut builder)?;
+    place_ship::<L>((x_t, y_t, z_t), bits, &mut builder)?;
+
+    let mut pw = PartialWitness::new();
+    pw.set_target_arr(board_t, Board::pack_bits(prior).map(F::from_canonical_u32));
+    pw.set_target(x_t, F::from_canonical_u8(ship.x));
+    pw.set_target(y_t, F::from_canonical_u8(ship.y));
+    pw.set_bool_target(z_t, ship.z);
+    let (x, y, z) = ship.canonical();
+    prove_isolated(builder, pw, &format!("place_ship {} ({}, {}, {})", name, x, y, z))
+}
+
+/**
+ * Build and prove a minimal circuit, converting a proving panic into an error
+ *
+ * @param builder - builder holding only the gadget under test
+ * @param pw - witness of the gadget inputs
+ * @param label - gadget and inputs named in the error
+ * @return - success if the proof verifies, or an error prefixed by the label
+ */
+fn prove_isolated(builder: CircuitBuilder<F, D>, pw: PartialWitness<F>, label: &str) -> Result<()> {
+    let data = builder.build::<C>();
+    match catch_unwind(AssertUnwindSafe(|| data.prove(pw))) {
+        Ok(Ok(proof)) => {
+            data.verify(proof).map_err(|e| anyhow!("{} failed to verify: {}", label, e))
+        }
+        Ok(Err(e)) => Err(anyhow!("{} failed to prove: {}", label, e)),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            Err(anyhow!("{} rejected its input: {}", label, message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproduce_place_ship() {
+        reproduce(&Board::standard_host(), DebugGadget::PlaceShip).unwrap();
+
+        // the submarine is moved onto the cruiser at (0, 0): the failure is pinned to the submarine
+        let overlapping = Board::new(
+            Ship::new(3, 4, false),
+            Ship::new(9, 6, true),
+            Ship::new(0, 0, false),
+            Ship::new(1, 0, false),
+            Ship::new(6, 1, true),
+        );
+        let err = reproduce(&overlapping, DebugGadget::PlaceShip).err().unwrap();
+        assert!(err.to_string().starts_with("place_ship submarine (1, 0, false)"));
+    }
+}
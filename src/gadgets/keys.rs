@@ -0,0 +1,173 @@
+use {
+    crate::circuits::{D, F},
+    anyhow::{ensure, Result},
+    plonky2::{
+        field::{secp256k1_base::Secp256K1Base, secp256k1_scalar::Secp256K1Scalar, types::Field},
+        iop::target::Target,
+        plonk::circuit_builder::CircuitBuilder,
+    },
+    plonky2_ecdsa::{
+        curve::secp256k1::Secp256K1,
+        gadgets::{
+            biguint::{BigUintTarget, CircuitBuilderBiguint},
+            curve::AffinePointTarget,
+            ecdsa::{verify_message_circuit, ECDSAPublicKeyTarget, ECDSASignatureTarget},
+            nonnative::CircuitBuilderNonNative,
+        },
+    },
+};
+
+// Targets of a public key and a signature it made, as LE u32 limbs (see utils::ecdsa)
+pub struct SignatureTargets {
+    pub key: [Target; 16],       // x coordinate limbs followed by y coordinate limbs
+    pub signature: [Target; 16], // r limbs followed by s limbs
+}
+
+/**
+ * Constrain an ecdsa signature over a board commitment under a witnessed public key
+ * @notice the message is utils::ecdsa::commitment_message: the commitment limbs as a 256 bit LE integer reduced into
+ *         a secp256k1 scalar
+ *
+ * @param commitment - board commitment as 4 u64 limbs
+ * @param builder - circuit builder
+ * @return - public key and signature targets to witness with public_key_limbs and signature_limbs
+ */
+pub fn verify_commitment_signature(
+    commitment: &[Target; 4],
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<SignatureTargets> {
+    // split each u64 commitment limb into LE u32 limbs and reduce into the signed message
+    let message = builder.add_virtual_biguint_target(8);
+    for (i, limb) in commitment.iter().enumerate() {
+        let (low, high) = builder.split_low_high(*limb, 32, 64);
+        builder.connect(message.limbs[2 * i].0, low);
+        builder.connect(message.limbs[2 * i + 1].0, high);
+    }
+    let message = builder.reduce::<Secp256K1Scalar>(&message);
+
+    // witnessed key coordinates and signature scalars, range checked into u32 limbs
+    let key = builder.add_virtual_biguint_target(16);
+    let signature = builder.add_virtual_biguint_target(16);
+    for limb in key.limbs.iter().chain(signature.limbs.iter()) {
+        builder.range_check(limb.0, 32);
+    }
+    let half = |x: &BigUintTarget, i: usize| BigUintTarget {
+        limbs: x.limbs[i * 8..(i + 1) * 8].to_vec(),
+    };
+    let key_t = ECDSAPublicKeyTarget::<Secp256K1>(AffinePointTarget {
+        x: builder.biguint_to_nonnative::<Secp256K1Base>(&half(&key, 0)),
+        y: builder.biguint_to_nonnative::<Secp256K1Base>(&half(&key, 1)),
+    });
+    let signature_t = ECDSASignatureTarget::<Secp256K1> {
+        r: builder.biguint_to_nonnative::<Secp256K1Scalar>(&half(&signature, 0)),
+        s: builder.biguint_to_nonnative::<Secp256K1Scalar>(&half(&signature, 1)),
+    };
+    verify_message_circuit(builder, message, signature_t, key_t);
+
+    Ok(SignatureTargets {
+        key: key.limbs.iter().map(|limb| limb.0).collect::<Vec<Target>>().try_into().unwrap(),
+        signature: signature
+            .limbs
+            .iter()
+            .map(|limb| limb.0)
+            .collect::<Vec<Target>>()
+            .try_into()
+            .unwrap(),
+    })
+}
+
+/**
+ * Constrain two equal-length limb arrays (i.e. public key coordinates) to differ in at least one limb
+ * @dev the prover witnesses weights w such that sum_i (a_i - b_i) * w_i = 1, which is unsatisfiable when a = b
+ *
+ * @param a - limbs of the first value
+ * @param b - limbs of the second value
+ * @param builder - circuit builder
+ * @return - weight targets to witness with distinct_weights
+ */
+pub fn assert_not_equal(
+    a: &[Target],
+    b: &[Target],
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<Vec<Target>> {
+    ensure!(a.len() == b.len(), "cannot compare {} limbs against {} limbs", a.len(), b.len());
+    let weights = builder.add_virtual_targets(a.len());
+    let mut sum = builder.zero();
+    for i in 0..a.len() {
+        let difference = builder.sub(a[i], b[i]);
+        sum = builder.mul_add(difference, weights[i], sum);
+    }
+    let one = builder.one();
+    builder.connect(sum, one);
+    Ok(weights)
+}
+
+/**
+ * Compute the weights witnessing that two limb arrays differ
+ * @notice inverse of the first differing limb difference, zero everywhere else
+ *
+ * @param a - limbs of the first value
+ * @param b - limbs of the second value
+ * @return - weights satisfying assert_not_equal, or error if the values are equal
+ */
+pub fn distinct_weights(a: &[u32], b: &[u32]) -> Result<Vec<F>> {
+    ensure!(a.len() == b.len(), "cannot compare {} limbs against {} limbs", a.len(), b.len());
+    let index = a.iter().zip(b.iter()).position(|(x, y)| x != y);
+    ensure!(index.is_some(), "values are equal");
+    let index = index.unwrap();
+    let mut weights = vec![F::ZERO; a.len()];
+    let difference = F::from_canonical_u32(a[index]) - F::from_canonical_u32(b[index]);
+    weights[index] = difference.inverse();
+    Ok(weights)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::circuits::{CircuitConfigBuilder, C},
+        plonky2::iop::witness::{PartialWitness, WitnessWrite},
+    };
+
+    /**
+     * Prove two limb arrays differ using the given weights
+     *
+     * @param a - limbs of the first value
+     * @param b - limbs of the second value
+     * @param weights - witnessed weights
+     * @return - ok if the proof verifies
+     */
+    fn prove_not_equal(a: &[u32], b: &[u32], weights: &[F]) -> Result<()> {
+        let mut builder = CircuitBuilder::<F, D>::new(CircuitConfigBuilder::new().build());
+        let a_t = builder.add_virtual_targets(a.len());
+        let b_t = builder.add_virtual_targets(b.len());
+        let weights_t = assert_not_equal(&a_t, &b_t, &mut builder)?;
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for i in 0..a.len() {
+            pw.set_target(a_t[i], F::from_canonical_u32(a[i]));
+            pw.set_target(b_t[i], F::from_canonical_u32(b[i]));
+            pw.set_target(weights_t[i], weights[i]);
+        }
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_not_equal() {
+        let a = [7, 0, u32::MAX, 3];
+        let b = [7, 0, 12, 3];
+        let weights = distinct_weights(&a, &b).unwrap();
+        prove_not_equal(&a, &b, &weights).unwrap();
+        assert!(distinct_weights(&a, &a).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_equal_rejected() {
+        // no weights can make a zero difference sum to one
+        let a = [7, 0, 12, 3];
+        prove_not_equal(&a, &a, &[F::ONE; 4]).unwrap();
+    }
+}
@@ -1,4 +1,8 @@
 pub mod board;
+pub mod debug;
+pub mod keys;
+#[cfg(feature = "profiling")]
+pub mod profile;
 pub mod range;
 pub mod shot;
 // pub mod ecdsa;
\ No newline at end of file
@@ -0,0 +1,92 @@
+use {
+    super::{
+        board::{decompose_board, place_ship},
+        range::less_than_10,
+        shot::{check_hit, serialize_shot},
+    },
+    crate::circuits::{game::board::BoardCircuit, D, F},
+    anyhow::Result,
+    plonky2::{field::types::Field, plonk::circuit_builder::CircuitBuilder},
+};
+
+// Gadget profiling: report the number of gates each gadget adds to a circuit
+// @dev gate counts diff builder.num_gates() so constants and lazily batched arithmetic are approximate
+
+/**
+ * Measure the number of gates added to a circuit by a gadget
+ *
+ * @param builder - circuit builder
+ * @param gadget - closure laying out the gadget
+ * @return - gadget output and number of gates it added
+ */
+pub fn count_gates<T>(
+    builder: &mut CircuitBuilder<F, D>,
+    gadget: impl FnOnce(&mut CircuitBuilder<F, D>) -> Result<T>,
+) -> Result<(T, usize)> {
+    let before = builder.num_gates();
+    let output = gadget(builder)?;
+    Ok((output, builder.num_gates() - before))
+}
+
+/**
+ * Measure the gates added by each gadget used in the board and shot circuits
+ * @dev each gadget is laid out in a fresh builder so batched gates are not shared between gadgets
+ *
+ * @return - (gadget name, gate count) for less_than_10, place_ship, serialize_shot, check_hit
+ */
+pub fn gadget_gate_counts() -> Result<Vec<(&'static str, usize)>> {
+    let config = BoardCircuit::config_inner()?;
+
+    // range check a single coordinate
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+    let x = builder.add_virtual_target();
+    let (_, range_gates) = count_gates(&mut builder, |builder| less_than_10(x, builder))?;
+
+    // place a carrier on an empty board
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+    let ship = (
+        builder.add_virtual_target(),
+        builder.add_virtual_target(),
+        builder.add_virtual_bool_target_safe(),
+    );
+    let blank = builder.constants(&[F::ZERO; 4]).try_into().unwrap();
+    let board = decompose_board(blank, &mut builder)?;
+    let (_, place_gates) =
+        count_gates(&mut builder, |builder| place_ship::<5>(ship, board, builder))?;
+
+    // serialize a shot coordinate
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+    let shot = builder.add_virtual_targets(2);
+    let (_, serialize_gates) =
+        count_gates(&mut builder, |builder| serialize_shot(shot[0], shot[1], builder))?;
+
+    // look up a serialized shot on a board
+    let mut builder = CircuitBuilder::<F, D>::new(config);
+    let board = builder.add_virtual_targets(4).try_into().unwrap();
+    let shot = builder.add_virtual_target();
+    let (_, hit_gates) = count_gates(&mut builder, |builder| check_hit(board, shot, builder))?;
+
+    Ok(vec![
+        ("less_than_10", range_gates),
+        ("place_ship", place_gates),
+        ("serialize_shot", serialize_gates),
+        ("check_hit", hit_gates),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gadget_gate_counts() {
+        let counts = gadget_gate_counts().unwrap();
+        for (gadget, gates) in counts.iter() {
+            println!("{}: {} gates", gadget, gates);
+        }
+
+        // every gadget adds constraints
+        assert_eq!(counts.len(), 4);
+        assert!(counts.iter().all(|(_, gates)| *gates > 0));
+    }
+}
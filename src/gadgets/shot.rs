@@ -1,7 +1,12 @@
 use super::{board::decompose_board, range::less_than_10};
-use crate::circuits::{D, F};
+use crate::circuits::{D, F, H};
 use anyhow::Result;
-use plonky2::{field::types::Field, iop::target::Target, plonk::circuit_builder::CircuitBuilder};
+use plonky2::{
+    field::types::Field,
+    hash::{hash_types::HashOutTarget, merkle_proofs::MerkleProofTarget},
+    iop::target::{BoolTarget, Target},
+    plonk::circuit_builder::CircuitBuilder,
+};
 
 /**
  * Constrain the computation of a shot coordinate into the serialized index
@@ -40,3 +45,335 @@ pub fn check_hit(
     let hit = builder.random_access(shot, bits);
     Ok(hit)
 }
+
+/**
+ * Constrain the computation of a shot coordinate on a square board of any size into the serialized index
+ * @dev x and y are range checked by bit decomposing both x and (side - 1 - x), which underflows if x >= side
+ *
+ * @param x - x coordinate of shot
+ * @param y - y coordinate of shot
+ * @param side - width/ height of the board
+ * @param builder - circuit builder
+ * @return - serialized shot coordinate (side * y + x)
+ */
+pub fn serialize_shot_sized(
+    x: Target,
+    y: Target,
+    side: usize,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<Target> {
+    // ensure x and y are within range of side
+    let bits = (usize::BITS - (side - 1).leading_zeros()).max(1) as usize;
+    let max = builder.constant(F::from_canonical_usize(side - 1));
+    for coordinate in [x, y] {
+        builder.range_check(coordinate, bits);
+        let headroom = builder.sub(max, coordinate);
+        builder.range_check(headroom, bits);
+    }
+    // serialize shot coordinate
+    let side_t = builder.constant(F::from_canonical_usize(side));
+    let y_serialized = builder.mul(y, side_t);
+    Ok(builder.add(x, y_serialized))
+}
+
+/**
+ * Constrain the lookup of a position on a Merkle-committed board (see utils::merkle_board)
+ * @notice proves a single Merkle opening instead of decomposing the whole board, so cost grows with log2(cells)
+ *
+ * @param root - Merkle root committing to the board
+ * @param shot - serialized shot coordinate (side * y + x)
+ * @param depth - depth of the Merkle tree
+ * @param builder - circuit builder
+ * @return - (hit boolean opened at the shot coordinate, Merkle proof targets to witness)
+ */
+pub fn check_hit_merkle(
+    root: HashOutTarget,
+    shot: Target,
+    depth: usize,
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<(BoolTarget, MerkleProofTarget)> {
+    // leaf value at the shot coordinate
+    let hit = builder.add_virtual_bool_target_safe();
+    // sibling hashes from the leaf up to the root
+    let proof = MerkleProofTarget {
+        siblings: builder.add_virtual_hashes(depth),
+    };
+    // open the root at the shot index
+    let index_bits = builder.split_le(shot, depth);
+    builder.verify_merkle_proof::<H>(vec![hit.target], &index_bits, root, &proof);
+    Ok((hit, proof))
+}
+
+/**
+ * Constrain the salted commitment to a serialized shot coordinate
+ * @notice salt prevents grinding the 100 possible coordinates from a public commitment
+ *
+ * @param shot - serialized shot coordinate (10y + x)
+ * @param salt - private salt targets
+ * @param builder - circuit builder
+ * @return - poseidon hash of the serialized shot and salt
+ */
+pub fn commit_shot(
+    shot: Target,
+    salt: [Target; 4],
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<HashOutTarget> {
+    let mut inputs = vec![shot];
+    inputs.extend_from_slice(&salt);
+    Ok(builder.hash_n_to_hash_no_pad::<H>(inputs))
+}
+
+/**
+ * Constrain the opening of a salted shot commitment to an on-board coordinate
+ * @notice a committed shot is guaranteed to be on the board before it is revealed
+ * @dev copy constraints fail if x/ y is out of range or the opening does not hash to the commitment
+ *
+ * @param commitment - public salted shot commitment
+ * @param x - private x coordinate of shot
+ * @param y - private y coordinate of shot
+ * @param salt - private salt targets
+ * @param builder - circuit builder
+ * @return - serialized shot coordinate (10y + x)
+ */
+pub fn open_shot_commitment(
+    commitment: [Target; 4],
+    x: Target,
+    y: Target,
+    salt: [Target; 4],
+    builder: &mut CircuitBuilder<F, D>,
+) -> Result<Target> {
+    // range check and serialize the opened coordinate
+    let serialized = serialize_shot(x, y, builder)?;
+    // recompute and bind the commitment
+    let opened = commit_shot(serialized, salt, builder)?;
+    for (opened_limb, limb) in opened.elements.iter().zip(commitment) {
+        builder.connect(*opened_limb, limb);
+    }
+    Ok(serialized)
+}
+
+/**
+ * Constrain a set of serialized shot coordinates to be pairwise distinct
+ * @notice each pairwise difference is witnessed an inverse, which does not exist for a zero difference
+ * @dev proving fails if any two shots collide; intended for batched shots/ move sequence validation
+ *
+ * @param shots - serialized shot coordinates (10y + x)
+ * @param builder - circuit builder
+ */
+pub fn assert_distinct(shots: &[Target], builder: &mut CircuitBuilder<F, D>) -> Result<()> {
+    for i in 0..shots.len() {
+        for j in i + 1..shots.len() {
+            // difference of every pair must be invertible (nonzero)
+            let difference = builder.sub(shots[i], shots[j]);
+            builder.inverse(difference);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            circuits::{CircuitConfigBuilder, C},
+            utils::{board::Board, commitment::shot_commitment, ship::Ship},
+        },
+        plonky2::{
+            field::types::{Field64, PrimeField64},
+            iop::witness::{PartialWitness, WitnessWrite},
+            plonk::circuit_data::CircuitConfig,
+        },
+    };
+
+    /**
+     * Prove the opening of a salted shot commitment
+     *
+     * @param shot - the shot coordinate (x, y)
+     * @param salt - private salt
+     * @return - (serialized shot, commitment) exported by the proof
+     */
+    fn prove_shot_commitment(shot: [u8; 2], salt: [u64; 4]) -> Result<(u8, [u64; 4])> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let shot_t = builder.add_virtual_target_arr::<2>();
+        let salt_t = builder.add_virtual_target_arr::<4>();
+        let serialized_t = serialize_shot(shot_t[0], shot_t[1], &mut builder)?;
+        let commitment_t = commit_shot(serialized_t, salt_t, &mut builder)?;
+        builder.register_public_input(serialized_t);
+        builder.register_public_inputs(&commitment_t.elements);
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        pw.set_target(shot_t[0], F::from_canonical_u8(shot[0]));
+        pw.set_target(shot_t[1], F::from_canonical_u8(shot[1]));
+        for i in 0..salt_t.len() {
+            pw.set_target(salt_t[i], F::from_noncanonical_u64(salt[i]));
+        }
+        let proof = data.prove(pw)?;
+        data.verify(proof.clone())?;
+
+        let outputs = proof
+            .public_inputs
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>();
+        Ok((outputs[0] as u8, outputs[1..5].try_into().unwrap()))
+    }
+
+    /**
+     * Prove a set of shot coordinates are pairwise distinct
+     *
+     * @param shots - shot coordinates (x, y)
+     * @return - error if proving fails
+     */
+    fn prove_distinct(shots: &[[u8; 2]]) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let shot_t = (0..shots.len())
+            .map(|_| builder.add_virtual_target_arr::<2>())
+            .collect::<Vec<[Target; 2]>>();
+        let serialized_t = shot_t
+            .iter()
+            .map(|shot| serialize_shot(shot[0], shot[1], &mut builder))
+            .collect::<Result<Vec<Target>>>()?;
+        assert_distinct(&serialized_t, &mut builder)?;
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for i in 0..shots.len() {
+            pw.set_target(shot_t[i][0], F::from_canonical_u8(shots[i][0]));
+            pw.set_target(shot_t[i][1], F::from_canonical_u8(shots[i][1]));
+        }
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_distinct_shots() {
+        prove_distinct(&[[0, 0], [1, 0], [0, 1], [9, 9]]).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicate_shots() {
+        // (3, 4) is shot twice
+        prove_distinct(&[[3, 4], [0, 0], [3, 4]]).unwrap();
+    }
+
+    /**
+     * Prove the opening of a public shot commitment to a private coordinate
+     *
+     * @param commitment - public salted shot commitment
+     * @param shot - the shot coordinate (x, y)
+     * @param salt - private salt
+     * @return - error if proving fails
+     */
+    fn prove_open_shot_commitment(
+        commitment: [u64; 4],
+        shot: [u8; 2],
+        salt: [u64; 4],
+    ) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let commitment_t = builder.add_virtual_target_arr::<4>();
+        let shot_t = builder.add_virtual_target_arr::<2>();
+        let salt_t = builder.add_virtual_target_arr::<4>();
+        open_shot_commitment(commitment_t, shot_t[0], shot_t[1], salt_t, &mut builder)?;
+        builder.register_public_inputs(&commitment_t);
+        let data = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for i in 0..commitment_t.len() {
+            pw.set_target(commitment_t[i], F::from_canonical_u64(commitment[i]));
+            pw.set_target(salt_t[i], F::from_noncanonical_u64(salt[i]));
+        }
+        pw.set_target(shot_t[0], F::from_canonical_u8(shot[0]));
+        pw.set_target(shot_t[1], F::from_canonical_u8(shot[1]));
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_open_shot_commitment() {
+        let salt = [11u64, 22, 33, 44];
+        let commitment = shot_commitment([3, 4], salt).unwrap();
+        prove_open_shot_commitment(commitment, [3, 4], salt).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_open_shot_commitment_off_board() {
+        // (12, 0) serializes to the same index as (2, 1) but is off the board
+        let salt = [11u64, 22, 33, 44];
+        let commitment = shot_commitment([2, 1], salt).unwrap();
+        prove_open_shot_commitment(commitment, [12, 0], salt).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_open_shot_commitment_wrong_salt() {
+        let commitment = shot_commitment([3, 4], [11, 22, 33, 44]).unwrap();
+        prove_open_shot_commitment(commitment, [3, 4], [0, 22, 33, 44]).unwrap();
+    }
+
+    #[test]
+    fn test_commit_shot_salted() {
+        let shot = [3u8, 4];
+        let salt_a = [1u64, 2, 3, 4];
+        let salt_b = [u64::MAX, 0, 42, 7];
+
+        // both salts open to the same coordinate
+        let (shot_a, commitment_a) = prove_shot_commitment(shot, salt_a).unwrap();
+        let (shot_b, commitment_b) = prove_shot_commitment(shot, salt_b).unwrap();
+        assert_eq!(shot_a, 43);
+        assert_eq!(shot_b, 43);
+
+        // salts yield different commitments matching the native computation
+        assert_ne!(commitment_a, commitment_b);
+        assert_eq!(Some(commitment_a), shot_commitment(shot, salt_a));
+        assert_eq!(Some(commitment_b), shot_commitment(shot, salt_b));
+    }
+
+    #[test]
+    fn test_shot_convention_matches_add_ship() {
+        // circuit reading the board bit at a shot and at its transpose
+        let config = CircuitConfigBuilder::new().with_random_access().build();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let board_t = builder.add_virtual_target_arr::<4>();
+        let shot_t = builder.add_virtual_target_arr::<2>();
+        for (x, y) in [(shot_t[0], shot_t[1]), (shot_t[1], shot_t[0])] {
+            let serialized_t = serialize_shot(x, y, &mut builder).unwrap();
+            let hit_t = check_hit(board_t, serialized_t, &mut builder).unwrap();
+            builder.register_public_input(hit_t);
+        }
+        let data = builder.build::<C>();
+
+        for coordinate in 0..100u8 {
+            let (x, y) = (coordinate % 10, coordinate / 10);
+            // board with only the cell placed at (x, y) occupied
+            let mut bits = [false; 100];
+            Board::add_ship(&Ship::<1>::new(x, y, false), &mut bits);
+            assert!(bits[coordinate as usize]);
+            let board = Board::pack_bits(&bits);
+
+            let mut pw = PartialWitness::new();
+            for i in 0..board_t.len() {
+                pw.set_target(board_t[i], F::from_canonical_u32(board[i]));
+            }
+            pw.set_target(shot_t[0], F::from_canonical_u8(x));
+            pw.set_target(shot_t[1], F::from_canonical_u8(y));
+            let proof = data.prove(pw).unwrap();
+            data.verify(proof.clone()).unwrap();
+
+            // the shot reads the placed bit; the transposed shot only reads it on the diagonal
+            let hits = proof
+                .public_inputs
+                .iter()
+                .map(|hit| hit.to_canonical_u64())
+                .collect::<Vec<u64>>();
+            assert_eq!(hits[0], 1, "shot ({}, {}) read a miss on its own cell", x, y);
+            assert_eq!(hits[1], (x == y) as u64, "shot ({}, {}) read the transposed cell", y, x);
+        }
+    }
+}
@@ -1,15 +1,26 @@
 use {
     crate::{
-        circuits::F,
-        utils::ship::Ship
+        circuits::{F, H},
+        utils::{merkle_board::CellCommittedBoard, ship::Ship},
     },
+    anyhow::{anyhow, ensure, Result},
     plonky2::{
         field::types::{Field, PrimeField64},
-        hash::poseidon::PoseidonHash,
         plonk::config::Hasher,
-    }
+    },
 };
 
+// ship placements (x, y, z) in fleet order
+type Placement = [(u8, u8, bool); 5];
+
+// Hit status of a single ship given a set of shots fired at its board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShipStatus {
+    pub length: usize, // number of cells occupied by the ship
+    pub hits: usize, // number of distinct ship cells fired upon
+    pub sunk: bool, // every cell of the ship has been fired upon
+}
+
 #[derive(Debug, Clone)]
 pub struct Board {
     pub carrier: Ship<5>,
@@ -36,6 +47,30 @@ impl Board {
         }
     }
 
+    /**
+     * Return the tonnage of the fleet placed on a board (the sum of its ship lengths)
+     * @notice the number of hits needed to sink every ship, i.e. the end condition of a channel
+     *
+     * @return - total number of cells occupied by the fleet
+     */
+    pub const fn tonnage() -> u8 {
+        (Ship::<5>::LENGTH
+            + Ship::<4>::LENGTH
+            + Ship::<3>::LENGTH
+            + Ship::<3>::LENGTH
+            + Ship::<2>::LENGTH) as u8
+    }
+
+    /**
+     * Return the tonnage of an arbitrary fleet given as ship lengths
+     *
+     * @param fleet - length of each ship in the fleet
+     * @return - total number of cells occupied by the fleet
+     */
+    pub fn fleet_tonnage(fleet: &[usize]) -> u8 {
+        fleet.iter().sum::<usize>() as u8
+    }
+
     /**
      * Add a ship to the board
      *
@@ -67,14 +102,160 @@ impl Board {
     }
 
     /**
-     * Turn the board into a LE-serialized representation of the ship placements as u64-serialized u128
+     * List the serialized coordinates (10y + x) occupied by ships
+     * @notice a valid board occupies one coordinate per unit of tonnage (17)
+     *
+     * @return - ascending serialized indices of all set bits
+     */
+    pub fn occupied_coordinates(&self) -> Vec<u8> {
+        self.bits()
+            .iter()
+            .enumerate()
+            .filter(|(_, occupied)| **occupied)
+            .map(|(coordinate, _)| coordinate as u8)
+            .collect()
+    }
+
+    /**
+     * Report the hit status of each ship given the shots fired at the board
+     * @notice repeated and off-board shots are ignored; a cell counts once however often it is fired upon
+     *
+     * @param fired - serialized shot coordinates (10y + x) fired at the board
+     * @return - status of carrier, battleship, cruiser, submarine, destroyer in that order
+     */
+    pub fn ship_statuses(&self, fired: &[u8]) -> Vec<ShipStatus> {
+        // bitmap of fired coordinates
+        let mut shots = [false; 100];
+        for coordinate in fired.iter().filter(|coordinate| **coordinate < 100) {
+            shots[*coordinate as usize] = true;
+        }
+        let status = |coordinates: &[u8]| {
+            let hits = coordinates
+                .iter()
+                .filter(|coordinate| shots[**coordinate as usize])
+                .count();
+            ShipStatus {
+                length: coordinates.len(),
+                hits,
+                sunk: hits == coordinates.len(),
+            }
+        };
+        vec![
+            status(&self.carrier.coordinates()),
+            status(&self.battleship.coordinates()),
+            status(&self.cruiser.coordinates()),
+            status(&self.submarine.coordinates()),
+            status(&self.destroyer.coordinates()),
+        ]
+    }
+
+    /**
+     * Count the coordinates occupied by ships
+     * @notice a valid board sets one bit per unit of tonnage (17); overlapping, wrapped or missing ships do not
+     *
+     * @return - number of set bits in the board
+     */
+    pub fn bit_count(&self) -> u32 {
+        self.bits().iter().filter(|occupied| **occupied).count() as u32
+    }
+
+    /**
+     * Natively check the board is a placement the board circuit would accept
+     * @notice every ship must lie fully on the grid and the fleet must occupy 17 distinct cells
+     * @dev ranges are checked before the bitmap is built since off-board ships would wrap or index past the grid
+     *
+     * @return - error describing the first violated rule
+     */
+    pub fn validate(&self) -> Result<()> {
+        // every ship lies fully on the grid
+        let ships = [
+            (self.carrier.canonical(), 5),
+            (self.battleship.canonical(), 4),
+            (self.cruiser.canonical(), 3),
+            (self.submarine.canonical(), 3),
+            (self.destroyer.canonical(), 2),
+        ];
+        for ((x, y, z), length) in ships {
+            let (along, across) = if z { (y, x) } else { (x, y) };
+            ensure!(
+                across < 10 && along as usize + length <= 10,
+                "ship of length {} at ({}, {}) is off the board",
+                length,
+                x,
+                y
+            );
+        }
+        // ships do not overlap and the fleet is present
+        Board::validate_bits(&self.bits())
+    }
+
+    /**
+     * Natively check a raw board bitmap carries a full fleet
+     * @notice rejects an empty bitmap explicitly: the board circuit's fleet constraints never accept a board with no ships
+     * @dev only counts cells; overlapping ships placed through Board are caught here since they set fewer than 17 bits
+     *
+     * @param bits - 100 bools representing the full board state
+     * @return - error if the bitmap has no ships or the wrong number of occupied cells
+     */
+    pub fn validate_bits(bits: &[bool; 100]) -> Result<()> {
+        let occupied = bits.iter().filter(|occupied| **occupied).count();
+        ensure!(occupied != 0, "board has no ships");
+        ensure!(
+            occupied == Board::tonnage() as usize,
+            "board occupies {} cells, expected {}",
+            occupied,
+            Board::tonnage()
+        );
+        Ok(())
+    }
+
+    /**
+     * Turn the board into a LE-serialized representation of the ship placements as u32-serialized u128
+     * @notice single pass over the occupied coordinates, without materializing the 100 bit board
      * @dev last 28 bits unused
      *
-     * @return - 2 u64s representing the full board state
+     * @return - 4 u32s representing the full board state
      */
     pub fn canonical(&self) -> [u32; 4] {
-        // get board as 100 LE bits
-        let bits = self.bits();
+        // single pass over the occupied coordinates, shifting each bit into its u32 limb
+        let mut canonical = [0u32; 4];
+        let coordinates = self
+            .carrier
+            .coordinates()
+            .into_iter()
+            .chain(self.battleship.coordinates())
+            .chain(self.cruiser.coordinates())
+            .chain(self.submarine.coordinates())
+            .chain(self.destroyer.coordinates());
+        for coordinate in coordinates {
+            canonical[(coordinate >> 5) as usize] |= 1 << (coordinate & 31);
+        }
+        canonical
+    }
+
+    /**
+     * Return the ship placements in fleet order
+     *
+     * @return - (x, y, z) of the carrier, battleship, cruiser, submarine and destroyer
+     */
+    fn placement(&self) -> Placement {
+        [
+            self.carrier.canonical(),
+            self.battleship.canonical(),
+            self.cruiser.canonical(),
+            self.submarine.canonical(),
+            self.destroyer.canonical(),
+        ]
+    }
+
+    /**
+     * Pack 100 LE board bits into the u32-serialized u128 used as circuit input
+     * @dev last 28 bits unused
+     *
+     * @param bits - 100 bools representing the full board state
+     * @return - 4 u32s representing the full board state
+     */
+    pub fn pack_bits(bits: &[bool; 100]) -> [u32; 4] {
         // convert into 4 u32s as a little-endian serialized u128
         let mut result = [0u32; 4];
         for (index, &bit) in bits.iter().enumerate() {
@@ -88,6 +269,21 @@ impl Board {
         result
     }
 
+    /**
+     * Unpack the u32-serialized u128 used as circuit input back into 100 LE board bits
+     * @dev inverse of pack_bits; the last 28 bits are dropped
+     *
+     * @param packed - 4 u32s representing the full board state
+     * @return - 100 bools representing the full board state
+     */
+    pub fn unpack_bits(packed: &[u32; 4]) -> [bool; 100] {
+        let mut bits = [false; 100];
+        for (index, bit) in bits.iter_mut().enumerate() {
+            *bit = packed[index / 32] >> (index % 32) & 1 == 1;
+        }
+        bits
+    }
+
     /**
      * Hash the board state into a 4 u64 array
      * @todo
@@ -102,7 +298,7 @@ impl Board {
             .try_into()
             .unwrap();
         // hash board state into 4 u64s
-        PoseidonHash::hash_no_pad(&board)
+        H::hash_no_pad(&board)
             .elements
             .iter()
             .map(|x| x.to_canonical_u64())
@@ -111,6 +307,140 @@ impl Board {
             .unwrap()
     }
 
+    /**
+     * Return the board bits occupied by a subset of the fleet
+     *
+     * @param ships - indices of the ships to place (0 = carrier, 1 = battleship, 2 = cruiser, 3 = submarine,
+     *                4 = destroyer)
+     * @return - 100 bools with only the named ships' cells set, or error if an index is not a ship
+     */
+    pub fn subset_bits(&self, ships: &[usize]) -> Result<[bool; 100]> {
+        let mut board = [false; 100];
+        for ship in ships.iter() {
+            match ship {
+                0 => Board::add_ship(&self.carrier, &mut board),
+                1 => Board::add_ship(&self.battleship, &mut board),
+                2 => Board::add_ship(&self.cruiser, &mut board),
+                3 => Board::add_ship(&self.submarine, &mut board),
+                4 => Board::add_ship(&self.destroyer, &mut board),
+                _ => return Err(anyhow!("fleet has no ship {}", ship)),
+            }
+        }
+        Ok(board)
+    }
+
+    /**
+     * Commit to the cells of a subset of the fleet
+     * @notice lets one ship be revealed in a dispute without revealing the rest of the board
+     * @dev same preimage layout as hash, with the salt limbs appended; unsalted over the full fleet it equals hash
+     *      (see gadgets::board::hash_board_salted for the circuit counterpart)
+     *
+     * @param ships - indices of the ships to commit to (see subset_bits)
+     * @param salt - optional salt hiding the subset from grinding
+     * @return - commitment to the subset as 4 u64s, or error if an index is not a ship
+     */
+    pub fn hash_subset(&self, ships: &[usize], salt: Option<[u64; 4]>) -> Result<[u64; 4]> {
+        let mut preimage = Board::pack_bits(&self.subset_bits(ships)?)
+            .map(F::from_canonical_u32)
+            .to_vec();
+        if let Some(salt) = salt {
+            preimage.extend(salt.map(F::from_noncanonical_u64));
+        }
+        Ok(H::hash_no_pad(&preimage)
+            .elements
+            .map(|element| element.to_canonical_u64()))
+    }
+
+    /**
+     * Commit to the board as a Merkle root of salted per-cell commitments
+     * @notice unlike hash, individual cells can later be revealed without revealing the board (see CellCommittedBoard)
+     *
+     * @param salt - private board salt
+     * @return - Merkle root of the cell commitments as 4 u64s
+     */
+    pub fn merkle_root(&self, salt: [u64; 4]) -> [u64; 4] {
+        CellCommittedBoard::new(self.bits(), salt).root()
+    }
+
+    /**
+     * Apply one of the 8 symmetries of the 10x10 grid (dihedral group D4) to the board
+     * @dev interchangeable ships (cruiser, submarine) are re-ordered canonically after transforming
+     *
+     * @param symmetry - index of the transform in 0..8 (see Ship::transform)
+     * @return - transformed board
+     */
+    pub fn transform(&self, symmetry: u8) -> Board {
+        let mut cruiser = self.cruiser.transform(symmetry);
+        let mut submarine = self.submarine.transform(symmetry);
+        if cruiser.coordinates()[0] > submarine.coordinates()[0] {
+            (cruiser, submarine) = (submarine, cruiser);
+        }
+        Board::new(
+            self.carrier.transform(symmetry),
+            self.battleship.transform(symmetry),
+            cruiser,
+            submarine,
+            self.destroyer.transform(symmetry),
+        )
+    }
+
+    /**
+     * Canonicalize the board under the symmetries of the grid so reflected/ rotated boards can be deduplicated
+     *
+     * @return - the transform of the board with the lexicographically smallest bitmap
+     */
+    pub fn canonical_under_symmetry(&self) -> Board {
+        (0..8)
+            .map(|symmetry| self.transform(symmetry))
+            .min_by_key(|board| board.bits())
+            .unwrap()
+    }
+
+    /**
+     * Check whether interchangeable ships are provided in canonical order
+     * @notice cruiser and submarine are both length 3; canonical order requires cruiser head < submarine head
+     *
+     * @return - true if the ship inputs are canonically ordered
+     */
+    pub fn is_canonical(&self) -> bool {
+        self.cruiser.coordinates()[0] < self.submarine.coordinates()[0]
+    }
+
+    /**
+     * Enumerate every valid board reachable by relocating exactly one ship
+     * @notice relocation covers any new head and orientation, not just single-step moves
+     *
+     * @return - valid boards differing from this board in the placement of a single ship
+     */
+    pub fn neighbors(&self) -> Vec<Board> {
+        let mut neighbors = Vec::new();
+        let current = self.placement();
+        for ship in 0..current.len() {
+            for y in 0..10 {
+                for x in 0..10 {
+                    for z in [false, true] {
+                        if current[ship] == (x, y, z) {
+                            continue;
+                        }
+                        let mut candidate = self.clone();
+                        match ship {
+                            0 => candidate.carrier = Ship::new(x, y, z),
+                            1 => candidate.battleship = Ship::new(x, y, z),
+                            2 => candidate.cruiser = Ship::new(x, y, z),
+                            3 => candidate.submarine = Ship::new(x, y, z),
+                            _ => candidate.destroyer = Ship::new(x, y, z),
+                        }
+                        // off-board and overlapping relocations are rejected by the native rules
+                        if candidate.validate().is_ok() {
+                            neighbors.push(candidate);
+                        }
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+
     /**
      * Render ASCII to the console representing the ship placement
      */
@@ -135,6 +465,47 @@ impl Board {
         }
     }
 
+    /**
+     * Render ASCII representing the ship placement overlaid with shot results
+     * @notice hit cells render as X and missed cells as O; every other cell renders its occupancy bit
+     *
+     * @param hits - serialized coordinates (10y + x) of shots that hit
+     * @param misses - serialized coordinates (10y + x) of shots that missed
+     * @return - rendered board, one line per row (highest row first) followed by the x axis
+     */
+    pub fn render_with_shots(&self, hits: &[u8], misses: &[u8]) -> String {
+        let board = self.bits();
+        let mut lines = vec![String::from(" (Y)")];
+        for y in (0..10).rev() {
+            let mut out = format!("{} |", y);
+            for x in 0..10 {
+                let coordinate = (y * 10 + x) as u8;
+                let cell = if hits.contains(&coordinate) {
+                    String::from("X")
+                } else if misses.contains(&coordinate) {
+                    String::from("O")
+                } else {
+                    (board[coordinate as usize] as u8).to_string()
+                };
+                out = format!("{} {}", out, cell);
+            }
+            lines.push(out);
+        }
+        lines.push(String::from("   -------------------- (X)"));
+        lines.push(String::from("    0 1 2 3 4 5 6 7 8 9"));
+        lines.join("\n")
+    }
+
+    /**
+     * Render ASCII to the console representing the ship placement and shot results
+     *
+     * @param hits - serialized coordinates (10y + x) of shots that hit
+     * @param misses - serialized coordinates (10y + x) of shots that missed
+     */
+    pub fn print_with_shots(&self, hits: &[u8], misses: &[u8]) {
+        println!("{}", self.render_with_shots(hits, misses));
+    }
+
     pub fn print_canonical(board: &[u32; 4]) {
         // convert board into 100 LE bits
         let mut bits = [false; 100];
@@ -163,19 +534,292 @@ impl Board {
 }
 
 #[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_board_print() {
-        let board = Board::new(
+impl Board {
+    /**
+     * Example host board used across tests
+     *
+     * @return - host board fixture
+     */
+    pub fn standard_host() -> Self {
+        Board::new(
             Ship::new(3, 4, false),
             Ship::new(9, 6, true),
             Ship::new(0, 0, false),
             Ship::new(0, 6, false),
             Ship::new(6, 1, true),
-        );
+        )
+    }
+
+    /**
+     * Example guest board used across tests
+     *
+     * @return - guest board fixture
+     */
+    pub fn standard_guest() -> Self {
+        Board::new(
+            Ship::new(3, 3, true),
+            Ship::new(5, 4, false),
+            Ship::new(0, 1, false),
+            Ship::new(0, 5, true),
+            Ship::new(6, 1, false),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gadgets::board::STANDARD_FLEET;
+
+    #[test]
+    fn test_tonnage() {
+        assert_eq!(Board::tonnage(), 17);
+        assert_eq!(Board::tonnage(), Board::fleet_tonnage(&STANDARD_FLEET));
+        assert_eq!(Board::standard_host().bit_count(), Board::tonnage() as u32);
+        // custom fleet without a carrier
+        assert_eq!(Board::fleet_tonnage(&[4, 3, 3, 2]), 12);
+    }
+
+    #[test]
+    fn test_ship_statuses() {
+        let board = Board::standard_host();
+        let mut fired = board.destroyer.coordinates().to_vec();
+        // a repeated shot and a cruiser hit do not sink anything else
+        fired.push(fired[0]);
+        fired.push(board.cruiser.coordinates()[0]);
+
+        let statuses = board.ship_statuses(&fired);
+        let lengths = statuses.iter().map(|status| status.length).collect::<Vec<usize>>();
+        assert_eq!(lengths, vec![5, 4, 3, 3, 2]);
+        // only the destroyer is sunk
+        let sunk = statuses.iter().map(|status| status.sunk).collect::<Vec<bool>>();
+        assert_eq!(sunk, vec![false, false, false, false, true]);
+        assert_eq!(statuses[4].hits, 2);
+        assert_eq!(statuses[2].hits, 1);
+        assert_eq!(statuses[0].hits, 0);
+    }
+
+    #[test]
+    fn test_board_print() {
+        let board = Board::standard_host();
 
         board.print();
     }
+
+    #[test]
+    fn test_canonical_under_symmetry() {
+        let board = Board::standard_host();
+
+        // standard host rotated 90 degrees by hand: (x, y) -> (9 - y, x)
+        let rotated = Board::new(
+            Ship::new(5, 3, true),
+            Ship::new(0, 9, false),
+            Ship::new(9, 0, true),
+            Ship::new(3, 0, true),
+            Ship::new(7, 6, false),
+        );
+        assert_eq!(board.transform(1).bits(), rotated.bits());
+        assert_ne!(board.bits(), rotated.bits());
+
+        // both canonicalize to the same representative
+        let canonical = board.canonical_under_symmetry();
+        let canonical_rotated = rotated.canonical_under_symmetry();
+        assert_eq!(canonical.bits(), canonical_rotated.bits());
+        assert!(canonical.is_canonical());
+        assert!(canonical_rotated.is_canonical());
+
+        // every transform preserves the fleet
+        for symmetry in 0..8 {
+            assert_eq!(board.transform(symmetry).bit_count(), 17);
+        }
+    }
+
+    #[test]
+    fn test_occupied_coordinates() {
+        let board = Board::standard_host();
+        let expected = vec![0, 1, 2, 16, 26, 43, 44, 45, 46, 47, 60, 61, 62, 69, 79, 89, 99];
+        assert_eq!(board.occupied_coordinates(), expected);
+        assert_eq!(board.occupied_coordinates().len() as u32, board.bit_count());
+    }
+
+    #[test]
+    fn test_canonical_preserves_bit_count() {
+        for board in [Board::standard_host(), Board::standard_guest()] {
+            assert_eq!(board.bit_count(), 17);
+
+            // canonical limbs carry every bit, including those straddling the 32 bit limb boundaries
+            let canonical = board.canonical();
+            assert_eq!(canonical.iter().map(|limb| limb.count_ones()).sum::<u32>(), 17);
+
+            // re-expanding the limbs recovers the exact board bits
+            let bits = Board::unpack_bits(&canonical);
+            assert_eq!(bits, board.bits());
+            assert_eq!(bits.iter().filter(|occupied| **occupied).count(), 17);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        // fixtures are valid placements
+        Board::standard_host().validate().unwrap();
+        Board::standard_guest().validate().unwrap();
+
+        // empty bitmap is rejected as having no ships
+        let err = Board::validate_bits(&[false; 100]).err().unwrap();
+        assert_eq!(err.to_string(), "board has no ships");
+
+        // overlapping ships (destroyer on the carrier) leave fewer than 17 cells
+        let mut overlapping = Board::standard_host();
+        overlapping.destroyer = Ship::new(3, 4, false);
+        assert!(overlapping.validate().is_err());
+
+        // carrier running off the right edge
+        let mut off_board = Board::standard_host();
+        off_board.carrier = Ship::new(7, 2, false);
+        assert!(off_board.validate().is_err());
+    }
+
+    // golden commitment of the standard host board: changes here indicate a change in the commitment format
+    const STANDARD_HOST_COMMITMENT: [u64; 4] = [
+        259386067528734052,
+        9853843094685791004,
+        7054096878597948727,
+        15303952516468002253,
+    ];
+
+    // commitment test vectors: ship placements (x, y, z) in fleet order, the packed preimage they commit to and the
+    // resulting commitment
+    // @dev update deliberately, and only when the commitment format changes by design
+    const COMMITMENT_VECTORS: [(Placement, [u32; 4], [u64; 4]); 3] = [
+        // standard host
+        (
+            [(3, 4, false), (9, 6, true), (0, 0, false), (0, 6, false), (6, 1, true)],
+            [67174407, 1879111680, 33587232, 8],
+            STANDARD_HOST_COMMITMENT,
+        ),
+        // standard guest
+        (
+            [(3, 3, true), (5, 4, false), (0, 1, false), (0, 5, true), (6, 1, false)],
+            [203776, 2418403330, 576, 0],
+            [
+                13859812366214506354,
+                12409061988742642482,
+                5220607979895817401,
+                2393598278610642428,
+            ],
+        ),
+        // every ship touching an edge, including both ends of the final (28 bit padded) limb
+        (
+            [(0, 0, true), (9, 6, true), (5, 9, false), (9, 0, true), (1, 9, false)],
+            [1612187137, 256, 2583724064, 11],
+            [
+                1705262384268642635,
+                4504383186771365681,
+                1237000201656614335,
+                11356843803417971988,
+            ],
+        ),
+    ];
+
+    #[test]
+    fn test_standard_host_hash() {
+        assert_eq!(Board::standard_host().hash(), STANDARD_HOST_COMMITMENT);
+    }
+
+    #[test]
+    fn test_commitment_vectors() {
+        for (placement, preimage, commitment) in COMMITMENT_VECTORS {
+            let [a, b, c, d, e] = placement;
+            let board = Board::new(
+                Ship::new(a.0, a.1, a.2),
+                Ship::new(b.0, b.1, b.2),
+                Ship::new(c.0, c.1, c.2),
+                Ship::new(d.0, d.1, d.2),
+                Ship::new(e.0, e.1, e.2),
+            );
+            board.validate().unwrap();
+
+            // bit layout: LE u32 limbs, index 10y + x, unused high bits zero
+            assert_eq!(board.canonical(), preimage);
+            assert_eq!(preimage[3] >> 4, 0);
+
+            // convention: unsalted poseidon hash of exactly the 4 preimage limbs, no domain separation
+            let expected = H::hash_no_pad(&preimage.map(F::from_canonical_u32))
+                .elements
+                .map(|x| x.to_canonical_u64());
+            assert_eq!(expected, commitment);
+            assert_eq!(board.hash(), commitment);
+        }
+        // the first vector is the standard host
+        assert_eq!(Board::standard_host().canonical(), COMMITMENT_VECTORS[0].1);
+    }
+
+    #[test]
+    fn test_render_with_shots() {
+        let board = Board::standard_host();
+        // hits on the cruiser at (0, 0) and (1, 0), misses at (5, 5) and (9, 0)
+        let rendered = board.render_with_shots(&[0, 1], &[55, 9]);
+        let lines = rendered.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], " (Y)");
+        assert_eq!(lines[5], "5 | 0 0 0 0 0 O 0 0 0 0");
+        assert_eq!(lines[10], "0 | X X 1 0 0 0 0 0 0 O");
+        assert_eq!(lines[12], "    0 1 2 3 4 5 6 7 8 9");
+    }
+
+    #[test]
+    fn test_canonical_single_pass() {
+        // the single pass packing matches packing the full bit vector
+        let mut board = Board::standard_host();
+        assert_eq!(board.canonical(), Board::pack_bits(&board.bits()));
+
+        // and follows the ships as they move
+        let before = board.canonical();
+        board.destroyer = Ship::new(8, 0, true);
+        assert_ne!(board.canonical(), before);
+        assert_eq!(board.canonical(), Board::pack_bits(&board.bits()));
+    }
+
+    #[test]
+    fn test_hash_subset() {
+        let board = Board::standard_host();
+
+        // unsalted full fleet commits exactly like hash
+        assert_eq!(board.hash_subset(&[0, 1, 2, 3, 4], None).unwrap(), board.hash());
+
+        // a single ship, and salting, change the commitment
+        let carrier = board.hash_subset(&[0], None).unwrap();
+        assert_ne!(carrier, board.hash());
+        assert_ne!(board.hash_subset(&[0], Some([1, 2, 3, 4])).unwrap(), carrier);
+        assert_eq!(board.subset_bits(&[0]).unwrap().iter().filter(|bit| **bit).count(), 5);
+
+        // unknown ship
+        assert!(board.hash_subset(&[5], None).is_err());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let board = Board::standard_host();
+        let neighbors = board.neighbors();
+        assert!(!neighbors.is_empty());
+        for neighbor in neighbors.iter() {
+            neighbor.validate().unwrap();
+            let moved = board
+                .placement()
+                .iter()
+                .zip(neighbor.placement().iter())
+                .filter(|(before, after)| before != after)
+                .count();
+            assert_eq!(moved, 1);
+        }
+
+        // every relocation is enumerated once
+        let mut placements = neighbors
+            .iter()
+            .map(|neighbor| neighbor.placement())
+            .collect::<Vec<_>>();
+        placements.sort();
+        placements.dedup();
+        assert_eq!(placements.len(), neighbors.len());
+    }
 }
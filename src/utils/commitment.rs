@@ -0,0 +1,200 @@
+use {
+    crate::{
+        circuits::{game::board::BoardVerifier, CircuitError, ProofTuple, C, D, F, H},
+        utils::shot::serialize_shot,
+    },
+    anyhow::{ensure, Result},
+    plonky2::{
+        field::types::{Field, Field64, PrimeField64},
+        plonk::config::Hasher,
+    },
+};
+
+/**
+ * Render a board commitment as a canonical hex string
+ * @notice big-endian concatenation of the LE-serialized limbs (limb 3 first), each as 16 hex chars
+ *
+ * @param commitment - 256-bit commitment as a LE-serialized u64 array
+ * @return - 64 char lowercase hex string
+ */
+pub fn to_hex(commitment: [u64; 4]) -> String {
+    commitment
+        .iter()
+        .rev()
+        .map(|limb| format!("{:016x}", limb))
+        .collect::<String>()
+}
+
+/**
+ * Parse a canonical hex string back into a board commitment
+ *
+ * @param hex - 64 char hex string as produced by to_hex
+ * @return - 256-bit commitment as a LE-serialized u64 array
+ */
+pub fn from_hex(hex: &str) -> Result<[u64; 4]> {
+    ensure!(hex.len() == 64, "commitment hex must be 64 chars, got {}", hex.len());
+    ensure!(
+        hex.chars().all(|c| c.is_ascii_hexdigit()),
+        "commitment hex contains non-hex characters"
+    );
+    // parse limbs from most significant to least significant
+    let mut commitment = [0u64; 4];
+    for i in 0..4 {
+        commitment[3 - i] = u64::from_str_radix(&hex[i * 16..(i + 1) * 16], 16)?;
+    }
+    Ok(commitment)
+}
+
+/**
+ * Encode a board commitment as 32 bytes, e.g. for storage in an on-chain uint256/ bytes32
+ * @notice big-endian: limb 3 first, each limb big-endian (the byte decoding of to_hex)
+ *
+ * @param commitment - 256-bit commitment as a LE-serialized u64 array
+ * @return - 32 byte big-endian commitment
+ */
+pub fn commitment_to_bytes(commitment: [u64; 4]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for i in 0..4 {
+        bytes[i * 8..(i + 1) * 8].copy_from_slice(&commitment[3 - i].to_be_bytes());
+    }
+    bytes
+}
+
+/**
+ * Decode a board commitment from the 32 byte encoding produced by commitment_to_bytes
+ *
+ * @param bytes - 32 byte big-endian commitment
+ * @return - 256-bit commitment as a LE-serialized u64 array
+ */
+pub fn commitment_from_bytes(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut commitment = [0u64; 4];
+    for i in 0..4 {
+        commitment[3 - i] = u64::from_be_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap());
+    }
+    commitment
+}
+
+/**
+ * Verify a board proof against the trusted board circuit and check it commits to a board commitment posted as bytes
+ * @dev the verifier data bundled with the proof is not trusted, see BoardVerifier
+ *
+ * @param verifier - verifier trusting the board circuit
+ * @param proof - board validity proof
+ * @param commitment - 32 byte big-endian commitment as produced by commitment_to_bytes
+ * @return - UntrustedCircuit if the proof claims another circuit, error if the proof does not verify, or
+ *           CommitmentMismatch if it exports a different commitment
+ */
+pub fn verify_against_bytes(
+    verifier: &BoardVerifier,
+    proof: ProofTuple<F, C, D>,
+    commitment: &[u8; 32],
+) -> Result<()> {
+    let exported = verifier.verify(proof)?.commitment;
+    if exported != commitment_from_bytes(commitment) {
+        return Err(CircuitError::CommitmentMismatch.into());
+    }
+    Ok(())
+}
+
+/**
+ * Compute the salted commitment to a shot coordinate (native counterpart of gadgets::shot::commit_shot)
+ * @notice salt prevents grinding the 100 possible coordinates from a public commitment
+ *
+ * @param shot - the shot coordinate (x, y)
+ * @param salt - private salt as 4 field elements (reduced mod p)
+ * @return - poseidon hash of the serialized shot and salt, or None if the shot is off the board
+ */
+pub fn shot_commitment(shot: [u8; 2], salt: [u64; 4]) -> Option<[u64; 4]> {
+    // serialize shot coordinate (10y + x) followed by salt limbs
+    let mut inputs = vec![F::from_canonical_u8(serialize_shot(shot[0], shot[1])?)];
+    inputs.extend(salt.iter().map(|limb| F::from_noncanonical_u64(*limb)));
+    // hash preimage into 4 u64s
+    Some(
+        H::hash_no_pad(&inputs)
+            .elements
+            .iter()
+            .map(|x| x.to_canonical_u64())
+            .collect::<Vec<u64>>()
+            .try_into()
+            .unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{circuits::game::board::BoardCircuit, utils::board::Board};
+
+    #[test]
+    fn test_hex_round_trip() {
+        let commitment = [0u64, 1, u64::MAX, 0x0123456789abcdef];
+        let hex = to_hex(commitment);
+        assert_eq!(hex.len(), 64);
+        assert_eq!(from_hex(&hex).unwrap(), commitment);
+    }
+
+    #[test]
+    fn test_hex_known_commitment() {
+        let board = Board::standard_host();
+        let expected = "d4628fbadb854dcd61e52fbca405c53788bfe216332eb31c039986410ebc5564";
+        assert_eq!(to_hex(board.hash()), expected);
+        assert_eq!(from_hex(expected).unwrap(), board.hash());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let commitment = [0u64, 1, u64::MAX, 0x0123456789abcdef];
+        let bytes = commitment_to_bytes(commitment);
+        assert_eq!(commitment_from_bytes(&bytes), commitment);
+        // byte encoding is the decoding of the hex encoding
+        let hex = bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        assert_eq!(hex, to_hex(commitment));
+    }
+
+    #[test]
+    fn test_verify_against_bytes() {
+        let verifier = BoardVerifier::new().unwrap();
+        let board = Board::standard_host();
+        let bytes = commitment_to_bytes(board.hash());
+        let proof = BoardCircuit::prove_inner(board.clone()).unwrap();
+        verify_against_bytes(&verifier, proof.clone(), &bytes).unwrap();
+
+        // another board's commitment is rejected
+        let other = commitment_to_bytes(Board::standard_guest().hash());
+        let err = verify_against_bytes(&verifier, proof, &other).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::CommitmentMismatch));
+
+        // a proof of the same commitment from a variant of the board circuit is rejected
+        let variant = BoardCircuit::build_with_ordering(&BoardCircuit::config_inner().unwrap(), true)
+            .unwrap()
+            .prove_cached(board)
+            .unwrap();
+        let err = verify_against_bytes(&verifier, variant, &bytes).err().unwrap();
+        assert_eq!(err.downcast_ref::<CircuitError>(), Some(&CircuitError::UntrustedCircuit));
+    }
+
+    #[test]
+    fn test_shot_commitment_salted() {
+        let shot = [3u8, 4];
+        // same coordinate under different salts produces unlinkable commitments
+        assert_ne!(shot_commitment(shot, [1, 2, 3, 4]), shot_commitment(shot, [5, 6, 7, 8]));
+        // commitment is deterministic for a given coordinate and salt
+        assert_eq!(shot_commitment(shot, [1, 2, 3, 4]), shot_commitment(shot, [1, 2, 3, 4]));
+        // different coordinates under the same salt do not collide
+        assert_ne!(shot_commitment(shot, [1, 2, 3, 4]), shot_commitment([4, 3], [1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_shot_commitment_off_board() {
+        // (12, 0) would alias (2, 1) under 10y + x, so it has no commitment
+        assert_eq!(shot_commitment([12, 0], [1, 2, 3, 4]), None);
+        assert_eq!(shot_commitment([0, 10], [1, 2, 3, 4]), None);
+        assert!(shot_commitment([2, 1], [1, 2, 3, 4]).is_some());
+    }
+
+    #[test]
+    fn test_hex_malformed() {
+        assert!(from_hex("d4628fbadb854dcd").is_err());
+        assert!(from_hex(&"z".repeat(64)).is_err());
+    }
+}
@@ -1,17 +1,130 @@
-use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
-use plonky2_ecdsa::curve::{
-    curve_types::{Curve, CurveScalar},
-    ecdsa::{sign_message, ECDSAPublicKey, ECDSASecretKey, ECDSASignature},
-    secp256k1::Secp256K1,
+use {
+    super::biguint_from_array,
+    crate::circuits::{F, H},
+    plonky2::{
+        field::{
+            secp256k1_scalar::Secp256K1Scalar,
+            types::{Field, PrimeField, PrimeField64, Sample},
+        },
+        plonk::config::Hasher,
+    },
+    plonky2_ecdsa::curve::{
+        ecdsa::{sign_message, verify_message, ECDSAPublicKey, ECDSASecretKey, ECDSASignature},
+        secp256k1::Secp256K1,
+    },
 };
 
+pub type SecretKey = ECDSASecretKey<Secp256K1>;
+pub type PublicKey = ECDSAPublicKey<Secp256K1>;
+pub type Signature = ECDSASignature<Secp256K1>;
+
+/**
+ * Generate a random secp256k1 keypair
+ *
+ * @return - (secret key, public key)
+ */
 pub fn keypair() -> (SecretKey, PublicKey) {
-    let mut rng = rand::thread_rng();
     let sk = ECDSASecretKey::<Secp256K1>(Secp256K1Scalar::rand());
-    let pk = ECDSAPublicKey((CurveScalar(sk.0) * Curve::GENERATOR_PROJECTIVE).to_affine());
+    let pk = sk.to_public();
     (sk, pk)
 }
 
-pub fn sign(msg: Secp256K1Scalar, sk: ECDSASecretKey<Secp256K1>) -> ECDSASignature<Secp256K1> {
+/**
+ * Sign a message with a secret key
+ *
+ * @param msg - message as a secp256k1 scalar
+ * @param sk - secret key of the signer
+ * @return - ecdsa signature over the message
+ */
+pub fn sign(msg: Secp256K1Scalar, sk: SecretKey) -> Signature {
     sign_message(msg, sk)
 }
+
+/**
+ * Verify a signature over a message against a public key
+ *
+ * @param msg - message as a secp256k1 scalar
+ * @param signature - ecdsa signature over the message
+ * @param pk - public key of the signer
+ * @return - true if the signature is valid
+ */
+pub fn verify(msg: Secp256K1Scalar, signature: Signature, pk: PublicKey) -> bool {
+    verify_message(msg, signature, pk)
+}
+
+/**
+ * Convert a board commitment into the message signed to bind it to a player's key
+ *
+ * @param commitment - 256-bit board commitment as a LE-serialized u64 array
+ * @return - commitment reduced into a secp256k1 scalar
+ */
+pub fn commitment_message(commitment: [u64; 4]) -> Secp256K1Scalar {
+    Secp256K1Scalar::from_noncanonical_biguint(biguint_from_array(commitment))
+}
+
+/**
+ * Convert the outputs of a shot proof into the message signed to attest the shot result
+ * @notice poseidon hash of the board commitment limbs, serialized shot and hit bool, reduced into a secp256k1 scalar
+ *
+ * @param commitment - 256-bit board commitment as a LE-serialized u64 array
+ * @param shot - serialized shot coordinate (10y + x)
+ * @param hit - whether the shot hit a ship
+ * @return - shot result reduced into a secp256k1 scalar
+ */
+pub fn shot_message(commitment: [u64; 4], shot: u8, hit: bool) -> Secp256K1Scalar {
+    let mut inputs = commitment
+        .iter()
+        .map(|limb| F::from_canonical_u64(*limb))
+        .collect::<Vec<F>>();
+    inputs.push(F::from_canonical_u8(shot));
+    inputs.push(F::from_bool(hit));
+    let digest: [u64; 4] = H::hash_no_pad(&inputs)
+        .elements
+        .map(|x| x.to_canonical_u64());
+    commitment_message(digest)
+}
+
+/**
+ * Serialize a public key into the LE u32 limbs of its affine coordinates
+ *
+ * @param pk - public key
+ * @return - x coordinate limbs followed by y coordinate limbs
+ */
+pub fn public_key_limbs(pk: &PublicKey) -> [u32; 16] {
+    let mut limbs = [0u32; 16];
+    for (offset, coordinate) in [(0, pk.0.x), (8, pk.0.y)] {
+        for (i, digit) in coordinate.to_canonical_biguint().to_u32_digits().iter().enumerate() {
+            limbs[offset + i] = *digit;
+        }
+    }
+    limbs
+}
+
+/**
+ * Serialize a signature into the LE u32 limbs of its scalars
+ *
+ * @param signature - ecdsa signature
+ * @return - r limbs followed by s limbs
+ */
+pub fn signature_limbs(signature: &Signature) -> [u32; 16] {
+    let mut limbs = [0u32; 16];
+    for (offset, scalar) in [(0, signature.r), (8, signature.s)] {
+        for (i, digit) in scalar.to_canonical_biguint().to_u32_digits().iter().enumerate() {
+            limbs[offset + i] = *digit;
+        }
+    }
+    limbs
+}
+
+/**
+ * Compute the poseidon identifier of a public key used in place of the key inside circuits
+ *
+ * @param pk - public key
+ * @return - poseidon hash of the public key limbs as a LE-serialized u64 array
+ */
+pub fn key_id(pk: &PublicKey) -> [u64; 4] {
+    let inputs = public_key_limbs(pk).map(F::from_canonical_u32);
+    H::hash_no_pad(&inputs)
+        .elements
+        .map(|x| x.to_canonical_u64())
+}
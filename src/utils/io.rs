@@ -0,0 +1,108 @@
+use {
+    crate::utils::{board::Board, ship::Ship, shot::serialize_shot},
+    anyhow::{anyhow, ensure, Result},
+    serde::Deserialize,
+};
+
+// JSON input format for scripting games:
+//  - board: {"ships":[{"x":3,"y":4,"z":false}, ...]} listing carrier, battleship, cruiser, submarine, destroyer in order
+//  - shot: {"x":3,"y":4}
+// @dev z follows Ship: false = horizontal, true = vertical
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShipJson {
+    x: u8,
+    y: u8,
+    z: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BoardJson {
+    ships: Vec<ShipJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShotJson {
+    x: u8,
+    y: u8,
+}
+
+/**
+ * Parse a board from its JSON input format
+ *
+ * @param json - {"ships":[...]} listing the 5 ships in fleet order
+ * @return - board, or error if the json is malformed or the board is not a valid placement
+ */
+pub fn parse_board_json(json: &str) -> Result<Board> {
+    let parsed: BoardJson =
+        serde_json::from_str(json).map_err(|e| anyhow!("malformed board json: {}", e))?;
+    ensure!(
+        parsed.ships.len() == 5,
+        "board json must list 5 ships (carrier, battleship, cruiser, submarine, destroyer), got {}",
+        parsed.ships.len()
+    );
+    let s = &parsed.ships;
+    let board = Board::new(
+        Ship::new(s[0].x, s[0].y, s[0].z),
+        Ship::new(s[1].x, s[1].y, s[1].z),
+        Ship::new(s[2].x, s[2].y, s[2].z),
+        Ship::new(s[3].x, s[3].y, s[3].z),
+        Ship::new(s[4].x, s[4].y, s[4].z),
+    );
+    board.validate()?;
+    Ok(board)
+}
+
+/**
+ * Parse a shot coordinate from its JSON input format
+ *
+ * @param json - {"x":..,"y":..}
+ * @return - the shot coordinate (x, y), or error if the json is malformed or the shot is off the board
+ */
+pub fn parse_shot_json(json: &str) -> Result<[u8; 2]> {
+    let parsed: ShotJson =
+        serde_json::from_str(json).map_err(|e| anyhow!("malformed shot json: {}", e))?;
+    serialize_shot(parsed.x, parsed.y)
+        .ok_or_else(|| anyhow!("shot ({}, {}) is off the board", parsed.x, parsed.y))?;
+    Ok([parsed.x, parsed.y])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const STANDARD_HOST_JSON: &str = r#"{"ships":[
+        {"x":3,"y":4,"z":false},
+        {"x":9,"y":6,"z":true},
+        {"x":0,"y":0,"z":false},
+        {"x":0,"y":6,"z":false},
+        {"x":6,"y":1,"z":true}
+    ]}"#;
+
+    #[test]
+    fn test_parse_board_json() {
+        let board = parse_board_json(STANDARD_HOST_JSON).unwrap();
+        assert_eq!(board.hash(), Board::standard_host().hash());
+        assert_eq!(parse_shot_json(r#"{"x":3,"y":4}"#).unwrap(), [3, 4]);
+    }
+
+    #[test]
+    fn test_parse_json_malformed() {
+        // syntax error
+        let err = parse_board_json(r#"{"ships":[{"x":3,"y":4,"z":false}"#).err().unwrap();
+        assert!(err.to_string().starts_with("malformed board json"));
+        // wrong number of ships
+        let err = parse_board_json(r#"{"ships":[{"x":3,"y":4,"z":false}]}"#).err().unwrap();
+        assert!(err.to_string().contains("must list 5 ships"));
+        // valid json but overlapping ships
+        let overlapping = STANDARD_HOST_JSON.replace(r#"{"x":0,"y":6,"z":false}"#, r#"{"x":0,"y":0,"z":false}"#);
+        assert!(parse_board_json(&overlapping).is_err());
+        // unknown field and off-board shot
+        assert!(parse_shot_json(r#"{"x":3,"y":4,"hit":true}"#).is_err());
+        let err = parse_shot_json(r#"{"x":10,"y":4}"#).err().unwrap();
+        assert!(err.to_string().contains("off the board"));
+    }
+}
@@ -0,0 +1,272 @@
+use {
+    crate::circuits::{F, H},
+    anyhow::{ensure, Result},
+    plonky2::{
+        field::types::{Field, PrimeField64},
+        hash::{
+            hash_types::HashOut,
+            merkle_proofs::{verify_merkle_proof, MerkleProof},
+            merkle_tree::MerkleTree,
+        },
+        plonk::config::Hasher,
+    },
+};
+
+// Merkle-committed board for boards too large to decompose in circuit
+// @dev each cell (index = side * y + x) is a leaf holding its occupied bit; leaves are padded with empty cells to a
+//      power of two. the commitment is the Merkle root, and a shot is checked with a single Merkle opening
+
+pub struct MerkleBoard {
+    pub side: usize,            // width/ height of the square board
+    pub tree: MerkleTree<F, H>, // Merkle tree over the occupied bit of every cell
+}
+
+impl MerkleBoard {
+    /**
+     * Commit to a square board of occupied cells
+     *
+     * @param side - width/ height of the board
+     * @param bits - occupied bit of every cell (index = side * y + x)
+     * @return - Merkle-committed board
+     */
+    pub fn new(side: usize, bits: &[bool]) -> Result<MerkleBoard> {
+        ensure!(side > 0, "board must have at least one cell");
+        ensure!(bits.len() == side * side, "expected {} cells, got {}", side * side, bits.len());
+
+        // one leaf per cell, padded with empty cells to a power of two
+        let mut leaves = bits
+            .iter()
+            .map(|occupied| vec![F::from_bool(*occupied)])
+            .collect::<Vec<Vec<F>>>();
+        leaves.resize(leaves.len().next_power_of_two(), vec![F::ZERO]);
+
+        Ok(Self {
+            side,
+            tree: MerkleTree::new(leaves, 0),
+        })
+    }
+
+    /**
+     * Number of levels between a leaf and the root
+     *
+     * @return - Merkle tree depth
+     */
+    pub fn depth(&self) -> usize {
+        MerkleBoard::depth_for(self.side)
+    }
+
+    /**
+     * Number of levels between a leaf and the root for a board of a given size
+     *
+     * @param side - width/ height of the board
+     * @return - Merkle tree depth
+     */
+    pub fn depth_for(side: usize) -> usize {
+        (side * side).next_power_of_two().trailing_zeros() as usize
+    }
+
+    /**
+     * Return the board commitment
+     *
+     * @return - Merkle root as 4 u64s
+     */
+    pub fn root(&self) -> [u64; 4] {
+        self.tree.cap.0[0].elements.map(|element| element.to_canonical_u64())
+    }
+
+    /**
+     * Serialize a coordinate into a cell index
+     *
+     * @param shot - the shot coordinate (x, y)
+     * @return - serialized cell index (side * y + x)
+     */
+    pub fn index(&self, shot: [usize; 2]) -> usize {
+        shot[1] * self.side + shot[0]
+    }
+
+    /**
+     * Read whether a cell is occupied
+     *
+     * @param shot - the shot coordinate (x, y)
+     * @return - true if the cell is occupied by a ship
+     */
+    pub fn occupied(&self, shot: [usize; 2]) -> bool {
+        self.tree.get(self.index(shot))[0] == F::ONE
+    }
+
+    /**
+     * Open the Merkle commitment at a cell
+     *
+     * @param shot - the shot coordinate (x, y)
+     * @return - Merkle proof of the cell's leaf against the root
+     */
+    pub fn open(&self, shot: [usize; 2]) -> MerkleProof<F, H> {
+        self.tree.prove(self.index(shot))
+    }
+}
+
+// Cell-committed board for selective reveal of individual cells (alternative to gadgets::board::hash_board)
+// @dev each leaf is a salted commitment to one cell's occupied bit, so an opening reveals only its own cell:
+//      sibling hashes are commitments to other cells and hide their bits. every cell salt is derived from a
+//      single board salt (cell salt = H(board salt, index)) so revealing a cell salt reveals no other cell salt
+
+// depth of the cell commitment tree over the 100 cells of a standard board (padded to 128 leaves)
+pub const CELL_TREE_DEPTH: usize = 7;
+
+/**
+ * Derive the salt of a single cell from the board salt
+ *
+ * @param salt - private board salt as 4 field elements (reduced mod p)
+ * @param index - serialized cell index (10y + x)
+ * @return - cell salt
+ */
+pub fn cell_salt(salt: [u64; 4], index: usize) -> [u64; 4] {
+    let mut inputs = salt.iter().map(|limb| F::from_noncanonical_u64(*limb)).collect::<Vec<F>>();
+    inputs.push(F::from_canonical_usize(index));
+    H::hash_no_pad(&inputs).elements.map(|x| x.to_canonical_u64())
+}
+
+/**
+ * Compute the salted commitment to a cell (native counterpart of gadgets::board::open_cell)
+ *
+ * @param occupied - whether the cell is occupied by a ship
+ * @param cell_salt - salt of the cell
+ * @return - leaf data of the cell: poseidon hash of the occupied bit and cell salt
+ */
+pub fn cell_commitment(occupied: bool, cell_salt: [u64; 4]) -> Vec<F> {
+    let mut inputs = vec![F::from_bool(occupied)];
+    inputs.extend(cell_salt.iter().map(|limb| F::from_noncanonical_u64(*limb)));
+    H::hash_no_pad(&inputs).elements.to_vec()
+}
+
+// Opening of a single cell of a cell-committed board
+pub struct CellOpening {
+    pub index: usize,             // serialized cell index (10y + x)
+    pub occupied: bool,           // revealed occupied bit
+    pub salt: [u64; 4],           // revealed cell salt
+    pub proof: MerkleProof<F, H>, // sibling commitments from the leaf up to the root
+}
+
+impl CellOpening {
+    /**
+     * Natively verify the opening against a board root
+     *
+     * @param root - Merkle root of the cell commitments
+     * @return - error if the opening does not hash to the root
+     */
+    pub fn verify(&self, root: [u64; 4]) -> Result<()> {
+        let root = HashOut {
+            elements: root.map(F::from_canonical_u64),
+        };
+        verify_merkle_proof(cell_commitment(self.occupied, self.salt), self.index, root, &self.proof)
+    }
+}
+
+// Merkle tree over the salted cell commitments of a standard 10x10 board
+pub struct CellCommittedBoard {
+    pub tree: MerkleTree<F, H>,
+    bits: [bool; 100],
+    salt: [u64; 4],
+}
+
+impl CellCommittedBoard {
+    /**
+     * Commit to every cell of a board under a board salt
+     *
+     * @param bits - occupied bit of every cell (index = 10y + x)
+     * @param salt - private board salt
+     * @return - cell-committed board
+     */
+    pub fn new(bits: [bool; 100], salt: [u64; 4]) -> CellCommittedBoard {
+        // one salted leaf per cell, padded with zero leaves to a power of two
+        let mut leaves = bits
+            .iter()
+            .enumerate()
+            .map(|(index, occupied)| cell_commitment(*occupied, cell_salt(salt, index)))
+            .collect::<Vec<Vec<F>>>();
+        leaves.resize(1 << CELL_TREE_DEPTH, vec![F::ZERO; 4]);
+        Self {
+            tree: MerkleTree::new(leaves, 0),
+            bits,
+            salt,
+        }
+    }
+
+    /**
+     * Return the board commitment
+     *
+     * @return - Merkle root as 4 u64s
+     */
+    pub fn root(&self) -> [u64; 4] {
+        self.tree.cap.0[0].elements.map(|element| element.to_canonical_u64())
+    }
+
+    /**
+     * Open the commitment at a single cell
+     *
+     * @param index - serialized cell index (10y + x)
+     * @return - opening revealing only the cell's occupied bit and salt
+     */
+    pub fn open(&self, index: usize) -> Result<CellOpening> {
+        ensure!(index < 100, "cell {} is off the board", index);
+        Ok(CellOpening {
+            index,
+            occupied: self.bits[index],
+            salt: cell_salt(self.salt, index),
+            proof: self.tree.prove(index),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use plonky2::hash::{hash_types::HashOut, merkle_proofs::verify_merkle_proof};
+
+    #[test]
+    fn test_merkle_board_opening() {
+        // 20x20 board (400 cells padded to 512 leaves) with a single occupied cell
+        let mut bits = vec![false; 400];
+        bits[7 * 20 + 13] = true;
+        let board = MerkleBoard::new(20, &bits).unwrap();
+        assert_eq!(board.depth(), 9);
+        assert!(board.occupied([13, 7]));
+        assert!(!board.occupied([7, 13]));
+
+        // opening verifies natively against the root
+        let root = HashOut {
+            elements: board.root().map(F::from_canonical_u64),
+        };
+        let proof = board.open([13, 7]);
+        verify_merkle_proof(vec![F::ONE], board.index([13, 7]), root, &proof).unwrap();
+        assert!(verify_merkle_proof(vec![F::ZERO], board.index([13, 7]), root, &proof).is_err());
+
+        // cell count must match the board size
+        assert!(MerkleBoard::new(20, &bits[1..]).is_err());
+    }
+
+    #[test]
+    fn test_cell_opening() {
+        let board = crate::utils::board::Board::standard_host();
+        let salt = [1, 2, 3, 4];
+        let committed = CellCommittedBoard::new(board.bits(), salt);
+        assert_eq!(board.merkle_root(salt), committed.root());
+        // root hides the board: another salt commits to the same board under another root
+        assert_ne!(board.merkle_root([5, 6, 7, 8]), committed.root());
+
+        // carrier head (3, 4) opens as occupied, (5, 5) as empty
+        let opening = committed.open(43).unwrap();
+        assert!(opening.occupied);
+        opening.verify(committed.root()).unwrap();
+        let empty = committed.open(55).unwrap();
+        assert!(!empty.occupied);
+        empty.verify(committed.root()).unwrap();
+
+        // tampered bit fails to open against the root
+        let tampered = CellOpening {
+            occupied: false,
+            ..opening
+        };
+        assert!(tampered.verify(committed.root()).is_err());
+    }
+}
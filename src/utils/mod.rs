@@ -2,7 +2,13 @@ use num::bigint::BigUint;
 
 pub mod ship;
 pub mod board;
-// pub mod ecdsa;
+pub mod commitment;
+pub mod merkle_board;
+pub mod shot;
+pub mod sim;
+pub mod transcript;
+pub mod ecdsa;
+pub mod io;
 
 pub fn biguint_from_array(arr: [u64; 4]) -> BigUint {
     BigUint::from_slice(&[
@@ -1,3 +1,5 @@
+use anyhow::{ensure, Result};
+
 #[derive(Debug, Clone)]
 pub struct Ship<const L: usize> {
     pub x: u8,
@@ -6,6 +8,9 @@ pub struct Ship<const L: usize> {
 }
 
 impl<const L: usize> Ship<L> {
+    // number of cells occupied by the ship
+    pub const LENGTH: usize = L;
+
     /**
      * Instantiate a new ship object
      *
@@ -18,6 +23,46 @@ impl<const L: usize> Ship<L> {
         Self { x, y, z }
     }
 
+    /**
+     * Instantiate a ship from the coordinates of its two end cells
+     * @notice endpoints may be given in either order; the head is the endpoint with the lower coordinate
+     *
+     * @param a - (x, y) of one end of the ship
+     * @param b - (x, y) of the other end of the ship
+     * @return - ship spanning the endpoints, or error if they are off the board, not axis-aligned or not L apart
+     */
+    pub fn from_endpoints(a: [u8; 2], b: [u8; 2]) -> Result<Ship<L>> {
+        ensure!(
+            a.iter().chain(b.iter()).all(|coordinate| *coordinate < 10),
+            "endpoints ({}, {}) and ({}, {}) must lie on the board",
+            a[0],
+            a[1],
+            b[0],
+            b[1]
+        );
+        ensure!(
+            a[0] == b[0] || a[1] == b[1],
+            "endpoints ({}, {}) and ({}, {}) are not axis-aligned",
+            a[0],
+            a[1],
+            b[0],
+            b[1]
+        );
+        // a single-cell ship has no orientation; treat it as horizontal
+        let z = a[0] == b[0] && a[1] != b[1];
+        let (head, tail) = if z {
+            (a[1].min(b[1]), a[1].max(b[1]))
+        } else {
+            (a[0].min(b[0]), a[0].max(b[0]))
+        };
+        let length = (tail - head) as usize + 1;
+        ensure!(length == L, "endpoints span {} cells, expected {}", length, L);
+        Ok(match z {
+            true => Ship::new(a[0], head, true),
+            false => Ship::new(head, a[1], false),
+        })
+    }
+
     /**
      * Return the indexes of coordiantes that the ship occupies
      * @notice "index of coordinate" means the serialization of (x, y) into (y * 10 + x)
@@ -35,7 +80,100 @@ impl<const L: usize> Ship<L> {
         coordinates
     }
 
+    /**
+     * Return the (x, y) cells the ship would occupy, e.g. to preview its footprint before committing a board
+     * @notice unlike coordinates() the cells are not serialized and the placement is range checked
+     *
+     * @return - (x, y) of every cell from head to tail, or error if any cell falls off the board
+     */
+    pub fn preview_coordinates(&self) -> Result<[[u8; 2]; L]> {
+        let (x, y) = (self.x as usize, self.y as usize);
+        let tail = if self.z { (x, y + L - 1) } else { (x + L - 1, y) };
+        ensure!(
+            tail.0 < 10 && tail.1 < 10,
+            "ship at ({}, {}) of length {} runs off the board",
+            self.x,
+            self.y,
+            L
+        );
+        let mut cells = [[0; 2]; L];
+        for (i, cell) in cells.iter_mut().enumerate() {
+            let i = i as u8;
+            *cell = if self.z { [self.x, self.y + i] } else { [self.x + i, self.y] };
+        }
+        Ok(cells)
+    }
+
     pub fn canonical(&self) -> (u8, u8, bool) {
         (self.x, self.y, self.z)
     }
+
+    /**
+     * Apply one of the 8 symmetries of the 10x10 grid (dihedral group D4) to the ship
+     * @notice symmetry % 4 = number of 90 degree rotations, symmetry >= 4 = mirror x before rotating
+     * @dev head and orientation are re-detected from the transformed coordinates
+     *
+     * @param symmetry - index of the transform in 0..8
+     * @return - ship occupying the transformed coordinates
+     */
+    pub fn transform(&self, symmetry: u8) -> Ship<L> {
+        // transform every occupied (x, y)
+        let cells = (0..L as u8)
+            .map(|i| {
+                let (mut x, mut y) = if self.z {
+                    (self.x, self.y + i)
+                } else {
+                    (self.x + i, self.y)
+                };
+                if symmetry >= 4 {
+                    x = 9 - x;
+                }
+                for _ in 0..symmetry % 4 {
+                    (x, y) = (9 - y, x);
+                }
+                (x, y)
+            })
+            .collect::<Vec<(u8, u8)>>();
+        // head is the lowest coordinate, ship is vertical if every cell shares x
+        let x = cells.iter().map(|cell| cell.0).min().unwrap();
+        let y = cells.iter().map(|cell| cell.1).min().unwrap();
+        let z = cells.iter().all(|cell| cell.0 == x);
+        Ship::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_endpoints() {
+        // horizontal carrier given tail first
+        let carrier = Ship::<5>::from_endpoints([7, 4], [3, 4]).unwrap();
+        assert_eq!(carrier.canonical(), (3, 4, false));
+
+        // vertical battleship
+        let battleship = Ship::<4>::from_endpoints([9, 6], [9, 9]).unwrap();
+        assert_eq!(battleship.canonical(), (9, 6, true));
+        assert_eq!(battleship.coordinates(), [69, 79, 89, 99]);
+    }
+
+    #[test]
+    fn test_from_endpoints_invalid() {
+        // diagonal
+        assert!(Ship::<3>::from_endpoints([0, 0], [2, 2]).is_err());
+        // length mismatch
+        assert!(Ship::<3>::from_endpoints([0, 0], [3, 0]).is_err());
+        // off the board
+        assert!(Ship::<2>::from_endpoints([9, 0], [10, 0]).is_err());
+    }
+
+    #[test]
+    fn test_preview_coordinates() {
+        let cruiser = Ship::<3>::new(0, 5, true);
+        assert_eq!(cruiser.preview_coordinates().unwrap(), [[0, 5], [0, 6], [0, 7]]);
+
+        // a vertical battleship starting at y = 7 would cover y = 10
+        assert!(Ship::<4>::new(2, 7, true).preview_coordinates().is_err());
+    }
 }
@@ -0,0 +1,134 @@
+use anyhow::{anyhow, ensure, Result};
+
+/**
+ * Serialize a shot coordinate into its board index (native counterpart of gadgets::shot::serialize_shot)
+ * @notice returns None where the circuit's range check fails, so clients can reject a shot before witnessing it
+ *
+ * @param x - x coordinate of shot
+ * @param y - y coordinate of shot
+ * @return - serialized shot coordinate (10y + x), or None if either coordinate is off the board
+ */
+pub fn serialize_shot(x: u8, y: u8) -> Option<u8> {
+    match x < 10 && y < 10 {
+        true => Some(y * 10 + x),
+        false => None,
+    }
+}
+
+/**
+ * Native record of the coordinates fired upon on one board
+ * @notice complements the in-circuit replay prevention, e.g. for an AI opponent choosing its next shot
+ * @dev 100 bit fired set indexed by serialized shot (10y + x)
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShotTracker {
+    fired: u128,
+}
+
+impl ShotTracker {
+    /**
+     * Instantiate a tracker where no coordinate has been fired upon
+     *
+     * @return - empty shot tracker
+     */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Record a shot at a coordinate
+     *
+     * @param coord - the shot coordinate (x, y)
+     * @return - error if the coordinate is off the board or was already fired upon
+     */
+    pub fn fire(&mut self, coord: [u8; 2]) -> Result<()> {
+        let index = serialize_shot(coord[0], coord[1])
+            .ok_or_else(|| anyhow!("shot ({}, {}) is off the board", coord[0], coord[1]))?;
+        ensure!(
+            !self.is_fired(coord),
+            "shot ({}, {}) was already fired",
+            coord[0],
+            coord[1]
+        );
+        self.fired |= 1 << index;
+        Ok(())
+    }
+
+    /**
+     * Check whether a coordinate has been fired upon
+     *
+     * @param coord - the shot coordinate (x, y)
+     * @return - true if the coordinate was fired upon (off board coordinates are never fired upon)
+     */
+    pub fn is_fired(&self, coord: [u8; 2]) -> bool {
+        match serialize_shot(coord[0], coord[1]) {
+            Some(index) => self.fired >> index & 1 == 1,
+            None => false,
+        }
+    }
+
+    /**
+     * Return every coordinate not yet fired upon
+     *
+     * @return - remaining shot coordinates (x, y) in serialized order
+     */
+    pub fn remaining(&self) -> Vec<[u8; 2]> {
+        (0..100u8)
+            .map(|index| [index % 10, index / 10])
+            .filter(|coord| !self.is_fired(*coord))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{circuits::game::shot::ShotCircuit, utils::board::Board};
+
+    #[test]
+    fn test_serialize_shot_matches_circuit() {
+        // build the shot circuit once and prove every on-board coordinate
+        let circuit = ShotCircuit::build(&ShotCircuit::config_inner().unwrap()).unwrap();
+        let board = Board::standard_host();
+        for y in 0..10u8 {
+            for x in 0..10u8 {
+                let proof = circuit.prove_cached(board.clone(), [x, y]).unwrap();
+                let output = ShotCircuit::decode_public(proof.0).unwrap();
+                assert_eq!(serialize_shot(x, y), Some(output.shot));
+            }
+        }
+    }
+
+    #[test]
+    fn test_serialize_shot_off_board() {
+        // (12, 0) would alias (2, 1) without the range check
+        assert_eq!(serialize_shot(12, 0), None);
+        assert_eq!(serialize_shot(0, 10), None);
+        assert_eq!(serialize_shot(10, 10), None);
+        assert_eq!(serialize_shot(u8::MAX, 0), None);
+        assert_eq!(serialize_shot(9, 9), Some(99));
+    }
+
+    #[test]
+    fn test_shot_tracker_remaining() {
+        let mut tracker = ShotTracker::new();
+        assert_eq!(tracker.remaining().len(), 100);
+
+        // each shot removes exactly its coordinate from the remaining shots
+        let shots = [[0, 0], [9, 9], [3, 7], [7, 3]];
+        for (i, shot) in shots.iter().enumerate() {
+            assert!(!tracker.is_fired(*shot));
+            tracker.fire(*shot).unwrap();
+            assert!(tracker.is_fired(*shot));
+            let remaining = tracker.remaining();
+            assert_eq!(remaining.len(), 100 - i - 1);
+            assert!(!remaining.contains(shot));
+        }
+        assert!(tracker.remaining().contains(&[1, 0]));
+
+        // repeated and off board shots are rejected without changing the fired set
+        assert!(tracker.fire([3, 7]).is_err());
+        assert!(tracker.fire([10, 0]).is_err());
+        assert_eq!(tracker.remaining().len(), 96);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    circuits::channel::close_channel::WIN_THRESHOLD,
+    utils::{board::Board, shot::serialize_shot},
+};
+
+/**
+ * Natively replay a shot log to find the winner of a channel
+ * @notice mirrors the channel circuits: every hit increments damage (repeat hits on a cell are counted again)
+ * @dev turn convention matches the circuits: true = shot checked against the guest board, false = host board
+ *
+ * @param host - host board configuration
+ * @param guest - guest board configuration
+ * @param moves - ordered (turn, shot coordinate) log
+ * @return - winner in the close circuit's convention (true = guest, false = host), or None if no board is sunk
+ *           (a log with an off-board shot never finishes a game since the circuits cannot prove the shot)
+ */
+pub fn resolve(host: &Board, guest: &Board, moves: &[(bool, [u8; 2])]) -> Option<bool> {
+    let host_bits = host.bits();
+    let guest_bits = guest.bits();
+    let mut host_damage = 0u8;
+    let mut guest_damage = 0u8;
+    for (turn, shot) in moves {
+        let coordinate = serialize_shot(shot[0], shot[1])? as usize;
+        if *turn {
+            // shot at guest board: guest sunk means host wins
+            guest_damage += guest_bits[coordinate] as u8;
+            if guest_damage == WIN_THRESHOLD {
+                return Some(false);
+            }
+        } else {
+            // shot at host board: host sunk means guest wins
+            host_damage += host_bits[coordinate] as u8;
+            if host_damage == WIN_THRESHOLD {
+                return Some(true);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_resolve_host_sunk() {
+        let host = Board::standard_host();
+        let guest = Board::standard_guest();
+
+        // alternate shots at the guest and host boards, hitting every host coordinate
+        let mut moves = Vec::<(bool, [u8; 2])>::new();
+        for coordinate in host.occupied_coordinates() {
+            let shot = [coordinate % 10, coordinate / 10];
+            moves.push((true, shot));
+            moves.push((false, shot));
+        }
+
+        // guest wins once the final host coordinate is hit
+        assert_eq!(resolve(&host, &guest, &moves), Some(true));
+        // log stopping short of the final hit does not finish the game
+        assert_eq!(resolve(&host, &guest, &moves[..moves.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_resolve_guest_sunk() {
+        let host = Board::standard_host();
+        let guest = Board::standard_guest();
+
+        // only shots at the guest board
+        let moves = guest
+            .occupied_coordinates()
+            .iter()
+            .map(|coordinate| (true, [coordinate % 10, coordinate / 10]))
+            .collect::<Vec<(bool, [u8; 2])>>();
+        assert_eq!(resolve(&host, &guest, &moves), Some(false));
+    }
+}
@@ -0,0 +1,257 @@
+use {
+    crate::{
+        circuits::{
+            channel::{close_channel::WIN_THRESHOLD, GameState},
+            game::board::UNBOUND_NONCE,
+        },
+        utils::{board::Board, shot::serialize_shot},
+    },
+    anyhow::{anyhow, ensure, Result},
+};
+
+// BattleZips Game Transcript: compact archive of a finished (or abandoned) game without its proofs
+//  - opening: host and guest board commitments
+//  - shots: every shot in order with its hit/ miss result, alternating guest board, host board
+//    (starting with the host's opening shot, following the turn convention of the channel circuits)
+//  - winner: recorded outcome in the close circuit's convention (true = guest, false = host)
+// Byte layout (version 1):
+//  [0] version | [1..33] host commitment (LE u64 limbs) | [33..65] guest commitment | [65] winner
+//  (0 = none, 1 = host, 2 = guest) | [66..68] LE u16 shot count | 1 byte per shot (hit << 7 | 10y + x)
+
+// version byte of the transcript layout produced by to_bytes
+pub const TRANSCRIPT_VERSION: u8 = 1;
+
+// byte length of the transcript header preceding the shots
+pub const TRANSCRIPT_HEADER_BYTES: usize = 68;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameTranscript {
+    pub host: [u64; 4],
+    pub guest: [u64; 4],
+    pub shots: Vec<([u8; 2], bool)>, // (shot coordinate, hit)
+    pub winner: Option<bool>,
+}
+
+impl GameTranscript {
+    /**
+     * Record the transcript of a shot log played between two boards
+     * @notice shot log follows the convention of prove_channel_with_progress; shots after a board is sunk are dropped
+     *
+     * @param host - host board configuration
+     * @param guest - guest board configuration
+     * @param shots - ordered shot log alternating guest board, host board (starting with the host's opening shot)
+     * @return - transcript of the game, or error if a shot is off the board
+     */
+    pub fn record(host: &Board, guest: &Board, shots: &[[u8; 2]]) -> Result<GameTranscript> {
+        let host_bits = host.bits();
+        let guest_bits = guest.bits();
+        let mut transcript = GameTranscript {
+            host: host.hash(),
+            guest: guest.hash(),
+            shots: Vec::new(),
+            winner: None,
+        };
+        for (k, shot) in shots.iter().enumerate() {
+            let coordinate = serialize_shot(shot[0], shot[1])
+                .ok_or_else(|| anyhow!("shot ({}, {}) is off the board", shot[0], shot[1]))?;
+            // even shots are checked against the guest board, odd shots against the host board
+            let bits = if k % 2 == 0 { guest_bits } else { host_bits };
+            transcript.shots.push((*shot, bits[coordinate as usize]));
+            let state = transcript.replay_shots()?;
+            if let Some(winner) = winner_of(&state) {
+                transcript.winner = Some(winner);
+                break;
+            }
+        }
+        Ok(transcript)
+    }
+
+    /**
+     * Natively re-derive the game state from the recorded shots and check it against the recorded winner
+     *
+     * @return - game state once every recorded shot is applied, or error if the transcript is inconsistent
+     */
+    pub fn replay(&self) -> Result<GameState> {
+        let state = self.replay_shots()?;
+        ensure!(
+            winner_of(&state) == self.winner,
+            "replayed winner does not match the recorded winner"
+        );
+        Ok(state)
+    }
+
+    /**
+     * Apply the recorded shots to the opening state
+     * @dev the state's pending shot is the next recorded shot, or the final shot once all are applied
+     *
+     * @return - game state once every recorded shot is applied, or error if a shot is invalid or follows a win
+     */
+    fn replay_shots(&self) -> Result<GameState> {
+        let serialize = |shot: &[u8; 2]| {
+            serialize_shot(shot[0], shot[1])
+                .ok_or_else(|| anyhow!("shot ({}, {}) is off the board", shot[0], shot[1]))
+        };
+        let opening = match self.shots.first() {
+            Some((shot, _)) => serialize(shot)?,
+            None => 0,
+        };
+        // channel open: host's opening shot is pending against the guest board
+        let mut state = GameState {
+            host: self.host,
+            guest: self.guest,
+            host_damage: 0,
+            guest_damage: 0,
+            turn: true,
+            shot: opening,
+            moves: 0,
+            resolved_shot: 0,
+            resolved_hit: false,
+            // transcripts do not record which game the boards were bound to
+            nonce: UNBOUND_NONCE,
+            match_index: 0,
+        };
+        for k in 0..self.shots.len() {
+            ensure!(winner_of(&state).is_none(), "shot {} recorded after the game ended", k);
+            let next_shot = match self.shots.get(k + 1) {
+                Some((shot, _)) => serialize(shot)?,
+                None => state.shot,
+            };
+            state = state.next(self.shots[k].1, next_shot);
+        }
+        Ok(state)
+    }
+
+    /**
+     * Serialize the transcript into the versioned byte layout
+     *
+     * @return - serialized transcript
+     */
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        ensure!(self.shots.len() <= u16::MAX as usize, "transcript has too many shots");
+        let mut bytes = Vec::with_capacity(TRANSCRIPT_HEADER_BYTES + self.shots.len());
+        bytes.push(TRANSCRIPT_VERSION);
+        for limb in self.host.iter().chain(self.guest.iter()) {
+            bytes.extend_from_slice(&limb.to_le_bytes());
+        }
+        bytes.push(match self.winner {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        });
+        bytes.extend_from_slice(&(self.shots.len() as u16).to_le_bytes());
+        for (shot, hit) in self.shots.iter() {
+            let coordinate = serialize_shot(shot[0], shot[1])
+                .ok_or_else(|| anyhow!("shot ({}, {}) is off the board", shot[0], shot[1]))?;
+            bytes.push((*hit as u8) << 7 | coordinate);
+        }
+        Ok(bytes)
+    }
+
+    /**
+     * Deserialize a transcript from the versioned byte layout produced by to_bytes
+     *
+     * @param bytes - serialized transcript
+     * @return - transcript, or error if the version is unknown or the layout is malformed
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<GameTranscript> {
+        ensure!(
+            bytes.len() >= TRANSCRIPT_HEADER_BYTES,
+            "transcript must be at least {} bytes, got {}",
+            TRANSCRIPT_HEADER_BYTES,
+            bytes.len()
+        );
+        ensure!(
+            bytes[0] == TRANSCRIPT_VERSION,
+            "unsupported transcript version {}",
+            bytes[0]
+        );
+        let limb = |offset: usize| u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        let winner = match bytes[65] {
+            0 => None,
+            1 => Some(false),
+            2 => Some(true),
+            byte => return Err(anyhow!("invalid transcript winner byte {}", byte)),
+        };
+        let count = u16::from_le_bytes(bytes[66..68].try_into().unwrap()) as usize;
+        ensure!(
+            bytes.len() == TRANSCRIPT_HEADER_BYTES + count,
+            "transcript declares {} shots but holds {}",
+            count,
+            bytes.len() - TRANSCRIPT_HEADER_BYTES
+        );
+        let mut shots = Vec::with_capacity(count);
+        for byte in bytes[TRANSCRIPT_HEADER_BYTES..].iter() {
+            let coordinate = byte & 0x7f;
+            ensure!(coordinate < 100, "transcript shot {} is off the board", coordinate);
+            shots.push(([coordinate % 10, coordinate / 10], byte >> 7 == 1));
+        }
+        Ok(GameTranscript {
+            host: [limb(1), limb(9), limb(17), limb(25)],
+            guest: [limb(33), limb(41), limb(49), limb(57)],
+            shots,
+            winner,
+        })
+    }
+}
+
+/**
+ * Return the winner of a game state in the close circuit's convention
+ *
+ * @param state - game state
+ * @return - true if the guest sunk the host, false if the host sunk the guest, None if no board is sunk
+ */
+fn winner_of(state: &GameState) -> Option<bool> {
+    if state.host_damage == WIN_THRESHOLD {
+        Some(true)
+    } else if state.guest_damage == WIN_THRESHOLD {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::sim::resolve;
+
+    #[test]
+    fn test_transcript_round_trip() {
+        let host = Board::standard_host();
+        let guest = Board::standard_guest();
+
+        // full game: host hits every guest coordinate while the guest shoots the same empty cell
+        let mut shots = Vec::<[u8; 2]>::new();
+        for coordinate in guest.occupied_coordinates() {
+            shots.push([coordinate % 10, coordinate / 10]);
+            shots.push([5, 5]);
+        }
+        let transcript = GameTranscript::record(&host, &guest, &shots).unwrap();
+        // trailing guest shot after the final hit is dropped
+        assert_eq!(transcript.shots.len(), shots.len() - 1);
+        assert_eq!(transcript.winner, Some(false));
+
+        // round trip through bytes
+        let bytes = transcript.to_bytes().unwrap();
+        assert_eq!(bytes.len(), TRANSCRIPT_HEADER_BYTES + transcript.shots.len());
+        let decoded = GameTranscript::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, transcript);
+
+        // replayed winner matches the recorded winner and the native simulation
+        let state = decoded.replay().unwrap();
+        assert_eq!(state.guest_damage, WIN_THRESHOLD);
+        assert_eq!(state.host_damage, 0);
+        let moves = (0..transcript.shots.len())
+            .map(|k| (k % 2 == 0, shots[k]))
+            .collect::<Vec<(bool, [u8; 2])>>();
+        assert_eq!(resolve(&host, &guest, &moves), decoded.winner);
+
+        // tampered winner and unknown version are rejected
+        let mut tampered = bytes.clone();
+        tampered[65] = 2;
+        assert!(GameTranscript::from_bytes(&tampered).unwrap().replay().is_err());
+        let mut versioned = bytes;
+        versioned[0] = TRANSCRIPT_VERSION + 1;
+        assert!(GameTranscript::from_bytes(&versioned).is_err());
+    }
+}